@@ -1,15 +1,10 @@
-use cosmwasm_schema::{cw_serde, write_api, QueryResponses};
-use cosmwasm_std::Empty;
-use infinity_builder::InstantiateMsg;
-
-#[cw_serde]
-#[derive(QueryResponses)]
-pub enum QueryMsg {}
+use cosmwasm_schema::write_api;
+use infinity_builder::{ExecuteMsg, InstantiateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
-        execute: Empty,
+        execute: ExecuteMsg,
         query: QueryMsg,
     }
 }
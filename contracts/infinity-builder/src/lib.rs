@@ -1,13 +1,22 @@
-use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    instantiate2_address, to_binary, Addr, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env,
-    Instantiate2AddressError, MessageInfo, StdError, StdResult, WasmMsg,
+    attr, ensure, instantiate2_address, to_binary, Addr, Binary, Coin, Decimal, Deps, DepsMut,
+    Empty, Env, Event, Instantiate2AddressError, MessageInfo, StdError, StdResult, WasmMsg,
 };
 use cw2::set_contract_version;
-use infinity_factory::msg::InstantiateMsg as InfinityFactoryInstantiateMsg;
-use infinity_global::{msg::InstantiateMsg as InfinityGlobalInstantiateMsg, GlobalConfig};
+use cw_storage_plus::Item;
+use infinity_factory::msg::{
+    InstantiateMsg as InfinityFactoryInstantiateMsg, SudoMsg as InfinityFactorySudoMsg,
+};
+use infinity_global::{
+    load_global_config,
+    msg::{InstantiateMsg as InfinityGlobalInstantiateMsg, SudoMsg as InfinityGlobalSudoMsg},
+    ConfigUpdate, GlobalConfig,
+};
 use infinity_index::msg::InstantiateMsg as InfinityIndexInstantiateMsg;
+use infinity_pair::msg::MigrateMsg as PairMigrateMsg;
 use infinity_router::msg::InstantiateMsg as InfinityRouterInstantiateMsg;
+use infinity_shared::InfinityError;
 use sg_std::Response;
 use sha2::{Digest, Sha256};
 use thiserror::Error;
@@ -25,6 +34,9 @@ pub enum ContractError {
 
     #[error("{0}")]
     Instantiate2AddressError(#[from] Instantiate2AddressError),
+
+    #[error("{0}")]
+    InfinityError(#[from] InfinityError),
 }
 
 #[cw_serde]
@@ -46,11 +58,70 @@ pub struct InstantiateMsg {
     pub default_royalty_fee_percent: Decimal,
     pub max_royalty_fee_percent: Decimal,
     pub max_swap_fee_percent: Decimal,
+    pub max_referral_fee_percent: Decimal,
     pub code_ids: CodeIds,
     pub min_prices: Vec<Coin>,
+    /// The admin of the deployed contracts, when unset the builder is the admin
+    /// so that it can migrate the suite
     pub admin: Option<String>,
 }
 
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Migrates the core contracts whose code ids have changed, only callable by the admin
+    /// of the builder. When the infinity pair code id differs from the pair code id of the
+    /// global config, the new code id is proposed to the global config and an unrestricted
+    /// migration from the current pair code id is registered in the factory. Both updates are
+    /// applied through the migrate entry points of the global and factory contracts, which
+    /// are migrated to their current code ids when those are unchanged. The factory only
+    /// migrates pairs to the new pair code id once the proposed config has been executed,
+    /// after the config timelock.
+    MigrateSuite {
+        code_ids: CodeIds,
+        /// The migrate msg sent to pairs migrating to the new pair code id
+        pair_migrate_msg: Option<PairMigrateMsg>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(DeployedContractsResponse)]
+    DeployedContracts {},
+}
+
+#[cw_serde]
+pub struct InfinityContracts {
+    pub infinity_global: Addr,
+    pub infinity_factory: Addr,
+    pub infinity_index: Addr,
+    pub infinity_router: Addr,
+}
+
+#[cw_serde]
+pub struct DeployedContract {
+    pub address: Addr,
+    pub code_id: u64,
+    pub contract_name: String,
+    pub contract_version: String,
+}
+
+#[cw_serde]
+pub struct DeployedContractsResponse {
+    pub infinity_global: DeployedContract,
+    pub infinity_factory: DeployedContract,
+    pub infinity_index: DeployedContract,
+    pub infinity_router: DeployedContract,
+    /// The pair code id of the global config, which only changes once a proposed
+    /// pair code id has been executed
+    pub infinity_pair_code_id: u64,
+}
+
+pub const INFINITY_CONTRACTS: Item<InfinityContracts> = Item::new("c");
+
+/// The code ids the suite was last deployed or migrated with
+pub const CODE_IDS: Item<CodeIds> = Item::new("i");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -88,10 +159,23 @@ pub fn instantiate(
         msg.code_ids.infinity_router,
     )?;
 
+    INFINITY_CONTRACTS.save(
+        deps.storage,
+        &InfinityContracts {
+            infinity_global: infinity_global.clone(),
+            infinity_factory: infinity_factory.clone(),
+            infinity_index: infinity_index.clone(),
+            infinity_router: infinity_router.clone(),
+        },
+    )?;
+    CODE_IDS.save(deps.storage, &msg.code_ids)?;
+
+    let admin = Some(msg.admin.unwrap_or(env.contract.address.to_string()));
+
     let mut response = Response::new();
 
     response = response.add_message(WasmMsg::Instantiate2 {
-        admin: msg.admin.clone(),
+        admin: admin.clone(),
         code_id: msg.code_ids.infinity_global,
         label: "Infinity Global".to_string(),
         msg: to_binary(&InfinityGlobalInstantiateMsg {
@@ -108,6 +192,8 @@ pub fn instantiate(
                 default_royalty_fee_percent: msg.default_royalty_fee_percent,
                 max_royalty_fee_percent: msg.max_royalty_fee_percent,
                 max_swap_fee_percent: msg.max_swap_fee_percent,
+                max_referral_fee_percent: msg.max_referral_fee_percent,
+                paused: false,
             },
             min_prices: msg.min_prices,
         })?,
@@ -116,7 +202,7 @@ pub fn instantiate(
     });
 
     response = response.add_message(WasmMsg::Instantiate2 {
-        admin: msg.admin.clone(),
+        admin: admin.clone(),
         code_id: msg.code_ids.infinity_factory,
         label: "Infinity Factory".to_string(),
         msg: to_binary(&InfinityFactoryInstantiateMsg {
@@ -127,7 +213,7 @@ pub fn instantiate(
     });
 
     response = response.add_message(WasmMsg::Instantiate2 {
-        admin: msg.admin.clone(),
+        admin: admin.clone(),
         code_id: msg.code_ids.infinity_index,
         label: "Infinity Index".to_string(),
         msg: to_binary(&InfinityIndexInstantiateMsg {
@@ -138,7 +224,7 @@ pub fn instantiate(
     });
 
     response = response.add_message(WasmMsg::Instantiate2 {
-        admin: msg.admin.clone(),
+        admin: admin.clone(),
         code_id: msg.code_ids.infinity_router,
         label: "Infinity Router".to_string(),
         msg: to_binary(&InfinityRouterInstantiateMsg {
@@ -184,15 +270,144 @@ pub fn generate_instantiate_2_addr(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    _msg: Empty,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    unimplemented!()
+    match msg {
+        ExecuteMsg::MigrateSuite {
+            code_ids,
+            pair_migrate_msg,
+        } => execute_migrate_suite(deps, env, info, code_ids, pair_migrate_msg),
+    }
+}
+
+pub fn execute_migrate_suite(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    code_ids: CodeIds,
+    pair_migrate_msg: Option<PairMigrateMsg>,
+) -> Result<Response, ContractError> {
+    let builder_info = deps.querier.query_wasm_contract_info(&env.contract.address)?;
+    ensure!(
+        builder_info.admin == Some(info.sender.to_string()),
+        InfinityError::Unauthorized("sender is not the admin of the builder".to_string())
+    );
+
+    let infinity_contracts = INFINITY_CONTRACTS.load(deps.storage)?;
+    let prev_code_ids = CODE_IDS.load(deps.storage)?;
+
+    // The pair code id is read from the global config, as it only changes there once a
+    // proposed pair code id has been executed
+    let global_config = load_global_config(&deps.querier, &infinity_contracts.infinity_global)?;
+    let prev_pair_code_id = global_config.infinity_pair_code_id;
+    let pair_code_id_changed = code_ids.infinity_pair != prev_pair_code_id;
+
+    let mut response = Response::new();
+    let mut event = Event::new("migrate-suite");
+
+    if pair_code_id_changed || code_ids.infinity_global != prev_code_ids.infinity_global {
+        let global_migrate_msg =
+            pair_code_id_changed.then(|| InfinityGlobalSudoMsg::ProposeConfig {
+                config_update: ConfigUpdate {
+                    infinity_pair_code_id: Some(code_ids.infinity_pair),
+                    ..ConfigUpdate::default()
+                },
+            });
+        response = response.add_message(migrate_msg(
+            &infinity_contracts.infinity_global,
+            code_ids.infinity_global,
+            to_binary(&global_migrate_msg)?,
+        ));
+        event =
+            event.add_attribute("infinity_global_code_id", code_ids.infinity_global.to_string());
+    }
+
+    if pair_code_id_changed || code_ids.infinity_factory != prev_code_ids.infinity_factory {
+        let factory_migrate_msg =
+            pair_code_id_changed.then(|| InfinityFactorySudoMsg::AddUnrestrictedMigration {
+                starting_code_id: prev_pair_code_id,
+                target_code_id: code_ids.infinity_pair,
+                migrate_msg: pair_migrate_msg,
+            });
+        response = response.add_message(migrate_msg(
+            &infinity_contracts.infinity_factory,
+            code_ids.infinity_factory,
+            to_binary(&factory_migrate_msg)?,
+        ));
+        event =
+            event.add_attribute("infinity_factory_code_id", code_ids.infinity_factory.to_string());
+    }
+
+    if code_ids.infinity_index != prev_code_ids.infinity_index {
+        response = response.add_message(migrate_msg(
+            &infinity_contracts.infinity_index,
+            code_ids.infinity_index,
+            to_binary(&Empty {})?,
+        ));
+        event = event.add_attribute("infinity_index_code_id", code_ids.infinity_index.to_string());
+    }
+
+    if code_ids.infinity_router != prev_code_ids.infinity_router {
+        response = response.add_message(migrate_msg(
+            &infinity_contracts.infinity_router,
+            code_ids.infinity_router,
+            to_binary(&Empty {})?,
+        ));
+        event =
+            event.add_attribute("infinity_router_code_id", code_ids.infinity_router.to_string());
+    }
+
+    if pair_code_id_changed {
+        event = event.add_attributes(vec![
+            attr("prev_infinity_pair_code_id", prev_pair_code_id.to_string()),
+            attr("infinity_pair_code_id", code_ids.infinity_pair.to_string()),
+        ]);
+    }
+
+    CODE_IDS.save(deps.storage, &code_ids)?;
+
+    Ok(response.add_event(event))
+}
+
+fn migrate_msg(contract_addr: &Addr, new_code_id: u64, msg: Binary) -> WasmMsg {
+    WasmMsg::Migrate {
+        contract_addr: contract_addr.to_string(),
+        new_code_id,
+        msg,
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
-    unimplemented!()
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::DeployedContracts {} => to_binary(&query_deployed_contracts(deps)?),
+    }
+}
+
+pub fn query_deployed_contracts(deps: Deps) -> StdResult<DeployedContractsResponse> {
+    let infinity_contracts = INFINITY_CONTRACTS.load(deps.storage)?;
+    let global_config = load_global_config(&deps.querier, &infinity_contracts.infinity_global)?;
+
+    Ok(DeployedContractsResponse {
+        infinity_global: query_deployed_contract(deps, infinity_contracts.infinity_global)?,
+        infinity_factory: query_deployed_contract(deps, infinity_contracts.infinity_factory)?,
+        infinity_index: query_deployed_contract(deps, infinity_contracts.infinity_index)?,
+        infinity_router: query_deployed_contract(deps, infinity_contracts.infinity_router)?,
+        infinity_pair_code_id: global_config.infinity_pair_code_id,
+    })
+}
+
+fn query_deployed_contract(deps: Deps, address: Addr) -> StdResult<DeployedContract> {
+    let contract_info = deps.querier.query_wasm_contract_info(&address)?;
+    let contract_version = cw2::query_contract_info(&deps.querier, address.to_string())?;
+
+    Ok(DeployedContract {
+        address,
+        code_id: contract_info.code_id,
+        contract_name: contract_version.contract,
+        contract_version: contract_version.version,
+    })
 }
@@ -1,16 +1,33 @@
-use crate::helpers::generate_salt;
+use crate::helpers::{
+    generate_instantiate_2_addr, migrate_pair_msg, num_pairs_after, register_pair,
+    validate_target_code_id,
+};
 use crate::msg::ExecuteMsg;
-use crate::state::{INFINITY_GLOBAL, SENDER_COUNTER, UNRESTRICTED_MIGRATIONS};
+use crate::reply::CREATE_PAIR_REPLY_ID;
+use crate::state::{
+    INFINITY_GLOBAL, MIGRATION_PROGRESS, PAIRS, PAIR_MIGRATE_MSGS, SENDER_COUNTER,
+    UNRESTRICTED_MIGRATIONS,
+};
 use crate::ContractError;
 
-use cosmwasm_std::{attr, ensure_eq, to_binary, DepsMut, Empty, Env, Event, MessageInfo, WasmMsg};
+use cosmwasm_std::{
+    attr, ensure, ensure_eq, to_binary, DepsMut, Env, Event, MessageInfo, Order, StdResult, SubMsg,
+    WasmMsg,
+};
+use cw_storage_plus::Bound;
 use infinity_global::load_global_config;
-use infinity_pair::msg::InstantiateMsg as InfinityPairInstantiateMsg;
+use infinity_pair::msg::{
+    ExecuteMsg as InfinityPairExecuteMsg, InstantiateMsg as InfinityPairInstantiateMsg,
+};
+use infinity_shared::InfinityError;
 use sg_std::Response;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
+pub const DEFAULT_MIGRATION_LIMIT: u32 = 10;
+pub const MAX_MIGRATION_LIMIT: u32 = 50;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -28,17 +45,21 @@ pub fn execute(
 
             let mut response = Response::new();
 
-            response = response.add_message(WasmMsg::Instantiate {
-                admin: Some(env.contract.address.into()),
-                code_id: global_config.infinity_pair_code_id,
-                label: "Infinity Pair".to_string(),
-                msg: to_binary(&InfinityPairInstantiateMsg {
-                    infinity_global: infinity_global.to_string(),
-                    pair_immutable,
-                    pair_config,
-                })?,
-                funds: info.funds,
-            });
+            // The pair is added to the pair registry once its address is known
+            response = response.add_submessage(SubMsg::reply_on_success(
+                WasmMsg::Instantiate {
+                    admin: Some(env.contract.address.into()),
+                    code_id: global_config.infinity_pair_code_id,
+                    label: "Infinity Pair".to_string(),
+                    msg: to_binary(&InfinityPairInstantiateMsg {
+                        infinity_global: infinity_global.to_string(),
+                        pair_immutable,
+                        pair_config,
+                    })?,
+                    funds: info.funds,
+                },
+                CREATE_PAIR_REPLY_ID,
+            ));
 
             // Event used by indexer to track pair creation
             response = response.add_event(
@@ -57,8 +78,15 @@ pub fn execute(
             let counter_key = (info.sender.clone(), global_config.infinity_pair_code_id);
             let counter =
                 SENDER_COUNTER.may_load(deps.storage, counter_key.clone())?.unwrap_or_default();
-            let salt = generate_salt(&info.sender, counter);
+            let (pair, salt) = generate_instantiate_2_addr(
+                deps.as_ref(),
+                &env,
+                &info.sender,
+                counter,
+                global_config.infinity_pair_code_id,
+            )?;
             SENDER_COUNTER.save(deps.storage, counter_key, &(counter + 1))?;
+            register_pair(deps.storage, &pair)?;
 
             let mut response = Response::new();
 
@@ -96,13 +124,18 @@ pub fn execute(
                 valid_target_code_id,
                 ContractError::InvalidMigration("Invalid target code id".to_string())
             );
+            validate_target_code_id(deps.as_ref(), target_code_id)?;
+
+            let migrate_msg = PAIR_MIGRATE_MSGS
+                .may_load(deps.storage, contract_info_response.code_id)?
+                .unwrap_or_default();
 
             let response = Response::new()
-                .add_message(WasmMsg::Migrate {
-                    contract_addr: pair_address.clone(),
-                    new_code_id: target_code_id,
-                    msg: to_binary(&Empty {})?,
-                })
+                .add_message(migrate_pair_msg(
+                    &deps.api.addr_validate(&pair_address)?,
+                    target_code_id,
+                    &migrate_msg,
+                )?)
                 .add_event(Event::new("factory-migrate-pair".to_string()).add_attributes(vec![
                     attr("pair_address", pair_address),
                     attr("target_code_id", target_code_id.to_string()),
@@ -110,5 +143,143 @@ pub fn execute(
 
             Ok(response)
         },
+        ExecuteMsg::RefreshPairQuotes {
+            pair_addresses,
+        } => {
+            ensure!(
+                !pair_addresses.is_empty(),
+                InfinityError::InvalidInput("pair addresses must not be empty".to_string())
+            );
+
+            let mut response = Response::new();
+            let mut event = Event::new("factory-refresh-pair-quotes".to_string());
+
+            for pair_address in pair_addresses {
+                let pair_address = deps.api.addr_validate(&pair_address)?;
+
+                // Only pairs administered by the factory can be refreshed
+                let contract_info_response =
+                    deps.querier.query_wasm_contract_info(&pair_address)?;
+                ensure_eq!(
+                    contract_info_response.admin,
+                    Some(env.contract.address.to_string()),
+                    InfinityError::InvalidInput(format!(
+                        "pair {} was not created by the factory",
+                        pair_address
+                    ))
+                );
+
+                response = response.add_message(WasmMsg::Execute {
+                    contract_addr: pair_address.to_string(),
+                    msg: to_binary(&InfinityPairExecuteMsg::RefreshQuotes {})?,
+                    funds: vec![],
+                });
+                event = event.add_attribute("pair_address", pair_address);
+            }
+
+            Ok(response.add_event(event))
+        },
+        ExecuteMsg::MigrateAllPairs {
+            from_code_id,
+            limit,
+            start_after,
+        } => {
+            let target_code_id = UNRESTRICTED_MIGRATIONS
+                .may_load(deps.storage, from_code_id)?
+                .ok_or(ContractError::InvalidMigration(
+                    "No unrestricted migration for code id".to_string(),
+                ))?;
+            validate_target_code_id(deps.as_ref(), target_code_id)?;
+
+            let migrate_msg =
+                PAIR_MIGRATE_MSGS.may_load(deps.storage, from_code_id)?.unwrap_or_default();
+
+            let mut progress =
+                MIGRATION_PROGRESS.may_load(deps.storage, from_code_id)?.unwrap_or_default();
+
+            let limit = limit.unwrap_or(DEFAULT_MIGRATION_LIMIT).min(MAX_MIGRATION_LIMIT) as usize;
+            let pairs = PAIRS
+                .range(
+                    deps.storage,
+                    start_after.or(progress.last_pair_id).map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            let mut response = Response::new();
+            let mut num_migrated = 0u64;
+
+            for (pair_id, pair_address) in pairs {
+                // Pairs already migrated, or no longer administered by the factory, are skipped
+                let contract_info_response =
+                    deps.querier.query_wasm_contract_info(&pair_address)?;
+                if contract_info_response.code_id == from_code_id
+                    && contract_info_response.admin == Some(env.contract.address.to_string())
+                {
+                    response = response.add_message(migrate_pair_msg(
+                        &pair_address,
+                        target_code_id,
+                        &migrate_msg,
+                    )?);
+                    num_migrated += 1;
+                }
+                progress.last_pair_id = Some(pair_id);
+            }
+
+            progress.num_migrated += num_migrated;
+            MIGRATION_PROGRESS.save(deps.storage, from_code_id, &progress)?;
+
+            let num_remaining = num_pairs_after(deps.storage, progress.last_pair_id)?;
+
+            let mut event =
+                Event::new("factory-migrate-all-pairs".to_string()).add_attributes(vec![
+                    attr("from_code_id", from_code_id.to_string()),
+                    attr("target_code_id", target_code_id.to_string()),
+                    attr("num_migrated", num_migrated.to_string()),
+                    attr("num_remaining", num_remaining.to_string()),
+                ]);
+            if let Some(last_pair_id) = progress.last_pair_id {
+                event = event.add_attribute("last_pair_id", last_pair_id.to_string());
+            }
+
+            Ok(response.add_event(event))
+        },
+        ExecuteMsg::RegisterPairs {
+            pair_addresses,
+        } => {
+            ensure!(
+                !pair_addresses.is_empty(),
+                InfinityError::InvalidInput("pair addresses must not be empty".to_string())
+            );
+
+            let mut event = Event::new("factory-register-pairs".to_string());
+
+            for pair_address in pair_addresses {
+                let pair_address = deps.api.addr_validate(&pair_address)?;
+
+                // Only pairs administered by the factory can be registered
+                let contract_info_response =
+                    deps.querier.query_wasm_contract_info(&pair_address)?;
+                ensure_eq!(
+                    contract_info_response.admin,
+                    Some(env.contract.address.to_string()),
+                    InfinityError::InvalidInput(format!(
+                        "pair {} was not created by the factory",
+                        pair_address
+                    ))
+                );
+
+                if let Some(pair_id) = register_pair(deps.storage, &pair_address)? {
+                    event = event.add_attributes(vec![
+                        attr("pair_address", pair_address),
+                        attr("pair_id", pair_id.to_string()),
+                    ]);
+                }
+            }
+
+            Ok(Response::new().add_event(event))
+        },
     }
 }
@@ -1,6 +1,12 @@
+use crate::state::{INFINITY_GLOBAL, PAIRS, PAIR_COUNT, PAIR_IDS};
 use crate::ContractError;
 
-use cosmwasm_std::{instantiate2_address, Addr, Binary, Deps, Env, Order};
+use cosmwasm_std::{
+    ensure_eq, instantiate2_address, to_binary, Addr, Binary, Deps, Env, Order, StdResult, Storage,
+    WasmMsg,
+};
+use infinity_global::load_global_config;
+use infinity_pair::msg::MigrateMsg as PairMigrateMsg;
 use sg_index_query::{QueryBound, QueryOptions, QueryOptionsInternal};
 use sha2::{Digest, Sha256};
 use std::cmp::{max, min};
@@ -35,6 +41,55 @@ pub fn generate_instantiate_2_addr(
     Ok((addr, salt))
 }
 
+/// Adds the pair to the pair registry, returning the id of the pair if it was not
+/// already registered
+pub fn register_pair(storage: &mut dyn Storage, pair: &Addr) -> StdResult<Option<u64>> {
+    if PAIR_IDS.has(storage, pair.clone()) {
+        return Ok(None);
+    }
+
+    let pair_id = PAIR_COUNT.may_load(storage)?.unwrap_or_default();
+    PAIRS.save(storage, pair_id, pair)?;
+    PAIR_IDS.save(storage, pair.clone(), &pair_id)?;
+    PAIR_COUNT.save(storage, &(pair_id + 1))?;
+
+    Ok(Some(pair_id))
+}
+
+/// The number of registered pairs after the given pair id
+pub fn num_pairs_after(storage: &dyn Storage, pair_id: Option<u64>) -> StdResult<u64> {
+    let num_pairs = PAIR_COUNT.may_load(storage)?.unwrap_or_default();
+    Ok(num_pairs.saturating_sub(pair_id.map_or(0, |pair_id| pair_id + 1)))
+}
+
+pub fn migrate_pair_msg(
+    pair_address: &Addr,
+    target_code_id: u64,
+    migrate_msg: &PairMigrateMsg,
+) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Migrate {
+        contract_addr: pair_address.to_string(),
+        new_code_id: target_code_id,
+        msg: to_binary(migrate_msg)?,
+    })
+}
+
+/// Validates that pairs are only migrated to the pair code id of the global config, so that
+/// a migration registered alongside a proposed pair code id only takes effect once the
+/// proposed config has been applied
+pub fn validate_target_code_id(deps: Deps, target_code_id: u64) -> Result<(), ContractError> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let global_config = load_global_config(&deps.querier, &infinity_global)?;
+    ensure_eq!(
+        target_code_id,
+        global_config.infinity_pair_code_id,
+        ContractError::InvalidMigration(
+            "Target code id is not the pair code id of the global config".to_string()
+        )
+    );
+    Ok(())
+}
+
 pub fn index_range_from_query_options(
     num_pairs: u64,
     query_options: QueryOptions<u64>,
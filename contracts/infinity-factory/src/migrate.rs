@@ -13,7 +13,11 @@ use sg_std::Response;
 use cosmwasm_std::entry_point;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(mut deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+pub fn migrate(
+    mut deps: DepsMut,
+    env: Env,
+    msg: Option<SudoMsg>,
+) -> Result<Response, ContractError> {
     let prev_contract_version = cw2::get_contract_version(deps.storage)?;
 
     let valid_contract_names = [CONTRACT_NAME.to_string()];
@@ -22,13 +26,20 @@ pub fn migrate(mut deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, Co
         StdError::generic_err("Invalid contract name for migration")
     );
 
+    // Migrating to the same contract version is only allowed to apply a sudo message, so
+    // that the builder can update the config without deploying new code
+    let prev_version = Version::parse(&prev_contract_version.version).unwrap();
+    let version = Version::parse(CONTRACT_VERSION).unwrap();
     ensure!(
-        Version::parse(&prev_contract_version.version).unwrap()
-            < Version::parse(CONTRACT_VERSION).unwrap(),
+        prev_version < version || (prev_version == version && msg.is_some()),
         StdError::generic_err("Must upgrade contract version")
     );
 
-    let mut response = sudo(deps.branch(), env, msg)?;
+    // The migrate message optionally carries a sudo message to apply during the migration
+    let mut response = match msg {
+        Some(msg) => sudo(deps.branch(), env, msg)?,
+        None => Response::new(),
+    };
 
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Binary, Uint128};
+use infinity_pair::msg::MigrateMsg as PairMigrateMsg;
 use infinity_pair::pair::Pair;
 use infinity_pair::state::{PairConfig, PairImmutable};
 use sg_index_query::QueryOptions;
@@ -27,9 +28,30 @@ pub enum ExecuteMsg {
     UnrestrictedMigratePair {
         /// The address of the pair to migrate
         pair_address: String,
-        /// The new code id to migrate to
+        /// The new code id to migrate to, must be the pair code id of the global config
         target_code_id: u64,
     },
+    /// Recompute the quotes of the pairs and update the indices, callable by anyone.
+    RefreshPairQuotes {
+        /// The addresses of the pairs to refresh, each must have been created by the factory
+        pair_addresses: Vec<String>,
+    },
+    /// Migrates the registered pairs in batches, once the target code id of the unrestricted
+    /// migration is the pair code id of the global config.
+    MigrateAllPairs {
+        /// The code id of the pairs to migrate, must have an unrestricted migration
+        from_code_id: u64,
+        /// The maximum number of registered pairs to walk in this batch,
+        /// each batch resumes where the previous batch stopped
+        limit: Option<u32>,
+        /// The pair id to start after, overriding where the previous batch stopped
+        start_after: Option<u64>,
+    },
+    RegisterPairs {
+        /// The addresses of pairs created before the pair registry existed,
+        /// each must have been created by the factory
+        pair_addresses: Vec<String>,
+    },
 }
 
 #[cw_serde]
@@ -68,6 +90,18 @@ pub enum QueryMsg {
     UnrestrictedMigrations {
         query_options: Option<QueryOptions<u64>>,
     },
+    #[returns(Option<PairMigrateMsg>)]
+    PairMigrateMsg {
+        starting_code_id: u64,
+    },
+    #[returns(Vec<(u64, Addr)>)]
+    Pairs {
+        query_options: Option<QueryOptions<u64>>,
+    },
+    #[returns(MigrationProgressResponse)]
+    MigrationProgress {
+        from_code_id: u64,
+    },
 }
 
 #[cw_serde]
@@ -79,11 +113,22 @@ pub struct QuotesResponse {
 
 pub type UnrestrictedMigrationsResponse = Vec<(u64, u64)>;
 
+#[cw_serde]
+pub struct MigrationProgressResponse {
+    pub from_code_id: u64,
+    pub target_code_id: Option<u64>,
+    pub last_pair_id: Option<u64>,
+    pub num_migrated: u64,
+    pub num_remaining: u64,
+}
+
 #[cw_serde]
 pub enum SudoMsg {
     AddUnrestrictedMigration {
         starting_code_id: u64,
         target_code_id: u64,
+        /// The migrate msg sent to each migrated pair, used to transform the pair state
+        migrate_msg: Option<PairMigrateMsg>,
     },
     RemoveUnrestrictedMigration {
         starting_code_id: u64,
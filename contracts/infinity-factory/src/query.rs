@@ -1,11 +1,17 @@
-use crate::helpers::{generate_instantiate_2_addr, index_range_from_query_options};
-use crate::msg::{NextPairResponse, QueryMsg, QuotesResponse};
-use crate::state::{INFINITY_GLOBAL, SENDER_COUNTER, UNRESTRICTED_MIGRATIONS};
+use crate::helpers::{
+    generate_instantiate_2_addr, index_range_from_query_options, num_pairs_after,
+};
+use crate::msg::{MigrationProgressResponse, NextPairResponse, QueryMsg, QuotesResponse};
+use crate::state::{
+    INFINITY_GLOBAL, MIGRATION_PROGRESS, PAIRS, PAIR_MIGRATE_MSGS, SENDER_COUNTER,
+    UNRESTRICTED_MIGRATIONS,
+};
 
 use cosmwasm_std::{to_binary, Addr, Binary, Deps, Env, StdError, StdResult, Uint128};
 use infinity_global::{load_global_config, GlobalConfig};
-use infinity_pair::helpers::load_payout_context;
+use infinity_pair::helpers::{load_payout_context, SimNftPrices};
 use infinity_pair::pair::Pair;
+use infinity_pair::state::PairStats;
 use sg_index_query::{QueryOptions, QueryOptionsInternal};
 
 #[cfg(not(feature = "library"))]
@@ -39,6 +45,15 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::UnrestrictedMigrations {
             query_options,
         } => to_binary(&query_unrestricted_migrations(deps, query_options.unwrap_or_default())?),
+        QueryMsg::PairMigrateMsg {
+            starting_code_id,
+        } => to_binary(&PAIR_MIGRATE_MSGS.may_load(deps.storage, starting_code_id)?),
+        QueryMsg::Pairs {
+            query_options,
+        } => to_binary(&query_pairs(deps, query_options.unwrap_or_default())?),
+        QueryMsg::MigrationProgress {
+            from_code_id,
+        } => to_binary(&query_migration_progress(deps, from_code_id)?),
     }
 }
 
@@ -101,8 +116,12 @@ pub fn query_sim_sell_to_pair_swaps(
     )
     .map_err(|_| StdError::generic_err("failed to load payout context".to_string()))?;
 
+    // The provided pair is simulated without a trading history or listing prices
+    let mut pair_stats = PairStats::default();
+    let mut sim_nft_prices = SimNftPrices::unlisted(pair.internal.total_nfts);
+
     pair.update_sell_to_pair_quote_summary(&payout_context);
-    pair.update_buy_from_pair_quote_summary(&payout_context);
+    pair.update_nft_buy_from_pair_quote_summary(&payout_context, &sim_nft_prices.cheapest());
 
     let mut sell_to_pair_quotes: Vec<Uint128> = vec![];
     let mut buy_from_pair_quotes: Vec<Uint128> = vec![];
@@ -120,7 +139,7 @@ pub fn query_sim_sell_to_pair_swaps(
             break;
         }
 
-        pair.sim_swap_nft_for_tokens(&payout_context);
+        pair.sim_swap_nft_for_tokens(&payout_context, &mut pair_stats, &mut sim_nft_prices);
 
         idx += 1;
     }
@@ -146,8 +165,12 @@ pub fn query_sim_buy_from_pair_swaps(
     )
     .map_err(|_| StdError::generic_err("failed to load payout context".to_string()))?;
 
+    // The provided pair is simulated without a trading history or listing prices
+    let mut pair_stats = PairStats::default();
+    let mut sim_nft_prices = SimNftPrices::unlisted(pair.internal.total_nfts);
+
     pair.update_sell_to_pair_quote_summary(&payout_context);
-    pair.update_buy_from_pair_quote_summary(&payout_context);
+    pair.update_nft_buy_from_pair_quote_summary(&payout_context, &sim_nft_prices.cheapest());
 
     let mut sell_to_pair_quotes: Vec<Uint128> = vec![];
     let mut buy_from_pair_quotes: Vec<Uint128> = vec![];
@@ -165,7 +188,7 @@ pub fn query_sim_buy_from_pair_swaps(
             break;
         }
 
-        pair.sim_swap_tokens_for_nft(&payout_context);
+        pair.sim_swap_tokens_for_nft(&payout_context, &mut pair_stats, &mut sim_nft_prices);
 
         idx += 1;
     }
@@ -195,3 +218,34 @@ pub fn query_unrestricted_migrations(
 
     Ok(results)
 }
+
+pub fn query_pairs(deps: Deps, query_options: QueryOptions<u64>) -> StdResult<Vec<(u64, Addr)>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| *offset), None, None);
+
+    let results =
+        PAIRS.range(deps.storage, min, max, order).take(limit).collect::<StdResult<Vec<_>>>()?;
+
+    Ok(results)
+}
+
+pub fn query_migration_progress(
+    deps: Deps,
+    from_code_id: u64,
+) -> StdResult<MigrationProgressResponse> {
+    let target_code_id = UNRESTRICTED_MIGRATIONS.may_load(deps.storage, from_code_id)?;
+    let progress = MIGRATION_PROGRESS.may_load(deps.storage, from_code_id)?.unwrap_or_default();
+    let num_remaining = num_pairs_after(deps.storage, progress.last_pair_id)?;
+
+    Ok(MigrationProgressResponse {
+        from_code_id,
+        target_code_id,
+        last_pair_id: progress.last_pair_id,
+        num_migrated: progress.num_migrated,
+        num_remaining,
+    })
+}
@@ -0,0 +1,35 @@
+use crate::helpers::register_pair;
+use crate::ContractError;
+
+use cosmwasm_std::{DepsMut, Env, Event, Reply};
+use cw_utils::parse_reply_instantiate_data;
+use infinity_shared::InfinityError;
+use sg_std::Response;
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+pub const CREATE_PAIR_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        CREATE_PAIR_REPLY_ID => {
+            let instantiate_response = parse_reply_instantiate_data(msg)
+                .map_err(|err| InfinityError::InternalError(err.to_string()))?;
+            let pair_address = deps.api.addr_validate(&instantiate_response.contract_address)?;
+
+            let mut response = Response::new();
+            if let Some(pair_id) = register_pair(deps.storage, &pair_address)? {
+                response = response.add_event(
+                    Event::new("factory-register-pair".to_string())
+                        .add_attribute("pair_address", pair_address)
+                        .add_attribute("pair_id", pair_id.to_string()),
+                );
+            }
+
+            Ok(response)
+        },
+        id => Err(InfinityError::InternalError(format!("unknown reply id {}", id)).into()),
+    }
+}
@@ -1,5 +1,7 @@
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Addr;
 use cw_storage_plus::{Item, Map};
+use infinity_pair::msg::MigrateMsg as PairMigrateMsg;
 
 pub const INFINITY_GLOBAL: Item<Addr> = Item::new("g");
 
@@ -10,3 +12,29 @@ pub const SENDER_COUNTER: Map<(Addr, u64), u64> = Map::new("s");
 // This is a map of code ids that are allowed to migrate to subsequent code ids.
 // This set of migrations can be invoked by anyone.
 pub const UNRESTRICTED_MIGRATIONS: Map<u64, u64> = Map::new("um");
+
+// code_id => migrate msg
+// The governance approved migrate msg sent to pairs migrating away from the code id.
+// When unset, pairs are migrated with the default migrate msg.
+pub const PAIR_MIGRATE_MSGS: Map<u64, PairMigrateMsg> = Map::new("mm");
+
+// pair_id => pair address
+// This is a registry of the pairs created by the factory, used to walk all pairs.
+pub const PAIRS: Map<u64, Addr> = Map::new("p");
+
+// pair address => pair_id
+pub const PAIR_IDS: Map<Addr, u64> = Map::new("pi");
+
+pub const PAIR_COUNT: Item<u64> = Item::new("pc");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrationProgress {
+    /// The id of the last registered pair that was walked
+    pub last_pair_id: Option<u64>,
+    /// The number of pairs migrated so far
+    pub num_migrated: u64,
+}
+
+// from_code_id => progress of migrating the registered pairs away from the code id
+pub const MIGRATION_PROGRESS: Map<u64, MigrationProgress> = Map::new("mp");
@@ -1,8 +1,9 @@
 use crate::msg::SudoMsg;
-use crate::state::UNRESTRICTED_MIGRATIONS;
+use crate::state::{MIGRATION_PROGRESS, PAIR_MIGRATE_MSGS, UNRESTRICTED_MIGRATIONS};
 use crate::ContractError;
 
-use cosmwasm_std::{attr, ensure, ensure_eq, DepsMut, Env, Event};
+use cosmwasm_std::{attr, ensure, ensure_eq, to_binary, DepsMut, Env, Event};
+use infinity_pair::msg::MigrateMsg as PairMigrateMsg;
 use infinity_shared::InfinityError;
 use sg_std::Response;
 
@@ -14,7 +15,8 @@ pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, Contract
         SudoMsg::AddUnrestrictedMigration {
             starting_code_id,
             target_code_id,
-        } => sudo_add_unrestricted_migration(deps, starting_code_id, target_code_id),
+            migrate_msg,
+        } => sudo_add_unrestricted_migration(deps, starting_code_id, target_code_id, migrate_msg),
         SudoMsg::RemoveUnrestrictedMigration {
             starting_code_id,
         } => sudo_remove_unrestricted_migration(deps, starting_code_id),
@@ -25,6 +27,7 @@ pub fn sudo_add_unrestricted_migration(
     deps: DepsMut,
     starting_code_id: u64,
     target_code_id: u64,
+    migrate_msg: Option<PairMigrateMsg>,
 ) -> Result<Response, ContractError> {
     let existing_migration = UNRESTRICTED_MIGRATIONS.may_load(deps.storage, starting_code_id)?;
     ensure_eq!(
@@ -34,13 +37,20 @@ pub fn sudo_add_unrestricted_migration(
     );
 
     UNRESTRICTED_MIGRATIONS.save(deps.storage, starting_code_id, &target_code_id)?;
+    // A new migration walks the pair registry from the start
+    MIGRATION_PROGRESS.remove(deps.storage, starting_code_id);
 
-    let response = Response::new().add_event(
-        Event::new("sudo-add-unrestricted-migration").add_attributes(vec![
-            attr("starting_code_id", starting_code_id.to_string()),
-            attr("target_code_id", target_code_id.to_string()),
-        ]),
-    );
+    let mut event = Event::new("sudo-add-unrestricted-migration").add_attributes(vec![
+        attr("starting_code_id", starting_code_id.to_string()),
+        attr("target_code_id", target_code_id.to_string()),
+    ]);
+
+    if let Some(migrate_msg) = migrate_msg {
+        event = event.add_attribute("migrate_msg", to_binary(&migrate_msg)?.to_base64());
+        PAIR_MIGRATE_MSGS.save(deps.storage, starting_code_id, &migrate_msg)?;
+    }
+
+    let response = Response::new().add_event(event);
 
     Ok(response)
 }
@@ -56,6 +66,8 @@ pub fn sudo_remove_unrestricted_migration(
     );
 
     UNRESTRICTED_MIGRATIONS.remove(deps.storage, starting_code_id);
+    PAIR_MIGRATE_MSGS.remove(deps.storage, starting_code_id);
+    MIGRATION_PROGRESS.remove(deps.storage, starting_code_id);
 
     let response = Response::new().add_event(
         Event::new("sudo-remove-unrestricted-migration")
@@ -1,11 +1,10 @@
 use cosmwasm_schema::write_api;
-use cosmwasm_std::Empty;
-use infinity_global::msg::{InstantiateMsg, QueryMsg, SudoMsg};
+use infinity_global::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
-        execute: Empty,
+        execute: ExecuteMsg,
         query: QueryMsg,
         sudo: SudoMsg
     }
@@ -1,2 +1,5 @@
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The default delay between proposing and executing a config update, two days
+pub const DEFAULT_CONFIG_TIMELOCK_SECONDS: u64 = 172_800;
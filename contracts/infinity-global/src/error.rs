@@ -1,8 +1,35 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Decimal, StdError};
+use infinity_shared::InfinityError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
+
+    #[error("{0}")]
+    InfinityError(#[from] InfinityError),
+
+    #[error("{field} must be less than or equal to one, got {fee_percent}")]
+    FeePercentTooHigh {
+        field: String,
+        fee_percent: Decimal,
+    },
+
+    #[error("default royalty fee percent {0} exceeds max royalty fee percent {1}")]
+    DefaultRoyaltyExceedsMax(Decimal, Decimal),
+
+    #[error("max fee percents must sum to at most one, got {total_fee_percent}")]
+    TotalFeePercentTooHigh {
+        total_fee_percent: Decimal,
+    },
+
+    #[error("infinity pair code id {0} does not exist")]
+    InvalidCodeId(u64),
+
+    #[error("{field} is not a contract: {address}")]
+    NotAContract {
+        field: String,
+        address: String,
+    },
 }
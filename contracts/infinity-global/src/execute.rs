@@ -1,6 +1,13 @@
+use crate::helpers::{save_config_version, validate_config_update};
+use crate::msg::ExecuteMsg;
+use crate::state::{
+    RoyaltySettings, CONFIG_TIMELOCK_SECONDS, GLOBAL_CONFIG, PENDING_CONFIG, ROYALTY_SETTINGS,
+};
 use crate::ContractError;
 
-use cosmwasm_std::{DepsMut, Empty, Env, MessageInfo};
+use cosmwasm_std::{attr, ensure, ensure_eq, Addr, Decimal, DepsMut, Env, Event, MessageInfo};
+use infinity_shared::InfinityError;
+use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
 use sg_std::Response;
 
 #[cfg(not(feature = "library"))]
@@ -8,10 +15,151 @@ use cosmwasm_std::entry_point;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    _msg: Empty,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    unimplemented!()
+    let api = deps.api;
+
+    match msg {
+        ExecuteMsg::SetRoyaltySettings {
+            collection,
+            royalty_settings,
+        } => execute_set_royalty_settings(
+            deps,
+            info,
+            api.addr_validate(&collection)?,
+            royalty_settings.map(|rs| rs.str_to_addr(api)).transpose()?,
+        ),
+        ExecuteMsg::ExecutePendingConfig {} => execute_pending_config(deps, env, info),
+    }
+}
+
+pub fn execute_set_royalty_settings(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: Addr,
+    royalty_settings: Option<RoyaltySettings<Addr>>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.funds.is_empty(),
+        InfinityError::InvalidInput("message does not accept funds".to_string())
+    );
+
+    let collection_info = deps.querier.query_wasm_smart::<CollectionInfoResponse>(
+        &collection,
+        &Sg721QueryMsg::CollectionInfo {},
+    )?;
+    ensure_eq!(
+        info.sender,
+        collection_info.creator,
+        InfinityError::Unauthorized("sender is not the creator of the collection".to_string())
+    );
+
+    let mut event =
+        Event::new("set-royalty-settings").add_attribute("collection", collection.to_string());
+
+    match royalty_settings {
+        Some(royalty_settings) => {
+            validate_royalty_settings(&royalty_settings)?;
+
+            for split in &royalty_settings.splits {
+                event = event.add_attributes(vec![
+                    attr("recipient", split.recipient.to_string()),
+                    attr("weight", split.weight.to_string()),
+                ]);
+            }
+            if let Some(min_royalty_fee_percent) = royalty_settings.min_royalty_fee_percent {
+                event = event
+                    .add_attribute("min_royalty_fee_percent", min_royalty_fee_percent.to_string());
+            }
+
+            ROYALTY_SETTINGS.save(deps.storage, collection, &royalty_settings)?;
+        },
+        None => {
+            ROYALTY_SETTINGS.remove(deps.storage, collection);
+        },
+    }
+
+    Ok(Response::new().add_event(event))
+}
+
+pub fn execute_pending_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.funds.is_empty(),
+        InfinityError::InvalidInput("message does not accept funds".to_string())
+    );
+
+    let pending_config = PENDING_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(InfinityError::InvalidInput("no pending config to execute".to_string()))?;
+    ensure!(
+        env.block.time >= pending_config.executable_at,
+        InfinityError::InvalidInput("pending config timelock has not elapsed".to_string())
+    );
+    PENDING_CONFIG.remove(deps.storage);
+
+    let mut config = GLOBAL_CONFIG.load(deps.storage)?;
+    validate_config_update(&deps.querier, &config, &pending_config.config_update)?;
+
+    let config_timelock_seconds = pending_config.config_update.config_timelock_seconds;
+    let attributes = pending_config.config_update.apply(&mut config);
+    GLOBAL_CONFIG.save(deps.storage, &config)?;
+
+    // An update of the config timelock alone leaves the global config unchanged
+    let changed_fields = attributes.iter().map(|attr| attr.key.clone()).collect::<Vec<String>>();
+    if !changed_fields.is_empty() {
+        save_config_version(deps.storage, env.block.height, &config, changed_fields)?;
+    }
+
+    let mut event = Event::new("execute-pending-config").add_attributes(attributes);
+
+    if let Some(config_timelock_seconds) = config_timelock_seconds {
+        CONFIG_TIMELOCK_SECONDS.save(deps.storage, &config_timelock_seconds)?;
+        event = event.add_attribute("config_timelock_seconds", config_timelock_seconds.to_string());
+    }
+
+    Ok(Response::new().add_event(event))
+}
+
+pub fn validate_royalty_settings(
+    royalty_settings: &RoyaltySettings<Addr>,
+) -> Result<(), InfinityError> {
+    if !royalty_settings.splits.is_empty() {
+        ensure!(
+            royalty_settings.splits.iter().all(|split| !split.weight.is_zero()),
+            InfinityError::InvalidInput(
+                "royalty split weight must be greater than zero".to_string()
+            )
+        );
+
+        let total_weight = royalty_settings
+            .splits
+            .iter()
+            .try_fold(Decimal::zero(), |acc, split| acc.checked_add(split.weight))
+            .map_err(|_| {
+                InfinityError::InvalidInput("invalid royalty split weights".to_string())
+            })?;
+        ensure_eq!(
+            total_weight,
+            Decimal::one(),
+            InfinityError::InvalidInput("royalty split weights must sum to one".to_string())
+        );
+    }
+
+    if let Some(min_royalty_fee_percent) = royalty_settings.min_royalty_fee_percent {
+        ensure!(
+            min_royalty_fee_percent <= Decimal::one(),
+            InfinityError::InvalidInput(
+                "min royalty fee percent must be less than or equal to one".to_string()
+            )
+        );
+    }
+
+    Ok(())
 }
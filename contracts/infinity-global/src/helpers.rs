@@ -1,6 +1,12 @@
-use crate::{msg::QueryMsg, state::GlobalConfig};
+use crate::{
+    msg::QueryMsg,
+    state::{
+        ConfigUpdate, ConfigVersion, GlobalConfig, RoyaltySettings, CONFIG_HISTORY, MIN_PRICES,
+    },
+    ContractError,
+};
 
-use cosmwasm_std::{Addr, Coin, QuerierWrapper, StdResult};
+use cosmwasm_std::{coin, ensure, Addr, Coin, Decimal, Order, QuerierWrapper, StdResult, Storage};
 
 pub fn load_global_config(
     querier: &QuerierWrapper,
@@ -21,3 +27,161 @@ pub fn load_min_price(
         },
     )
 }
+
+pub fn load_global_config_at_height(
+    querier: &QuerierWrapper,
+    infinity_global: &Addr,
+    height: u64,
+) -> StdResult<Option<ConfigVersion>> {
+    querier.query_wasm_smart::<Option<ConfigVersion>>(
+        infinity_global,
+        &QueryMsg::ConfigAtHeight {
+            height,
+        },
+    )
+}
+
+/// Appends the global config and the current min prices to the config history. Updates within
+/// the same block as the latest version are merged into that version.
+pub fn save_config_version(
+    storage: &mut dyn Storage,
+    height: u64,
+    global_config: &GlobalConfig<Addr>,
+    changed_fields: Vec<String>,
+) -> StdResult<()> {
+    let min_prices = MIN_PRICES
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(denom, amount)| coin(amount.u128(), denom)))
+        .collect::<StdResult<Vec<Coin>>>()?;
+
+    let latest_version = CONFIG_HISTORY
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map(|(_, config_version)| config_version);
+
+    let config_version = match latest_version {
+        Some(mut config_version) if config_version.height == height => {
+            for field in changed_fields {
+                if !config_version.changed_fields.contains(&field) {
+                    config_version.changed_fields.push(field);
+                }
+            }
+            config_version.global_config = global_config.clone();
+            config_version.min_prices = min_prices;
+            config_version
+        },
+        latest_version => ConfigVersion {
+            version: latest_version.map_or(1u64, |config_version| config_version.version + 1),
+            height,
+            changed_fields,
+            global_config: global_config.clone(),
+            min_prices,
+        },
+    };
+
+    CONFIG_HISTORY.save(storage, height, &config_version)
+}
+
+pub fn load_royalty_settings(
+    querier: &QuerierWrapper,
+    infinity_global: &Addr,
+    collection: &Addr,
+) -> StdResult<Option<RoyaltySettings<Addr>>> {
+    querier.query_wasm_smart::<Option<RoyaltySettings<Addr>>>(
+        infinity_global,
+        &QueryMsg::RoyaltySettings {
+            collection: collection.to_string(),
+        },
+    )
+}
+
+/// Validates that the fee percents of the global config are consistent with each other, so that
+/// the fees charged on a sale can never exceed the sale amount
+pub fn validate_global_config(global_config: &GlobalConfig<Addr>) -> Result<(), ContractError> {
+    let fee_percents = [
+        ("fair_burn_fee_percent", global_config.fair_burn_fee_percent),
+        ("default_royalty_fee_percent", global_config.default_royalty_fee_percent),
+        ("max_royalty_fee_percent", global_config.max_royalty_fee_percent),
+        ("max_swap_fee_percent", global_config.max_swap_fee_percent),
+        ("max_referral_fee_percent", global_config.max_referral_fee_percent),
+    ];
+    for (field, fee_percent) in fee_percents {
+        ensure!(
+            fee_percent <= Decimal::one(),
+            ContractError::FeePercentTooHigh {
+                field: field.to_string(),
+                fee_percent,
+            }
+        );
+    }
+
+    ensure!(
+        global_config.default_royalty_fee_percent <= global_config.max_royalty_fee_percent,
+        ContractError::DefaultRoyaltyExceedsMax(
+            global_config.default_royalty_fee_percent,
+            global_config.max_royalty_fee_percent,
+        )
+    );
+
+    // The fair burn, royalty and swap fees are deducted from the sale amount of a swap,
+    // and the referral fee is deducted from the proceeds of the seller
+    let total_fee_percent = global_config.fair_burn_fee_percent
+        + global_config.max_royalty_fee_percent
+        + global_config.max_swap_fee_percent
+        + global_config.max_referral_fee_percent;
+    ensure!(
+        total_fee_percent <= Decimal::one(),
+        ContractError::TotalFeePercentTooHigh {
+            total_fee_percent,
+        }
+    );
+
+    ensure!(
+        global_config.infinity_pair_code_id > 0,
+        ContractError::InvalidCodeId(global_config.infinity_pair_code_id)
+    );
+
+    Ok(())
+}
+
+/// Validates that the code id has been stored on chain
+pub fn validate_code_id(querier: &QuerierWrapper, code_id: u64) -> Result<(), ContractError> {
+    querier.query_wasm_code_info(code_id).map_err(|_| ContractError::InvalidCodeId(code_id))?;
+    Ok(())
+}
+
+/// Validates the global config that results from applying the config update, checking that
+/// the updated code id exists and that each updated address belongs to a contract
+pub fn validate_config_update(
+    querier: &QuerierWrapper,
+    global_config: &GlobalConfig<Addr>,
+    config_update: &ConfigUpdate<Addr>,
+) -> Result<(), ContractError> {
+    let mut next_global_config = global_config.clone();
+    config_update.clone().apply(&mut next_global_config);
+    validate_global_config(&next_global_config)?;
+
+    if let Some(infinity_pair_code_id) = config_update.infinity_pair_code_id {
+        validate_code_id(querier, infinity_pair_code_id)?;
+    }
+
+    let addresses = [
+        ("fair_burn", &config_update.fair_burn),
+        ("royalty_registry", &config_update.royalty_registry),
+        ("marketplace", &config_update.marketplace),
+        ("infinity_factory", &config_update.infinity_factory),
+        ("infinity_index", &config_update.infinity_index),
+        ("infinity_router", &config_update.infinity_router),
+    ];
+    for (field, address) in addresses {
+        if let Some(address) = address {
+            querier.query_wasm_contract_info(address).map_err(|_| ContractError::NotAContract {
+                field: field.to_string(),
+                address: address.to_string(),
+            })?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use crate::{
     constants::{CONTRACT_NAME, CONTRACT_VERSION},
+    helpers::{save_config_version, validate_code_id, validate_global_config},
     msg::InstantiateMsg,
     state::{GLOBAL_CONFIG, MIN_PRICES},
+    ContractError,
 };
 
 use cosmwasm_std::{DepsMut, Env, MessageInfo, StdError};
@@ -14,23 +16,28 @@ use cosmwasm_std::entry_point;
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> Result<Response, StdError> {
+) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     let global_config = msg.global_config.str_to_addr(deps.api)?;
+    validate_global_config(&global_config)?;
+    validate_code_id(&deps.querier, global_config.infinity_pair_code_id)?;
+
     GLOBAL_CONFIG.save(deps.storage, &global_config)?;
 
     for min_price in msg.min_prices {
         if MIN_PRICES.has(deps.storage, min_price.denom.clone()) {
-            return Err(StdError::generic_err("Duplicate min price"));
+            return Err(StdError::generic_err("Duplicate min price").into());
         } else {
             MIN_PRICES.save(deps.storage, min_price.denom, &min_price.amount)?;
         }
     }
 
+    save_config_version(deps.storage, env.block.height, &global_config, vec![])?;
+
     Ok(Response::new()
         .add_attribute("action", "instantiate")
         .add_attribute("contract_name", CONTRACT_NAME)
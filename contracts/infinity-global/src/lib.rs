@@ -11,5 +11,9 @@ pub mod sudo;
 mod error;
 
 pub use error::ContractError;
-pub use helpers::{load_global_config, load_min_price};
-pub use state::GlobalConfig;
+pub use helpers::{
+    load_global_config, load_global_config_at_height, load_min_price, load_royalty_settings,
+};
+pub use state::{
+    ConfigUpdate, ConfigVersion, GlobalConfig, PendingConfig, RoyaltySettings, RoyaltySplit,
+};
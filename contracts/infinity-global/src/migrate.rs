@@ -1,10 +1,13 @@
 use crate::{
     constants::{CONTRACT_NAME, CONTRACT_VERSION},
+    helpers::save_config_version,
     msg::SudoMsg,
+    state::{CONFIG_HISTORY, GLOBAL_CONFIG},
     sudo::sudo,
+    ContractError,
 };
 
-use cosmwasm_std::{ensure, DepsMut, Env, Event, StdError};
+use cosmwasm_std::{ensure, DepsMut, Env, Event, Order, StdError};
 use semver::Version;
 use sg_std::Response;
 
@@ -13,7 +16,11 @@ use cosmwasm_std::entry_point;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 #[allow(clippy::cmp_owned)]
-pub fn migrate(mut deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, StdError> {
+pub fn migrate(
+    mut deps: DepsMut,
+    env: Env,
+    msg: Option<SudoMsg>,
+) -> Result<Response, ContractError> {
     let prev_contract_version = cw2::get_contract_version(deps.storage)?;
 
     let valid_contract_names = [CONTRACT_NAME.to_string()];
@@ -22,13 +29,27 @@ pub fn migrate(mut deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, St
         StdError::generic_err("Invalid contract name for migration")
     );
 
+    // Migrating to the same contract version is only allowed to apply a sudo message, so
+    // that the builder can update the config without deploying new code
+    let prev_version = Version::parse(&prev_contract_version.version).unwrap();
+    let version = Version::parse(CONTRACT_VERSION).unwrap();
     ensure!(
-        Version::parse(&prev_contract_version.version).unwrap()
-            < Version::parse(CONTRACT_VERSION).unwrap(),
+        prev_version < version || (prev_version == version && msg.is_some()),
         StdError::generic_err("Must upgrade contract version")
     );
 
-    let mut response = sudo(deps.branch(), env, msg)?;
+    // Contracts deployed before the config history was introduced begin their history with
+    // the config at the time of migration
+    if CONFIG_HISTORY.keys(deps.storage, None, None, Order::Ascending).next().is_none() {
+        let global_config = GLOBAL_CONFIG.load(deps.storage)?;
+        save_config_version(deps.storage, env.block.height, &global_config, vec![])?;
+    }
+
+    // The migrate message optionally carries a sudo message to apply during the migration
+    let mut response = match msg {
+        Some(msg) => sudo(deps.branch(), env, msg)?,
+        None => Response::new(),
+    };
 
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
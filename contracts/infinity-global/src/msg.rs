@@ -1,7 +1,8 @@
-use crate::state::GlobalConfig;
+use crate::state::{ConfigUpdate, ConfigVersion, GlobalConfig, PendingConfig, RoyaltySettings};
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin, Decimal};
+use cosmwasm_std::{Addr, Coin};
+use sg_index_query::QueryOptions;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -9,6 +10,17 @@ pub struct InstantiateMsg {
     pub min_prices: Vec<Coin>,
 }
 
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Set or remove the royalty settings for a collection, only callable by the collection creator
+    SetRoyaltySettings {
+        collection: String,
+        royalty_settings: Option<RoyaltySettings<String>>,
+    },
+    /// Apply the pending config update once its timelock has elapsed, callable by anyone
+    ExecutePendingConfig {},
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -18,24 +30,50 @@ pub enum QueryMsg {
     MinPrice {
         denom: String,
     },
+    #[returns(Vec<Coin>)]
+    MinPrices {
+        query_options: Option<QueryOptions<String>>,
+    },
+    #[returns(Vec<String>)]
+    SupportedDenoms {
+        query_options: Option<QueryOptions<String>>,
+    },
+    /// The global config versions, keyed by the block height at which they were set.
+    /// A version is recorded whenever the global config or the min prices change.
+    #[returns(Vec<ConfigVersion>)]
+    ConfigHistory {
+        query_options: Option<QueryOptions<u64>>,
+    },
+    /// The global config version, including the min prices, that applied at the given block height
+    #[returns(Option<ConfigVersion>)]
+    ConfigAtHeight {
+        height: u64,
+    },
+    /// The proposed config update waiting out the config timelock
+    #[returns(Option<PendingConfig>)]
+    PendingConfig {},
+    /// The number of seconds a proposed config update must wait before it can be executed
+    #[returns(u64)]
+    ConfigTimelock {},
+    #[returns(Option<RoyaltySettings<Addr>>)]
+    RoyaltySettings {
+        collection: String,
+    },
 }
 
 #[cw_serde]
 #[allow(clippy::large_enum_variant)]
 pub enum SudoMsg {
-    UpdateConfig {
-        fair_burn: Option<String>,
-        royalty_registry: Option<String>,
-        marketplace: Option<String>,
-        infinity_factory: Option<String>,
-        infinity_index: Option<String>,
-        infinity_router: Option<String>,
-        infinity_pair_code_id: Option<u64>,
-        pair_creation_fee: Option<Coin>,
-        fair_burn_fee_percent: Option<Decimal>,
-        default_royalty_fee_percent: Option<Decimal>,
-        max_royalty_fee_percent: Option<Decimal>,
-        max_swap_fee_percent: Option<Decimal>,
+    /// Propose a config update, executable once the config timelock has elapsed.
+    /// Replaces any pending config update.
+    ProposeConfig {
+        config_update: ConfigUpdate<String>,
+    },
+    /// Remove the pending config update
+    CancelPendingConfig {},
+    /// Pause or unpause trading across all pairs, takes effect immediately
+    SetPaused {
+        paused: bool,
     },
     AddMinPrices {
         min_prices: Vec<Coin>,
@@ -1,9 +1,15 @@
 use crate::{
+    constants::DEFAULT_CONFIG_TIMELOCK_SECONDS,
     msg::QueryMsg,
-    state::{GLOBAL_CONFIG, MIN_PRICES},
+    state::{
+        ConfigVersion, CONFIG_HISTORY, CONFIG_TIMELOCK_SECONDS, GLOBAL_CONFIG, MIN_PRICES,
+        PENDING_CONFIG, ROYALTY_SETTINGS,
+    },
 };
 
-use cosmwasm_std::{coin, to_binary, Binary, Deps, Env, StdResult};
+use cosmwasm_std::{coin, to_binary, Binary, Coin, Deps, Env, Order, StdResult};
+use cw_storage_plus::Bound;
+use sg_index_query::{QueryOptions, QueryOptionsInternal};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
@@ -18,5 +24,83 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let min_amount = MIN_PRICES.may_load(deps.storage, denom.clone())?;
             to_binary(&Some(min_amount.map(|a| coin(a.u128(), denom))))
         },
+        QueryMsg::MinPrices {
+            query_options,
+        } => to_binary(&query_min_prices(deps, query_options.unwrap_or_default())?),
+        QueryMsg::SupportedDenoms {
+            query_options,
+        } => to_binary(&query_supported_denoms(deps, query_options.unwrap_or_default())?),
+        QueryMsg::ConfigHistory {
+            query_options,
+        } => to_binary(&query_config_history(deps, query_options.unwrap_or_default())?),
+        QueryMsg::ConfigAtHeight {
+            height,
+        } => to_binary(&query_config_at_height(deps, height)?),
+        QueryMsg::PendingConfig {} => to_binary(&PENDING_CONFIG.may_load(deps.storage)?),
+        QueryMsg::ConfigTimelock {} => to_binary(
+            &CONFIG_TIMELOCK_SECONDS
+                .may_load(deps.storage)?
+                .unwrap_or(DEFAULT_CONFIG_TIMELOCK_SECONDS),
+        ),
+        QueryMsg::RoyaltySettings {
+            collection,
+        } => to_binary(
+            &ROYALTY_SETTINGS.may_load(deps.storage, deps.api.addr_validate(&collection)?)?,
+        ),
     }
 }
+
+pub fn query_min_prices(deps: Deps, query_options: QueryOptions<String>) -> StdResult<Vec<Coin>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| offset.clone()), None, None);
+
+    MIN_PRICES
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|(denom, amount)| coin(amount.u128(), denom)))
+        .collect::<StdResult<Vec<_>>>()
+}
+
+pub fn query_supported_denoms(
+    deps: Deps,
+    query_options: QueryOptions<String>,
+) -> StdResult<Vec<String>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| offset.clone()), None, None);
+
+    MIN_PRICES.keys(deps.storage, min, max, order).take(limit).collect::<StdResult<Vec<_>>>()
+}
+
+pub fn query_config_history(
+    deps: Deps,
+    query_options: QueryOptions<u64>,
+) -> StdResult<Vec<ConfigVersion>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| *offset), None, None);
+
+    CONFIG_HISTORY
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|(_, config_version)| config_version))
+        .collect::<StdResult<Vec<_>>>()
+}
+
+pub fn query_config_at_height(deps: Deps, height: u64) -> StdResult<Option<ConfigVersion>> {
+    CONFIG_HISTORY
+        .range(deps.storage, None, Some(Bound::inclusive(height)), Order::Descending)
+        .next()
+        .transpose()
+        .map(|res| res.map(|(_, config_version)| config_version))
+}
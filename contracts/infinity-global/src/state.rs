@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, StdError, Uint128};
+use cosmwasm_std::{attr, Addr, Attribute, StdError, Timestamp, Uint128};
 use cosmwasm_std::{Api, Coin, Decimal};
 use cw_address_like::AddressLike;
 use cw_storage_plus::{Item, Map};
@@ -30,6 +30,12 @@ pub struct GlobalConfig<T: AddressLike> {
     pub max_royalty_fee_percent: Decimal,
     /// The maximum percentage amount of a sale that can be paid to LPs
     pub max_swap_fee_percent: Decimal,
+    /// The maximum percentage amount of a router swap that can be paid to a referrer
+    #[serde(default)]
+    pub max_referral_fee_percent: Decimal,
+    /// Whether trading is paused across all pairs, only set through the emergency pause
+    #[serde(default)]
+    pub paused: bool,
 }
 
 impl GlobalConfig<String> {
@@ -47,10 +53,214 @@ impl GlobalConfig<String> {
             default_royalty_fee_percent: self.default_royalty_fee_percent,
             max_royalty_fee_percent: self.max_royalty_fee_percent,
             max_swap_fee_percent: self.max_swap_fee_percent,
+            max_referral_fee_percent: self.max_referral_fee_percent,
+            paused: self.paused,
         })
     }
 }
 
 pub const GLOBAL_CONFIG: Item<GlobalConfig<Addr>> = Item::new("g");
 
+/// ConfigUpdate represents a set of changes to the global config, fields set to `None`
+/// are left unchanged
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate<T: AddressLike> {
+    pub fair_burn: Option<T>,
+    pub royalty_registry: Option<T>,
+    pub marketplace: Option<T>,
+    pub infinity_factory: Option<T>,
+    pub infinity_index: Option<T>,
+    pub infinity_router: Option<T>,
+    pub infinity_pair_code_id: Option<u64>,
+    pub pair_creation_fee: Option<Coin>,
+    pub fair_burn_fee_percent: Option<Decimal>,
+    pub default_royalty_fee_percent: Option<Decimal>,
+    pub max_royalty_fee_percent: Option<Decimal>,
+    pub max_swap_fee_percent: Option<Decimal>,
+    pub max_referral_fee_percent: Option<Decimal>,
+    /// The number of seconds a proposed config update must wait before it can be executed.
+    /// Changes to the timelock are themselves subject to the current timelock.
+    pub config_timelock_seconds: Option<u64>,
+}
+
+impl ConfigUpdate<String> {
+    pub fn str_to_addr(self, api: &dyn Api) -> Result<ConfigUpdate<Addr>, StdError> {
+        let addr_validate = |address: Option<String>| -> Result<Option<Addr>, StdError> {
+            address.map(|address| api.addr_validate(&address)).transpose()
+        };
+
+        Ok(ConfigUpdate {
+            fair_burn: addr_validate(self.fair_burn)?,
+            royalty_registry: addr_validate(self.royalty_registry)?,
+            marketplace: addr_validate(self.marketplace)?,
+            infinity_factory: addr_validate(self.infinity_factory)?,
+            infinity_index: addr_validate(self.infinity_index)?,
+            infinity_router: addr_validate(self.infinity_router)?,
+            infinity_pair_code_id: self.infinity_pair_code_id,
+            pair_creation_fee: self.pair_creation_fee,
+            fair_burn_fee_percent: self.fair_burn_fee_percent,
+            default_royalty_fee_percent: self.default_royalty_fee_percent,
+            max_royalty_fee_percent: self.max_royalty_fee_percent,
+            max_swap_fee_percent: self.max_swap_fee_percent,
+            max_referral_fee_percent: self.max_referral_fee_percent,
+            config_timelock_seconds: self.config_timelock_seconds,
+        })
+    }
+}
+
+impl ConfigUpdate<Addr> {
+    /// Applies the update to the global config, returning an attribute for each changed field.
+    /// The config timelock is not part of the global config, and is applied separately.
+    pub fn apply(self, config: &mut GlobalConfig<Addr>) -> Vec<Attribute> {
+        let mut attributes = vec![];
+
+        if let Some(fair_burn) = self.fair_burn {
+            attributes.push(attr("fair_burn", fair_burn.to_string()));
+            config.fair_burn = fair_burn;
+        }
+
+        if let Some(royalty_registry) = self.royalty_registry {
+            attributes.push(attr("royalty_registry", royalty_registry.to_string()));
+            config.royalty_registry = royalty_registry;
+        }
+
+        if let Some(marketplace) = self.marketplace {
+            attributes.push(attr("marketplace", marketplace.to_string()));
+            config.marketplace = marketplace;
+        }
+
+        if let Some(infinity_factory) = self.infinity_factory {
+            attributes.push(attr("infinity_factory", infinity_factory.to_string()));
+            config.infinity_factory = infinity_factory;
+        }
+
+        if let Some(infinity_index) = self.infinity_index {
+            attributes.push(attr("infinity_index", infinity_index.to_string()));
+            config.infinity_index = infinity_index;
+        }
+
+        if let Some(infinity_router) = self.infinity_router {
+            attributes.push(attr("infinity_router", infinity_router.to_string()));
+            config.infinity_router = infinity_router;
+        }
+
+        if let Some(infinity_pair_code_id) = self.infinity_pair_code_id {
+            attributes.push(attr("infinity_pair_code_id", infinity_pair_code_id.to_string()));
+            config.infinity_pair_code_id = infinity_pair_code_id;
+        }
+
+        if let Some(pair_creation_fee) = self.pair_creation_fee {
+            attributes.push(attr("pair_creation_fee", pair_creation_fee.to_string()));
+            config.pair_creation_fee = pair_creation_fee;
+        }
+
+        if let Some(fair_burn_fee_percent) = self.fair_burn_fee_percent {
+            attributes.push(attr("fair_burn_fee_percent", fair_burn_fee_percent.to_string()));
+            config.fair_burn_fee_percent = fair_burn_fee_percent;
+        }
+
+        if let Some(default_royalty_fee_percent) = self.default_royalty_fee_percent {
+            attributes
+                .push(attr("default_royalty_fee_percent", default_royalty_fee_percent.to_string()));
+            config.default_royalty_fee_percent = default_royalty_fee_percent;
+        }
+
+        if let Some(max_royalty_fee_percent) = self.max_royalty_fee_percent {
+            attributes.push(attr("max_royalty_fee_percent", max_royalty_fee_percent.to_string()));
+            config.max_royalty_fee_percent = max_royalty_fee_percent;
+        }
+
+        if let Some(max_swap_fee_percent) = self.max_swap_fee_percent {
+            attributes.push(attr("max_swap_fee_percent", max_swap_fee_percent.to_string()));
+            config.max_swap_fee_percent = max_swap_fee_percent;
+        }
+
+        if let Some(max_referral_fee_percent) = self.max_referral_fee_percent {
+            attributes.push(attr("max_referral_fee_percent", max_referral_fee_percent.to_string()));
+            config.max_referral_fee_percent = max_referral_fee_percent;
+        }
+
+        attributes
+    }
+}
+
+/// PendingConfig represents a proposed config update waiting out the config timelock
+#[cw_serde]
+pub struct PendingConfig {
+    /// The changes to be applied to the global config
+    pub config_update: ConfigUpdate<Addr>,
+    /// The time at which the update was proposed
+    pub proposed_at: Timestamp,
+    /// The time from which the update can be executed
+    pub executable_at: Timestamp,
+}
+
+pub const PENDING_CONFIG: Item<PendingConfig> = Item::new("p");
+
+/// The number of seconds a proposed config update must wait before it can be executed.
+/// When unset, `DEFAULT_CONFIG_TIMELOCK_SECONDS` is used.
+pub const CONFIG_TIMELOCK_SECONDS: Item<u64> = Item::new("t");
+
 pub const MIN_PRICES: Map<String, Uint128> = Map::new("m");
+
+/// ConfigVersion represents the global config and min prices that applied from a given block height
+#[cw_serde]
+pub struct ConfigVersion {
+    /// The version number, incremented on every config update
+    pub version: u64,
+    /// The block height at which the config was set
+    pub height: u64,
+    /// The fields changed since the previous version
+    pub changed_fields: Vec<String>,
+    /// The global config as of this version
+    pub global_config: GlobalConfig<Addr>,
+    /// The min prices as of this version, one per supported denom
+    #[serde(default)]
+    pub min_prices: Vec<Coin>,
+}
+
+/// A map of block heights to the global config and min prices set at that height.
+/// Multiple updates within the same block are merged into a single version.
+pub const CONFIG_HISTORY: Map<u64, ConfigVersion> = Map::new("h");
+
+/// RoyaltySplit represents a recipient's share of a collection's royalty payment
+#[cw_serde]
+pub struct RoyaltySplit<T: AddressLike> {
+    /// The address of the royalty recipient
+    pub recipient: T,
+    /// The fraction of the royalty payment paid to the recipient
+    pub weight: Decimal,
+}
+
+/// RoyaltySettings are set by a collection creator to override how royalties are paid out
+#[cw_serde]
+pub struct RoyaltySettings<T: AddressLike> {
+    /// The recipients of the royalty payment, when empty the royalty is paid
+    /// to the recipient found in the royalty registry or collection info, falling back to
+    /// the collection creator when neither defines a recipient. Weights must sum to one.
+    pub splits: Vec<RoyaltySplit<T>>,
+    /// The minimum royalty percentage paid on every sale, regardless of the royalty registry.
+    /// Note: this value is still capped by `max_royalty_fee_percent`
+    pub min_royalty_fee_percent: Option<Decimal>,
+}
+
+impl RoyaltySettings<String> {
+    pub fn str_to_addr(self, api: &dyn Api) -> Result<RoyaltySettings<Addr>, StdError> {
+        Ok(RoyaltySettings {
+            splits: self
+                .splits
+                .into_iter()
+                .map(|split| {
+                    Ok(RoyaltySplit {
+                        recipient: api.addr_validate(&split.recipient)?,
+                        weight: split.weight,
+                    })
+                })
+                .collect::<Result<Vec<_>, StdError>>()?,
+            min_royalty_fee_percent: self.min_royalty_fee_percent,
+        })
+    }
+}
+
+pub const ROYALTY_SETTINGS: Map<Addr, RoyaltySettings<Addr>> = Map::new("r");
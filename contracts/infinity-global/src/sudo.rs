@@ -1,143 +1,100 @@
 use crate::{
+    constants::DEFAULT_CONFIG_TIMELOCK_SECONDS,
+    helpers::{save_config_version, validate_config_update},
     msg::SudoMsg,
-    state::{GLOBAL_CONFIG, MIN_PRICES},
+    state::{
+        ConfigUpdate, PendingConfig, CONFIG_TIMELOCK_SECONDS, GLOBAL_CONFIG, MIN_PRICES,
+        PENDING_CONFIG,
+    },
+    ContractError,
 };
 
-use cosmwasm_std::{attr, Coin, Decimal, DepsMut, Env, Event, StdError};
+use cosmwasm_std::{attr, Addr, Coin, DepsMut, Env, Event, StdError};
 use sg_std::Response;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, StdError> {
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    let api = deps.api;
+
     match msg {
-        SudoMsg::UpdateConfig {
-            fair_burn,
-            royalty_registry,
-            marketplace,
-            infinity_factory,
-            infinity_index,
-            infinity_router,
-            infinity_pair_code_id,
-            pair_creation_fee,
-            fair_burn_fee_percent,
-            default_royalty_fee_percent,
-            max_royalty_fee_percent,
-            max_swap_fee_percent,
-        } => sudo_update_config(
-            deps,
-            fair_burn,
-            royalty_registry,
-            marketplace,
-            infinity_factory,
-            infinity_index,
-            infinity_router,
-            infinity_pair_code_id,
-            pair_creation_fee,
-            fair_burn_fee_percent,
-            default_royalty_fee_percent,
-            max_royalty_fee_percent,
-            max_swap_fee_percent,
-        ),
+        SudoMsg::ProposeConfig {
+            config_update,
+        } => sudo_propose_config(deps, env, config_update.str_to_addr(api)?),
+        SudoMsg::CancelPendingConfig {} => sudo_cancel_pending_config(deps),
+        SudoMsg::SetPaused {
+            paused,
+        } => sudo_set_paused(deps, env, paused),
         SudoMsg::AddMinPrices {
             min_prices,
-        } => sudo_add_min_prices(deps, min_prices),
+        } => sudo_add_min_prices(deps, env, min_prices),
         SudoMsg::RemoveMinPrices {
             denoms,
-        } => sudo_remove_min_prices(deps, denoms),
+        } => sudo_remove_min_prices(deps, env, denoms),
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn sudo_update_config(
+pub fn sudo_propose_config(
     deps: DepsMut,
-    fair_burn: Option<String>,
-    royalty_registry: Option<String>,
-    marketplace: Option<String>,
-    infinity_factory: Option<String>,
-    infinity_index: Option<String>,
-    infinity_router: Option<String>,
-    infinity_pair_code_id: Option<u64>,
-    pair_creation_fee: Option<Coin>,
-    fair_burn_fee_percent: Option<Decimal>,
-    default_royalty_fee_percent: Option<Decimal>,
-    max_royalty_fee_percent: Option<Decimal>,
-    max_swap_fee_percent: Option<Decimal>,
-) -> Result<Response, StdError> {
-    let api = deps.api;
-
+    env: Env,
+    config_update: ConfigUpdate<Addr>,
+) -> Result<Response, ContractError> {
     let mut config = GLOBAL_CONFIG.load(deps.storage)?;
-
-    let mut event = Event::new("sudo-update-config");
-
-    if let Some(fair_burn) = fair_burn {
-        event = event.add_attribute("fair_burn", &fair_burn);
-        config.fair_burn = api.addr_validate(&fair_burn)?;
-    }
-
-    if let Some(royalty_registry) = royalty_registry {
-        event = event.add_attribute("royalty_registry", &royalty_registry);
-        config.royalty_registry = api.addr_validate(&royalty_registry)?;
+    validate_config_update(&deps.querier, &config, &config_update)?;
+
+    let timelock_seconds =
+        CONFIG_TIMELOCK_SECONDS.may_load(deps.storage)?.unwrap_or(DEFAULT_CONFIG_TIMELOCK_SECONDS);
+
+    let pending_config = PendingConfig {
+        config_update,
+        proposed_at: env.block.time,
+        executable_at: env.block.time.plus_seconds(timelock_seconds),
+    };
+    PENDING_CONFIG.save(deps.storage, &pending_config)?;
+
+    // The proposed values are applied to a copy of the config, to describe the update
+    let config_timelock_seconds = pending_config.config_update.config_timelock_seconds;
+    let mut event = Event::new("sudo-propose-config")
+        .add_attribute("executable_at", pending_config.executable_at.to_string())
+        .add_attributes(pending_config.config_update.apply(&mut config));
+    if let Some(config_timelock_seconds) = config_timelock_seconds {
+        event = event.add_attribute("config_timelock_seconds", config_timelock_seconds.to_string());
     }
 
-    if let Some(marketplace) = marketplace {
-        event = event.add_attribute("marketplace", &marketplace);
-        config.marketplace = api.addr_validate(&marketplace)?;
-    }
-
-    if let Some(infinity_factory) = infinity_factory {
-        event = event.add_attribute("infinity_factory", &infinity_factory);
-        config.infinity_factory = api.addr_validate(&infinity_factory)?;
-    }
-
-    if let Some(infinity_index) = infinity_index {
-        event = event.add_attribute("infinity_index", &infinity_index);
-        config.infinity_index = api.addr_validate(&infinity_index)?;
-    }
-
-    if let Some(infinity_router) = infinity_router {
-        event = event.add_attribute("infinity_router", &infinity_router);
-        config.infinity_router = api.addr_validate(&infinity_router)?;
-    }
-
-    if let Some(infinity_pair_code_id) = infinity_pair_code_id {
-        event = event.add_attribute("infinity_pair_code_id", infinity_pair_code_id.to_string());
-        config.infinity_pair_code_id = infinity_pair_code_id;
-    }
+    Ok(Response::new().add_event(event))
+}
 
-    if let Some(pair_creation_fee) = pair_creation_fee {
-        event = event.add_attribute("pair_creation_fee", pair_creation_fee.to_string());
-        config.pair_creation_fee = pair_creation_fee;
-    }
+pub fn sudo_cancel_pending_config(deps: DepsMut) -> Result<Response, ContractError> {
+    let pending_config = PENDING_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(StdError::generic_err("no pending config to cancel"))?;
+    PENDING_CONFIG.remove(deps.storage);
 
-    if let Some(fair_burn_fee_percent) = fair_burn_fee_percent {
-        event = event.add_attribute("fair_burn_fee_percent", fair_burn_fee_percent.to_string());
-        config.fair_burn_fee_percent = fair_burn_fee_percent;
-    }
+    let event = Event::new("sudo-cancel-pending-config")
+        .add_attribute("executable_at", pending_config.executable_at.to_string());
 
-    if let Some(default_royalty_fee_percent) = default_royalty_fee_percent {
-        event = event
-            .add_attribute("default_royalty_fee_percent", default_royalty_fee_percent.to_string());
-        config.default_royalty_fee_percent = default_royalty_fee_percent;
-    }
+    Ok(Response::new().add_event(event))
+}
 
-    if let Some(max_royalty_fee_percent) = max_royalty_fee_percent {
-        event = event.add_attribute("max_royalty_fee_percent", max_royalty_fee_percent.to_string());
-        config.max_royalty_fee_percent = max_royalty_fee_percent;
-    }
+pub fn sudo_set_paused(deps: DepsMut, env: Env, paused: bool) -> Result<Response, ContractError> {
+    let mut config = GLOBAL_CONFIG.load(deps.storage)?;
+    config.paused = paused;
+    GLOBAL_CONFIG.save(deps.storage, &config)?;
 
-    if let Some(max_swap_fee_percent) = max_swap_fee_percent {
-        event = event.add_attribute("max_swap_fee_percent", max_swap_fee_percent.to_string());
-        config.max_swap_fee_percent = max_swap_fee_percent;
-    }
+    save_config_version(deps.storage, env.block.height, &config, vec!["paused".to_string()])?;
 
-    GLOBAL_CONFIG.save(deps.storage, &config)?;
+    let event = Event::new("sudo-set-paused").add_attribute("paused", paused.to_string());
 
     Ok(Response::new().add_event(event))
 }
 
-pub fn sudo_add_min_prices(deps: DepsMut, min_prices: Vec<Coin>) -> Result<Response, StdError> {
+pub fn sudo_add_min_prices(
+    deps: DepsMut,
+    env: Env,
+    min_prices: Vec<Coin>,
+) -> Result<Response, ContractError> {
     let mut event = Event::new("sudo-add-min-prices");
     for min_price in min_prices {
         MIN_PRICES.save(deps.storage, min_price.denom.clone(), &min_price.amount)?;
@@ -147,15 +104,25 @@ pub fn sudo_add_min_prices(deps: DepsMut, min_prices: Vec<Coin>) -> Result<Respo
         ]);
     }
 
+    let config = GLOBAL_CONFIG.load(deps.storage)?;
+    save_config_version(deps.storage, env.block.height, &config, vec!["min_prices".to_string()])?;
+
     Ok(Response::new().add_event(event))
 }
 
-pub fn sudo_remove_min_prices(deps: DepsMut, denoms: Vec<String>) -> Result<Response, StdError> {
+pub fn sudo_remove_min_prices(
+    deps: DepsMut,
+    env: Env,
+    denoms: Vec<String>,
+) -> Result<Response, ContractError> {
     let mut event = Event::new("sudo-remove-min-prices");
     for denom in denoms {
         MIN_PRICES.remove(deps.storage, denom.clone());
         event = event.add_attributes(vec![attr("denom", denom.to_string())]);
     }
 
+    let config = GLOBAL_CONFIG.load(deps.storage)?;
+    save_config_version(deps.storage, env.block.height, &config, vec!["min_prices".to_string()])?;
+
     Ok(Response::new().add_event(event))
 }
@@ -1,4 +1,4 @@
-use crate::helpers::only_infinity_pair;
+use crate::helpers::{only_infinity_pair, record_price_observation};
 use crate::msg::ExecuteMsg;
 use crate::state::PairQuote;
 use crate::{
@@ -36,6 +36,11 @@ pub fn execute(
             sell_to_pair_quote,
             buy_from_pair_quote,
         ),
+        ExecuteMsg::RecordTrade {
+            collection,
+            denom,
+            price,
+        } => execute_record_trade(deps, env, info, api.addr_validate(&collection)?, denom, price),
     }
 }
 
@@ -86,3 +91,18 @@ pub fn execute_update_pair_indices(
 
     Ok(Response::new())
 }
+
+pub fn execute_record_trade(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    denom: String,
+    price: Uint128,
+) -> Result<Response, ContractError> {
+    only_infinity_pair(deps.as_ref(), &info.sender)?;
+
+    record_price_observation(deps.storage, &env.block, collection, denom, price)?;
+
+    Ok(Response::new())
+}
@@ -1,10 +1,24 @@
-use crate::state::INFINITY_GLOBAL;
+use crate::state::{PriceObservation, INFINITY_GLOBAL, PRICE_OBSERVATIONS};
 use crate::ContractError;
 
-use cosmwasm_std::{ensure_eq, Addr, Deps};
+use cosmwasm_std::{
+    ensure_eq, Addr, BlockInfo, Decimal, Deps, Order, StdResult, Storage, Uint128, Uint256,
+};
+use cw_storage_plus::Bound;
 use infinity_global::load_global_config;
 use infinity_shared::InfinityError;
 
+/// The maximum number of seconds over which a TWAP can be queried,
+/// observations older than this window are pruned as new trades are recorded.
+pub const MAX_TWAP_WINDOW_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// The maximum number of expired observations pruned when a trade is recorded
+pub const MAX_PRUNED_OBSERVATIONS: usize = 10;
+
+/// The maximum percentage by which a recorded trade price can deviate from the
+/// price of the previous observation.
+pub const MAX_PRICE_DEVIATION_PERCENT: u64 = 25;
+
 /// Only infinity pairs created by the infinity factory can execute this function
 /// and update the index.
 pub fn only_infinity_pair(deps: Deps, contract: &Addr) -> Result<(), ContractError> {
@@ -22,3 +36,129 @@ pub fn only_infinity_pair(deps: Deps, contract: &Addr) -> Result<(), ContractErr
 
     Ok(())
 }
+
+/// Returns the cumulative price of an observation projected forward to the given timestamp,
+/// assuming the observed price remained the last trade price in the meantime.
+pub fn cumulative_price_at(observation: &PriceObservation, timestamp: u64) -> Uint256 {
+    observation.cumulative_price
+        + Uint256::from(observation.price)
+            * Uint256::from(timestamp.saturating_sub(observation.timestamp))
+}
+
+/// Loads the most recent price observation for a collection and denom, at or before the
+/// optional timestamp.
+pub fn load_price_observation(
+    storage: &dyn Storage,
+    collection: Addr,
+    denom: String,
+    at_or_before: Option<u64>,
+) -> StdResult<Option<PriceObservation>> {
+    PRICE_OBSERVATIONS
+        .prefix((collection, denom))
+        .range(storage, None, at_or_before.map(Bound::inclusive), Order::Descending)
+        .next()
+        .transpose()
+        .map(|res| res.map(|(_, observation)| observation))
+}
+
+/// Records a trade price, accumulating the previous price over the time elapsed since the
+/// previous observation.
+///
+/// The oracle bounds how far trades can move the price:
+/// * Each trade price is clamped to within `MAX_PRICE_DEVIATION_PERCENT` of the price of the
+///   observation before the trade's block, so a single block moves the price by at most 25%.
+/// * Trades within the same block are averaged, weighting each trade equally, rather than the
+///   last trade in the block overwriting the others.
+/// * Pairs do not record trades with their own owner or asset recipient, and oracle anchored
+///   pairs do not record trades at all, so a pair cannot feed its own quotes back into the oracle.
+///
+/// Moving the TWAP therefore requires trading across many blocks, paying fees on every trade,
+/// and the TWAP over a window of `w` seconds moves by at most 25% per block containing a trade,
+/// weighted by the fraction of `w` that the manipulated price stands.
+pub fn record_price_observation(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    collection: Addr,
+    denom: String,
+    price: Uint128,
+) -> StdResult<()> {
+    let timestamp = block.time.seconds();
+
+    let last_observation =
+        load_price_observation(storage, collection.clone(), denom.clone(), None)?;
+
+    let prev_observation = match &last_observation {
+        Some(observation) if observation.timestamp == timestamp => load_price_observation(
+            storage,
+            collection.clone(),
+            denom.clone(),
+            Some(timestamp.saturating_sub(1)),
+        )?,
+        _ => last_observation.clone(),
+    };
+
+    let price = match prev_observation {
+        Some(prev) => {
+            let max_deviation = prev.price.mul_floor(Decimal::percent(MAX_PRICE_DEVIATION_PERCENT));
+            price.clamp(prev.price - max_deviation, prev.price + max_deviation)
+        },
+        None => price,
+    };
+
+    let observation = match last_observation {
+        Some(last) if last.timestamp == timestamp => {
+            let total_price =
+                Uint256::from(last.price) * Uint256::from(last.num_trades) + Uint256::from(price);
+            PriceObservation {
+                timestamp,
+                price: Uint128::try_from(total_price / Uint256::from(last.num_trades + 1))?,
+                num_trades: last.num_trades + 1,
+                cumulative_price: last.cumulative_price,
+            }
+        },
+        Some(last) => PriceObservation {
+            timestamp,
+            price,
+            num_trades: 1,
+            cumulative_price: cumulative_price_at(&last, timestamp),
+        },
+        None => PriceObservation {
+            timestamp,
+            price,
+            num_trades: 1,
+            cumulative_price: Uint256::zero(),
+        },
+    };
+
+    PRICE_OBSERVATIONS.save(
+        storage,
+        (collection.clone(), denom.clone(), timestamp),
+        &observation,
+    )?;
+
+    prune_price_observations(storage, collection, denom, timestamp)
+}
+
+/// Removes observations that fall outside of the max TWAP window, keeping the most recent
+/// expired observation as the starting point for windows that begin before the next observation.
+fn prune_price_observations(
+    storage: &mut dyn Storage,
+    collection: Addr,
+    denom: String,
+    timestamp: u64,
+) -> StdResult<()> {
+    let cutoff = timestamp.saturating_sub(MAX_TWAP_WINDOW_SECONDS);
+
+    let mut expired_timestamps = PRICE_OBSERVATIONS
+        .prefix((collection.clone(), denom.clone()))
+        .keys(storage, None, Some(Bound::exclusive(cutoff)), Order::Ascending)
+        .take(MAX_PRUNED_OBSERVATIONS + 1)
+        .collect::<StdResult<Vec<u64>>>()?;
+    expired_timestamps.pop();
+
+    for expired_timestamp in expired_timestamps {
+        PRICE_OBSERVATIONS.remove(storage, (collection.clone(), denom.clone(), expired_timestamp));
+    }
+
+    Ok(())
+}
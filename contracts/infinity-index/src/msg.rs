@@ -1,8 +1,8 @@
 #[cfg_attr(not(debug_assertions), allow(unused_imports))]
-use crate::state::PairQuote;
+use crate::state::{PairQuote, PriceObservation};
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Coin, Uint128};
 use sg_index_query::QueryOptions;
 
 #[cw_serde]
@@ -20,6 +20,13 @@ pub enum ExecuteMsg {
         sell_to_pair_quote: Option<Uint128>,
         buy_from_pair_quote: Option<Uint128>,
     },
+    /// Record the price of a trade executed by a pair, used to derive oracle prices.
+    /// See `record_price_observation` for the bounds on how far trades can move the price.
+    RecordTrade {
+        collection: String,
+        denom: String,
+        price: Uint128,
+    },
 }
 
 #[cw_serde]
@@ -45,4 +52,29 @@ pub enum QueryMsg {
         denom: String,
         query_options: Option<QueryOptions<PairQuoteOffset>>,
     },
+    #[returns(Option<PriceObservation>)]
+    LastTradePrice {
+        collection: String,
+        denom: String,
+    },
+    #[returns(Option<TwapResponse>)]
+    Twap {
+        collection: String,
+        denom: String,
+        /// The number of seconds, ending at the current block time, over which to average prices,
+        /// at most `MAX_TWAP_WINDOW_SECONDS`
+        window_seconds: u64,
+    },
+}
+
+#[cw_serde]
+pub struct TwapResponse {
+    /// The time weighted average price over the window
+    pub twap: Coin,
+    /// The start of the window, later than requested if there is not enough trade history
+    pub start_timestamp: u64,
+    /// The end of the window, the current block time
+    pub end_timestamp: u64,
+    /// The block time of the most recent trade, used to determine if the price is stale
+    pub last_trade_timestamp: u64,
 }
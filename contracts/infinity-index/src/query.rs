@@ -1,14 +1,19 @@
-use crate::msg::{PairQuoteOffset, QueryMsg};
-use crate::state::{buy_from_pair_quotes, sell_to_pair_quotes, PairQuote};
+use crate::helpers::{cumulative_price_at, load_price_observation, MAX_TWAP_WINDOW_SECONDS};
+use crate::msg::{PairQuoteOffset, QueryMsg, TwapResponse};
+use crate::state::{
+    buy_from_pair_quotes, sell_to_pair_quotes, PairQuote, PriceObservation, PRICE_OBSERVATIONS,
+};
 
-use cosmwasm_std::{to_binary, Addr, Binary, Deps, Env, StdResult};
+use cosmwasm_std::{
+    coin, ensure, to_binary, Addr, Binary, Deps, Env, Order, StdError, StdResult, Uint128, Uint256,
+};
 use sg_index_query::{QueryOptions, QueryOptionsInternal};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::SellToPairQuotes {
             collection,
@@ -30,6 +35,21 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             denom,
             query_options.unwrap_or_default(),
         )?),
+        QueryMsg::LastTradePrice {
+            collection,
+            denom,
+        } => to_binary(&query_last_trade_price(deps, deps.api.addr_validate(&collection)?, denom)?),
+        QueryMsg::Twap {
+            collection,
+            denom,
+            window_seconds,
+        } => to_binary(&query_twap(
+            deps,
+            env,
+            deps.api.addr_validate(&collection)?,
+            denom,
+            window_seconds,
+        )?),
     }
 }
 
@@ -90,3 +110,71 @@ pub fn query_buy_from_pair_quotes(
 
     Ok(results)
 }
+
+pub fn query_last_trade_price(
+    deps: Deps,
+    collection: Addr,
+    denom: String,
+) -> StdResult<Option<PriceObservation>> {
+    load_price_observation(deps.storage, collection, denom, None)
+}
+
+pub fn query_twap(
+    deps: Deps,
+    env: Env,
+    collection: Addr,
+    denom: String,
+    window_seconds: u64,
+) -> StdResult<Option<TwapResponse>> {
+    ensure!(window_seconds > 0, StdError::generic_err("window_seconds must be greater than zero"));
+    ensure!(
+        window_seconds <= MAX_TWAP_WINDOW_SECONDS,
+        StdError::generic_err("window_seconds exceeds the max twap window")
+    );
+
+    let last_observation =
+        match load_price_observation(deps.storage, collection.clone(), denom.clone(), None)? {
+            Some(observation) => observation,
+            None => return Ok(None),
+        };
+
+    let end_timestamp = env.block.time.seconds();
+    let requested_start_timestamp = end_timestamp.saturating_sub(window_seconds);
+
+    // When there is no trade before the start of the window, the window is shortened to begin
+    // at the first recorded trade.
+    let (start_observation, start_timestamp) = match load_price_observation(
+        deps.storage,
+        collection.clone(),
+        denom.clone(),
+        Some(requested_start_timestamp),
+    )? {
+        Some(observation) => (observation, requested_start_timestamp),
+        None => {
+            let first_observation = PRICE_OBSERVATIONS
+                .prefix((collection, denom.clone()))
+                .range(deps.storage, None, None, Order::Ascending)
+                .next()
+                .transpose()?
+                .map(|(_, observation)| observation)
+                .unwrap();
+            let timestamp = first_observation.timestamp;
+            (first_observation, timestamp)
+        },
+    };
+
+    let twap_amount = if end_timestamp == start_timestamp {
+        last_observation.price
+    } else {
+        let cumulative_price_delta = cumulative_price_at(&last_observation, end_timestamp)
+            - cumulative_price_at(&start_observation, start_timestamp);
+        Uint128::try_from(cumulative_price_delta / Uint256::from(end_timestamp - start_timestamp))?
+    };
+
+    Ok(Some(TwapResponse {
+        twap: coin(twap_amount.u128(), denom),
+        start_timestamp,
+        end_timestamp,
+        last_trade_timestamp: last_observation.timestamp,
+    }))
+}
@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{Addr, Coin, Uint128, Uint256};
 use cw_storage_macro::index_list;
-use cw_storage_plus::{IndexedMap, Item, MultiIndex};
+use cw_storage_plus::{IndexedMap, Item, Map, MultiIndex};
 
 pub const INFINITY_GLOBAL: Item<Addr> = Item::new("g");
 
@@ -47,3 +47,20 @@ pub fn sell_to_pair_quotes<'a>() -> IndexedMap<'a, Addr, PairQuote, SellPairQuot
     };
     IndexedMap::new("s", indexes)
 }
+
+/// PriceObservation is a record of the last trade price for a collection and denom at a point in time
+#[cw_serde]
+pub struct PriceObservation {
+    /// The block time of the trade, in seconds
+    pub timestamp: u64,
+    /// The average price of the trades at this timestamp, each bounded by the previous observation
+    pub price: Uint128,
+    /// The number of trades recorded at this timestamp
+    pub num_trades: u32,
+    /// The sum of each observed price multiplied by the number of seconds it was the last trade price,
+    /// accumulated from the first observation up to this timestamp
+    pub cumulative_price: Uint256,
+}
+
+// (collection, denom, timestamp) => PriceObservation
+pub const PRICE_OBSERVATIONS: Map<(Addr, String, u64), PriceObservation> = Map::new("po");
@@ -1,6 +1,9 @@
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The page size used when querying the NFTs owned by the pair during reconciliation
+pub const RECONCILE_PAGE_LIMIT: u32 = 100;
+
 /// Top level storage key. Values must not conflict.
 /// Each key is only one byte long to ensure we use the smallest possible storage keys.
 #[repr(u8)]
@@ -10,6 +13,8 @@ pub enum TopKey {
     PairImmutable = b'P',
     PairConfig = b'C',
     PairInternal = b'I',
+    NftSelectionNonce = b'S',
+    PairStats = b'T',
 }
 
 impl TopKey {
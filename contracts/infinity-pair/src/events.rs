@@ -1,6 +1,13 @@
-use crate::{pair::Pair, state::QuoteSummary};
+use crate::{
+    pair::Pair,
+    state::{PairTrigger, QuoteSummary},
+};
 
 use cosmwasm_std::{attr, Addr, Coin, Event};
+use infinity_shared::events::{
+    FAIR_BURN_FEE_ATTR, ROYALTY_FEE_ATTR, SELLER_AMOUNT_ATTR, SENDER_RECIPIENT_ATTR, SWAP_FEE_ATTR,
+    TOKEN_ID_ATTR,
+};
 use std::vec;
 
 pub struct CreatePairEvent<'a> {
@@ -21,7 +28,10 @@ impl<'a> From<CreatePairEvent<'a>> for Event {
             "spot_price",
             "delta",
             "is_active",
+            "active_from",
+            "expires_at",
             "asset_recipient",
+            "nft_selection",
         ]))
     }
 }
@@ -42,7 +52,10 @@ impl<'a> From<UpdatePairEvent<'a>> for Event {
             "spot_price",
             "delta",
             "is_active",
+            "active_from",
+            "expires_at",
             "asset_recipient",
+            "nft_selection",
         ]))
     }
 }
@@ -61,6 +74,30 @@ impl<'a> From<NftTransferEvent<'a>> for Event {
     }
 }
 
+pub struct PairTriggerEvent<'a> {
+    pub pair: &'a Pair,
+    pub trigger: &'a PairTrigger,
+}
+
+impl<'a> From<PairTriggerEvent<'a>> for Event {
+    fn from(pte: PairTriggerEvent) -> Self {
+        let trigger = match pte.trigger {
+            PairTrigger::MinSpotPrice => "min_spot_price",
+            PairTrigger::MinInventory => "min_inventory",
+            PairTrigger::MaxRealizedLosses => "max_realized_losses",
+        };
+        Event::new("pair-trigger".to_string()).add_attribute("trigger", trigger).add_attributes(
+            pte.pair.get_event_attrs(vec![
+                "is_active",
+                "sell_to_pair_halted",
+                "buy_from_pair_halted",
+                "spot_price",
+                "total_nfts",
+            ]),
+        )
+    }
+}
+
 pub struct TokenTransferEvent<'a> {
     pub ty: &'a str,
     pub funds: &'a Coin,
@@ -86,17 +123,17 @@ impl<'a> From<SwapEvent<'a>> for Event {
             .add_attributes(se.pair.get_event_attrs(vec!["spot_price", "is_active"]));
 
         event = event.add_attributes(vec![
-            attr("token_id", se.token_id),
-            attr("sender_recipient", se.sender_recipient),
-            attr("fair_burn_fee", se.quote_summary.fair_burn.amount),
-            attr("seller_amount", se.quote_summary.seller_amount),
+            attr(TOKEN_ID_ATTR, se.token_id),
+            attr(SENDER_RECIPIENT_ATTR, se.sender_recipient),
+            attr(FAIR_BURN_FEE_ATTR, se.quote_summary.fair_burn.amount),
+            attr(SELLER_AMOUNT_ATTR, se.quote_summary.seller_amount),
         ]);
 
-        if let Some(royalty) = se.quote_summary.royalty.as_ref() {
-            event = event.add_attribute("royalty_fee", royalty.amount);
+        if !se.quote_summary.royalties.is_empty() {
+            event = event.add_attribute(ROYALTY_FEE_ATTR, se.quote_summary.royalty_amount());
         }
         if let Some(swap) = se.quote_summary.swap.as_ref() {
-            event = event.add_attribute("swap_fee", swap.amount);
+            event = event.add_attribute(SWAP_FEE_ATTR, swap.amount);
         }
 
         event
@@ -105,14 +142,23 @@ impl<'a> From<SwapEvent<'a>> for Event {
 
 pub struct PairInternalEvent<'a> {
     pub pair: &'a Pair,
+    pub royalty_skipped: bool,
 }
 
 impl<'a> From<PairInternalEvent<'a>> for Event {
     fn from(pie: PairInternalEvent) -> Self {
-        Event::new("pair-internal".to_string()).add_attributes(pie.pair.get_event_attrs(vec![
-            "total_tokens",
-            "sell_to_pair_quote",
-            "buy_from_pair_quote",
-        ]))
+        let mut event =
+            Event::new("pair-internal".to_string()).add_attributes(pie.pair.get_event_attrs(vec![
+                "total_tokens",
+                "sell_to_pair_quote",
+                "buy_from_pair_quote",
+            ]));
+
+        // The quotes exclude royalties when the royalty info of the collection is unavailable
+        if pie.royalty_skipped {
+            event = event.add_attribute("royalty_skipped", "true");
+        }
+
+        event
     }
 }
@@ -1,17 +1,27 @@
 use crate::error::ContractError;
 use crate::events::{
-    NftTransferEvent, PairInternalEvent, SwapEvent, TokenTransferEvent, UpdatePairEvent,
+    NftTransferEvent, PairInternalEvent, PairTriggerEvent, SwapEvent, TokenTransferEvent,
+    UpdatePairEvent,
 };
-use crate::helpers::{load_pair, load_payout_context, only_active, only_pair_owner};
-use crate::msg::ExecuteMsg;
+use crate::helpers::{
+    load_cheapest_nft_prices, load_nft_reconciliation, load_owner_pair, load_pair,
+    load_pair_payout_context, load_payout_context, load_unpaused_global_config, only_active,
+    only_pair_owner, only_pair_owner_or_owner_pair, select_random_index,
+};
+use crate::msg::{ExecuteMsg, ReceiveNftMsg, SwapData, TransactionType};
 use crate::pair::Pair;
-use crate::state::{BondingCurve, PairType, INFINITY_GLOBAL, NFT_DEPOSITS};
+use crate::state::{
+    BondingCurve, NftPrice, NftSelection, PairLimits, PairSchedule, PairTriggers, PairType,
+    QuoteSummary, SweepPolicy, TokenId, INFINITY_GLOBAL, NFT_DEPOSITS, PAIR_STATS,
+};
 
 use cosmwasm_std::{
-    coin, ensure, ensure_eq, has_coins, Addr, Coin, DepsMut, Env, MessageInfo, Order, StdResult,
+    coin, ensure, ensure_eq, from_binary, has_coins, to_binary, Addr, Binary, Coin, DepsMut, Env,
+    Event, MessageInfo, Order, StdResult, Uint128, WasmMsg,
 };
-use cw721::{Cw721QueryMsg, TokensResponse};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, Cw721ReceiveMsg, TokensResponse};
 use cw_utils::{maybe_addr, must_pay, nonpayable};
+use infinity_shared::events::{SWAP_NFT_FOR_TOKENS_EVENT, SWAP_TOKENS_FOR_NFT_EVENT};
 use infinity_shared::{only_nft_owner, InfinityError};
 use sg_marketplace_common::address::address_or;
 use sg_marketplace_common::coin::transfer_coins;
@@ -28,9 +38,8 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    let pair = load_pair(&env.contract.address, deps.storage, &deps.querier)?;
-
-    let (mut pair, mut response) = handle_execute_msg(deps.branch(), env, info, msg, pair)?;
+    let mut pair = load_pair(deps.storage)?;
+    pair.update_limit_window(&env.block);
 
     let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
     let payout_context = load_payout_context(
@@ -40,11 +49,29 @@ pub fn execute(
         &pair.immutable.denom,
     )?;
 
+    // The schedule, the reference price and the limit window all depend on the current block,
+    // so the quotes are refreshed before the message is handled. This ensures that swaps never
+    // execute against an outdated schedule, limit window or reference price.
+    pair.update_schedule(&env.block);
+    pair.update_reference_price(&deps.querier, &env.block);
+    pair.update_sell_to_pair_quote_summary(&payout_context);
+    pair.update_nft_buy_from_pair_quote_summary(
+        &payout_context,
+        &load_cheapest_nft_prices(deps.storage)?,
+    );
+
+    let block = env.block.clone();
+    let (mut pair, mut response) = handle_execute_msg(deps.branch(), env, info, msg, pair)?;
+
+    pair.update_schedule(&block);
+    pair.update_reference_price(&deps.querier, &block);
+
     response = pair.save_and_update_indices(deps.storage, &payout_context, response)?;
 
     response = response.add_event(
         PairInternalEvent {
             pair: &pair,
+            royalty_skipped: payout_context.royalty_skipped,
         }
         .into(),
     );
@@ -104,7 +131,7 @@ pub fn handle_execute_msg(
             )
         },
         ExecuteMsg::DepositTokens {} => {
-            only_pair_owner(&info, &pair)?;
+            only_pair_owner_or_owner_pair(deps.as_ref(), &info.sender, &pair)?;
             execute_deposit_tokens(deps, info, env, pair)
         },
         ExecuteMsg::WithdrawTokens {
@@ -127,6 +154,11 @@ pub fn handle_execute_msg(
             pair_type,
             bonding_curve,
             asset_recipient,
+            nft_selection,
+            limits,
+            schedule,
+            sweep_policy,
+            triggers,
         } => {
             nonpayable(&info)?;
             only_pair_owner(&info, &pair)?;
@@ -139,6 +171,11 @@ pub fn handle_execute_msg(
                 pair_type,
                 bonding_curve,
                 maybe_addr(api, asset_recipient)?,
+                nft_selection,
+                limits,
+                schedule,
+                sweep_policy,
+                triggers,
             )
         },
         ExecuteMsg::SwapNftForTokens {
@@ -147,7 +184,7 @@ pub fn handle_execute_msg(
             asset_recipient,
         } => {
             nonpayable(&info)?;
-            only_active(&pair)?;
+            only_active(&pair, &env.block)?;
             only_nft_owner(&deps.querier, &info, &pair.immutable.collection, &token_id)?;
             execute_swap_nft_for_tokens(
                 deps,
@@ -163,7 +200,7 @@ pub fn handle_execute_msg(
             token_id,
             asset_recipient,
         } => {
-            only_active(&pair)?;
+            only_active(&pair, &env.block)?;
             execute_swap_tokens_for_specific_nft(
                 deps,
                 info,
@@ -176,7 +213,7 @@ pub fn handle_execute_msg(
         ExecuteMsg::SwapTokensForAnyNft {
             asset_recipient,
         } => {
-            only_active(&pair)?;
+            only_active(&pair, &env.block)?;
             execute_swap_tokens_for_any_nft(
                 deps,
                 info,
@@ -185,6 +222,79 @@ pub fn handle_execute_msg(
                 maybe_addr(api, asset_recipient)?,
             )
         },
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender,
+            token_id,
+            msg,
+        }) => {
+            nonpayable(&info)?;
+            ensure_eq!(
+                &info.sender,
+                &pair.immutable.collection,
+                InfinityError::InvalidInput("invalid collection".to_string())
+            );
+
+            let sender = api.addr_validate(&sender)?;
+            let receive_msg = if msg.is_empty() {
+                ReceiveNftMsg::DepositNft {}
+            } else {
+                from_binary::<ReceiveNftMsg>(&msg)?
+            };
+
+            match receive_msg {
+                ReceiveNftMsg::DepositNft {} => {
+                    only_pair_owner_or_owner_pair(deps.as_ref(), &sender, &pair)?;
+                    execute_receive_nft_deposit(deps, pair, token_id)
+                },
+                ReceiveNftMsg::SwapNftForTokens {
+                    min_output,
+                    asset_recipient,
+                } => {
+                    only_active(&pair, &env.block)?;
+                    let seller_recipient =
+                        address_or(maybe_addr(api, asset_recipient)?.as_ref(), &sender);
+                    swap_nft_for_tokens(
+                        deps,
+                        env,
+                        pair,
+                        token_id,
+                        min_output,
+                        seller_recipient,
+                        true,
+                    )
+                },
+            }
+        },
+        ExecuteMsg::SetNftPrices {
+            token_ids,
+            nft_price,
+        } => {
+            nonpayable(&info)?;
+            only_pair_owner(&info, &pair)?;
+            execute_set_nft_prices(deps, info, env, pair, token_ids, nft_price)
+        },
+        ExecuteMsg::SkimExcess {
+            asset_recipient,
+        } => {
+            nonpayable(&info)?;
+            only_pair_owner(&info, &pair)?;
+            execute_skim_excess(deps, info, env, pair, maybe_addr(api, asset_recipient)?)
+        },
+        ExecuteMsg::Reconcile {} => {
+            nonpayable(&info)?;
+            execute_reconcile(deps, info, env, pair)
+        },
+        ExecuteMsg::RefreshQuotes {} => {
+            nonpayable(&info)?;
+            execute_refresh_quotes(deps, info, env, pair)
+        },
+        ExecuteMsg::MigrateLiquidityTo {
+            new_pair,
+        } => {
+            nonpayable(&info)?;
+            only_pair_owner(&info, &pair)?;
+            execute_migrate_liquidity_to(deps, info, env, pair, api.addr_validate(&new_pair)?)
+        },
     }
 }
 
@@ -211,7 +321,7 @@ pub fn execute_deposit_nfts(
     for token_id in &token_ids {
         only_nft_owner(&deps.querier, &info, &collection, token_id)?;
         response = transfer_nft(&collection, token_id, &env.contract.address, response);
-        NFT_DEPOSITS.save(deps.storage, token_id.clone(), &true)?;
+        NFT_DEPOSITS.save(deps.storage, token_id.clone(), &None)?;
     }
 
     pair.internal.total_nfts += token_ids.len() as u64;
@@ -228,6 +338,27 @@ pub fn execute_deposit_nfts(
     Ok((pair, response))
 }
 
+pub fn execute_receive_nft_deposit(
+    deps: DepsMut,
+    mut pair: Pair,
+    token_id: String,
+) -> Result<(Pair, Response), ContractError> {
+    // The NFT has already been transferred to the pair by the collection
+    NFT_DEPOSITS.save(deps.storage, token_id.clone(), &None)?;
+    pair.internal.total_nfts += 1u64;
+
+    let response = Response::new().add_event(
+        NftTransferEvent {
+            ty: "deposit-nfts",
+            pair: &pair,
+            token_ids: &vec![token_id],
+        }
+        .into(),
+    );
+
+    Ok((pair, response))
+}
+
 pub fn execute_withdraw_nfts(
     deps: DepsMut,
     _info: MessageInfo,
@@ -245,6 +376,7 @@ pub fn execute_withdraw_nfts(
 
     let asset_recipient = address_or(asset_recipient.as_ref(), &pair.asset_recipient());
 
+    let total_nfts = pair.internal.total_nfts;
     for token_id in &token_ids {
         response = transfer_nft(&collection, token_id, &asset_recipient, response);
 
@@ -257,6 +389,11 @@ pub fn execute_withdraw_nfts(
     }
 
     if collection == pair.immutable.collection {
+        // Withdrawn NFTs take their share of the cost basis with them
+        let mut pair_stats = PAIR_STATS.may_load(deps.storage)?.unwrap_or_default();
+        pair_stats.remove_cost_basis(total_nfts - pair.internal.total_nfts, total_nfts);
+        PAIR_STATS.save(deps.storage, &pair_stats)?;
+
         response = response.add_event(
             NftTransferEvent {
                 ty: "withdraw-nfts",
@@ -298,9 +435,10 @@ pub fn execute_deposit_tokens(
     _deps: DepsMut,
     info: MessageInfo,
     _env: Env,
-    pair: Pair,
+    mut pair: Pair,
 ) -> Result<(Pair, Response), ContractError> {
     let received_amount = must_pay(&info, &pair.immutable.denom)?;
+    pair.internal.total_tokens += received_amount;
 
     let response = Response::new().add_event(
         TokenTransferEvent {
@@ -325,7 +463,12 @@ pub fn execute_withdraw_tokens(
 
     for fund in &funds {
         if fund.denom == pair.immutable.denom {
-            pair.total_tokens -= fund.amount;
+            pair.internal.total_tokens =
+                pair.internal.total_tokens.checked_sub(fund.amount).map_err(|_| {
+                    InfinityError::InsufficientFunds {
+                        expected: fund.clone(),
+                    }
+                })?;
 
             response = response.add_event(
                 TokenTransferEvent {
@@ -351,13 +494,91 @@ pub fn execute_withdraw_all_tokens(
     pair: Pair,
     asset_recipient: Option<Addr>,
 ) -> Result<(Pair, Response), ContractError> {
-    let all_tokens = deps.querier.query_all_balances(&env.contract.address)?;
+    // Tokens of the pair denom are limited to the tracked reserves, any excess must be skimmed
+    let mut all_tokens = deps
+        .querier
+        .query_all_balances(&env.contract.address)?
+        .into_iter()
+        .filter(|c| c.denom != pair.immutable.denom)
+        .collect::<Vec<Coin>>();
+    if !pair.internal.total_tokens.is_zero() {
+        all_tokens.push(coin(pair.internal.total_tokens.u128(), &pair.immutable.denom));
+    }
     execute_withdraw_tokens(deps, info, env, pair, all_tokens, asset_recipient)
 }
 
+pub fn execute_set_nft_prices(
+    deps: DepsMut,
+    _info: MessageInfo,
+    _env: Env,
+    pair: Pair,
+    token_ids: Vec<TokenId>,
+    nft_price: Option<NftPrice>,
+) -> Result<(Pair, Response), ContractError> {
+    ensure!(
+        !token_ids.is_empty(),
+        InfinityError::InvalidInput("token_ids should not be empty".to_string())
+    );
+
+    if let Some(NftPrice::Premium(amount) | NftPrice::Fixed(amount)) = &nft_price {
+        ensure!(
+            !amount.is_zero(),
+            InfinityError::InvalidInput("nft price must be greater than zero".to_string())
+        );
+    }
+
+    for token_id in &token_ids {
+        ensure!(
+            NFT_DEPOSITS.has(deps.storage, token_id.clone()),
+            InfinityError::InvalidInput("pair does not own NFT".to_string())
+        );
+        NFT_DEPOSITS.save(deps.storage, token_id.clone(), &nft_price)?;
+    }
+
+    let mut event = Event::new("set-nft-prices")
+        .add_attributes(token_ids.iter().map(|token_id| ("token_id", token_id)));
+    event = match &nft_price {
+        Some(NftPrice::Premium(premium)) => event.add_attribute("premium", premium.to_string()),
+        Some(NftPrice::Fixed(price)) => event.add_attribute("fixed_price", price.to_string()),
+        None => event,
+    };
+
+    Ok((pair, Response::new().add_event(event)))
+}
+
+pub fn execute_skim_excess(
+    deps: DepsMut,
+    _info: MessageInfo,
+    env: Env,
+    pair: Pair,
+    asset_recipient: Option<Addr>,
+) -> Result<(Pair, Response), ContractError> {
+    let balance = deps.querier.query_balance(&env.contract.address, &pair.immutable.denom)?;
+    let excess_amount = balance.amount.saturating_sub(pair.internal.total_tokens);
+    ensure!(
+        !excess_amount.is_zero(),
+        InfinityError::InvalidInput("pair does not hold any excess tokens".to_string())
+    );
+
+    let excess = coin(excess_amount.u128(), &pair.immutable.denom);
+
+    let mut response = Response::new().add_event(
+        TokenTransferEvent {
+            ty: "skim-excess",
+            funds: &excess,
+        }
+        .into(),
+    );
+
+    let asset_recipient = address_or(asset_recipient.as_ref(), &pair.asset_recipient());
+    response = transfer_coins(vec![excess], &asset_recipient, response);
+
+    Ok((pair, response))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_update_pair_config(
-    _deps: DepsMut,
+    deps: DepsMut,
     info: MessageInfo,
     _env: Env,
     mut pair: Pair,
@@ -365,11 +586,19 @@ pub fn execute_update_pair_config(
     pair_type: Option<PairType>,
     bonding_curve: Option<BondingCurve>,
     asset_recipient: Option<Addr>,
+    nft_selection: Option<NftSelection>,
+    limits: Option<PairLimits>,
+    schedule: Option<PairSchedule>,
+    sweep_policy: Option<SweepPolicy>,
+    triggers: Option<PairTriggers>,
 ) -> Result<(Pair, Response), ContractError> {
     nonpayable(&info)?;
 
     if let Some(is_active) = is_active {
         pair.config.is_active = is_active;
+        if is_active {
+            pair.clear_triggers();
+        }
     }
 
     if let Some(pair_type) = pair_type {
@@ -377,6 +606,7 @@ pub fn execute_update_pair_config(
     }
 
     if let Some(bonding_curve) = bonding_curve {
+        bonding_curve.validate(deps.api)?;
         pair.config.bonding_curve = bonding_curve;
     }
 
@@ -384,6 +614,28 @@ pub fn execute_update_pair_config(
         pair.config.asset_recipient = Some(asset_recipient);
     }
 
+    if let Some(nft_selection) = nft_selection {
+        pair.config.nft_selection = nft_selection;
+    }
+
+    if let Some(limits) = limits {
+        limits.validate()?;
+        pair.config.limits = limits;
+    }
+
+    if let Some(schedule) = schedule {
+        schedule.validate()?;
+        pair.config.schedule = schedule;
+    }
+
+    if let Some(sweep_policy) = sweep_policy {
+        pair.config.sweep_policy = sweep_policy;
+    }
+
+    if let Some(triggers) = triggers {
+        pair.config.triggers = triggers;
+    }
+
     let response = Response::new().add_event(
         UpdatePairEvent {
             ty: "update-pair",
@@ -400,17 +652,39 @@ pub fn execute_swap_nft_for_tokens(
     deps: DepsMut,
     info: MessageInfo,
     env: Env,
-    mut pair: Pair,
+    pair: Pair,
     token_id: String,
     min_output: Coin,
     asset_recipient: Option<Addr>,
 ) -> Result<(Pair, Response), ContractError> {
+    let seller_recipient = address_or(asset_recipient.as_ref(), &info.sender);
+    swap_nft_for_tokens(deps, env, pair, token_id, min_output, seller_recipient, false)
+}
+
+/// Swaps an NFT for tokens at the pair price. When `nft_received` is true the NFT has
+/// already been sent to the pair, otherwise the pair transfers it from the seller.
+pub fn swap_nft_for_tokens(
+    deps: DepsMut,
+    env: Env,
+    mut pair: Pair,
+    token_id: String,
+    min_output: Coin,
+    seller_recipient: Addr,
+    nft_received: bool,
+) -> Result<(Pair, Response), ContractError> {
+    let global_config = load_unpaused_global_config(deps.as_ref())?;
+
     let quote_summary = pair
         .internal
         .sell_to_pair_quote_summary
         .clone()
         .ok_or(ContractError::InvalidPair("pair cannot produce quote".to_string()))?;
 
+    ensure!(
+        pair.within_limits(quote_summary.total()),
+        ContractError::InvalidPair("pair has reached its purchase limits".to_string())
+    );
+
     let seller_coin = coin(quote_summary.seller_amount.u128(), &pair.immutable.denom);
     ensure!(
         has_coins(&[seller_coin], &min_output),
@@ -420,25 +694,51 @@ pub fn execute_swap_nft_for_tokens(
     let mut response = Response::new();
 
     // Payout token fees
-    let seller_recipient = address_or(asset_recipient.as_ref(), &info.sender);
     response = quote_summary.payout(&pair.immutable.denom, &seller_recipient, response)?;
 
     // Payout NFT, handle reinvest NFTs
-    let nft_recipient = if pair.reinvest_nfts() {
-        NFT_DEPOSITS.save(deps.storage, token_id.clone(), &true)?;
-        env.contract.address
+    if pair.reinvest_nfts() {
+        NFT_DEPOSITS.save(deps.storage, token_id.clone(), &None)?;
+        if !nft_received {
+            response = transfer_nft(
+                &pair.immutable.collection,
+                &token_id,
+                &env.contract.address,
+                response,
+            );
+        }
     } else {
-        pair.asset_recipient()
-    };
-    response = transfer_nft(&pair.immutable.collection, &token_id, &nft_recipient, response);
+        response =
+            transfer_nft(&pair.immutable.collection, &token_id, &pair.asset_recipient(), response);
+    }
+
+    // Update pair state, triggers are evaluated against the stats including the swap
+    let mut pair_stats = PAIR_STATS.may_load(deps.storage)?.unwrap_or_default();
+    pair_stats.record_sell_to_pair(&quote_summary, pair.reinvest_nfts());
+    PAIR_STATS.save(deps.storage, &pair_stats)?;
 
-    // Update pair state
-    pair.swap_nft_for_tokens();
+    let fired_triggers = pair.swap_nft_for_tokens(&pair_stats);
+
+    // Record the trade price with the oracle
+    response = pair.record_trade(
+        &global_config.infinity_index,
+        &seller_recipient,
+        quote_summary.total(),
+        response,
+    );
+
+    response = response.set_data(to_binary(&swap_data(
+        &env.contract.address,
+        &pair,
+        TransactionType::UserSubmitsNfts,
+        &token_id,
+        &quote_summary,
+    ))?);
 
     // Attach swap event
     response = response.add_event(
         SwapEvent {
-            ty: "swap-nft-for-tokens",
+            ty: SWAP_NFT_FOR_TOKENS_EVENT,
             pair: &pair,
             token_id: &token_id,
             sender_recipient: &seller_recipient,
@@ -447,24 +747,104 @@ pub fn execute_swap_nft_for_tokens(
         .into(),
     );
 
+    for trigger in &fired_triggers {
+        response = response.add_event(
+            PairTriggerEvent {
+                pair: &pair,
+                trigger,
+            }
+            .into(),
+        );
+    }
+
     Ok((pair, response))
 }
 
 pub fn execute_swap_tokens_for_specific_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    pair: Pair,
+    token_id: String,
+    asset_recipient: Option<Addr>,
+) -> Result<(Pair, Response), ContractError> {
+    let nft_price = NFT_DEPOSITS.may_load(deps.storage, token_id.clone())?.flatten();
+
+    let payout_context = load_pair_payout_context(deps.as_ref(), &pair)?;
+    let quote_summary = pair.build_nft_buy_from_pair_quote_summary(&payout_context, &nft_price);
+
+    swap_tokens_for_nft(deps, info, env, pair, token_id, quote_summary, asset_recipient)
+}
+
+pub fn execute_swap_tokens_for_any_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    pair: Pair,
+    asset_recipient: Option<Addr>,
+) -> Result<(Pair, Response), ContractError> {
+    let nft_deposits = NFT_DEPOSITS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(TokenId, Option<NftPrice>)>>>()?;
+
+    ensure!(
+        !nft_deposits.is_empty(),
+        ContractError::InvalidPair("pair does not have any NFTs".to_string())
+    );
+
+    let payout_context = load_pair_payout_context(deps.as_ref(), &pair)?;
+
+    // Find the NFTs with the cheapest quote, in token id order. The cheapest quote is the
+    // buy from pair quote stored in the index.
+    let mut cheapest_token_ids: Vec<TokenId> = vec![];
+    let mut cheapest_quote_summary: Option<QuoteSummary> = None;
+    for (token_id, nft_price) in nft_deposits {
+        let quote_summary = pair.build_nft_buy_from_pair_quote_summary(&payout_context, &nft_price);
+        if let Some(quote_summary) = quote_summary {
+            match &cheapest_quote_summary {
+                Some(cheapest) if quote_summary.total() > cheapest.total() => {},
+                Some(cheapest) if quote_summary.total() == cheapest.total() => {
+                    cheapest_token_ids.push(token_id);
+                },
+                _ => {
+                    cheapest_token_ids = vec![token_id];
+                    cheapest_quote_summary = Some(quote_summary);
+                },
+            }
+        }
+    }
+
+    let quote_summary = cheapest_quote_summary
+        .ok_or(ContractError::InvalidPair("pair cannot produce quote".to_string()))?;
+
+    let token_id = match pair.config.nft_selection {
+        NftSelection::Ordered => cheapest_token_ids.swap_remove(0),
+        NftSelection::Random => {
+            let idx =
+                select_random_index(deps.storage, &env, &info.sender, cheapest_token_ids.len())?;
+            cheapest_token_ids.swap_remove(idx)
+        },
+    };
+
+    swap_tokens_for_nft(deps, info, env, pair, token_id, Some(quote_summary), asset_recipient)
+}
+
+/// Swaps tokens for an NFT held by the pair, at the quote for that NFT
+pub fn swap_tokens_for_nft(
     deps: DepsMut,
     info: MessageInfo,
     env: Env,
     mut pair: Pair,
     token_id: String,
+    quote_summary: Option<QuoteSummary>,
     asset_recipient: Option<Addr>,
 ) -> Result<(Pair, Response), ContractError> {
+    let global_config = load_unpaused_global_config(deps.as_ref())?;
+
     let received_amount = must_pay(&info, &pair.immutable.denom)?;
 
-    let quote_summary = pair
-        .internal
-        .buy_from_pair_quote_summary
-        .clone()
-        .ok_or(ContractError::InvalidPair("pair cannot produce quote".to_string()))?;
+    let quote_summary =
+        quote_summary.ok_or(ContractError::InvalidPair("pair cannot produce quote".to_string()))?;
 
     let quote_total = quote_summary.total();
 
@@ -477,8 +857,9 @@ pub fn execute_swap_tokens_for_specific_nft(
     let mut response = Response::new();
 
     // Payout token fees, handle reinvest tokens
+    let pair_address = env.contract.address;
     let seller_recipient = if pair.reinvest_tokens() {
-        env.contract.address
+        pair_address.clone()
     } else {
         pair.asset_recipient()
     };
@@ -494,14 +875,42 @@ pub fn execute_swap_tokens_for_specific_nft(
     let nft_recipient = address_or(asset_recipient.as_ref(), &info.sender);
     response = transfer_nft(&pair.immutable.collection, &token_id, &nft_recipient, response);
 
-    // Update pair state
-    pair.total_tokens -= received_amount;
-    pair.swap_tokens_for_nft();
+    // Update pair state, stats are recorded against the NFTs held before the swap
+    let mut pair_stats = PAIR_STATS.may_load(deps.storage)?.unwrap_or_default();
+    pair_stats.record_buy_from_pair(&quote_summary, pair.internal.total_nfts);
+    PAIR_STATS.save(deps.storage, &pair_stats)?;
+
+    let fired_triggers = pair.swap_tokens_for_nft(quote_summary.seller_amount, &pair_stats);
+
+    // Sweep reinvested tokens above the sweep threshold to the asset recipient
+    if let Some(sweep_amount) = pair.sweep_tokens() {
+        let funds = coin(sweep_amount.u128(), &pair.immutable.denom);
+        response = response.add_event(
+            TokenTransferEvent {
+                ty: "sweep-tokens",
+                funds: &funds,
+            }
+            .into(),
+        );
+        response = transfer_coins(vec![funds], &pair.asset_recipient(), response);
+    }
+
+    // Record the trade price with the oracle
+    response =
+        pair.record_trade(&global_config.infinity_index, &nft_recipient, quote_total, response);
+
+    response = response.set_data(to_binary(&swap_data(
+        &pair_address,
+        &pair,
+        TransactionType::UserSubmitsTokens,
+        &token_id,
+        &quote_summary,
+    ))?);
 
     // Attach swap event
     response = response.add_event(
         SwapEvent {
-            ty: "swap-tokens-for-nft",
+            ty: SWAP_TOKENS_FOR_NFT_EVENT,
             pair: &pair,
             token_id: &token_id,
             sender_recipient: &nft_recipient,
@@ -510,23 +919,186 @@ pub fn execute_swap_tokens_for_specific_nft(
         .into(),
     );
 
+    for trigger in &fired_triggers {
+        response = response.add_event(
+            PairTriggerEvent {
+                pair: &pair,
+                trigger,
+            }
+            .into(),
+        );
+    }
+
     Ok((pair, response))
 }
 
-pub fn execute_swap_tokens_for_any_nft(
+/// Describes a swap with the pair, set as the data of the swap response
+fn swap_data(
+    pair_address: &Addr,
+    pair: &Pair,
+    transaction_type: TransactionType,
+    token_id: &str,
+    quote_summary: &QuoteSummary,
+) -> SwapData {
+    SwapData {
+        pair: pair_address.clone(),
+        transaction_type,
+        token_id: token_id.to_string(),
+        price: coin(quote_summary.total().u128(), &pair.immutable.denom),
+        fair_burn_fee: quote_summary.fair_burn.amount,
+        royalty_fee: quote_summary.royalty_amount(),
+        swap_fee: quote_summary.swap.as_ref().map_or(Uint128::zero(), |swap| swap.amount),
+        seller_amount: quote_summary.seller_amount,
+    }
+}
+
+pub fn execute_reconcile(
     deps: DepsMut,
     info: MessageInfo,
     env: Env,
+    mut pair: Pair,
+) -> Result<(Pair, Response), ContractError> {
+    let reconciliation = load_nft_reconciliation(deps.as_ref(), &env.contract.address, &pair)?;
+
+    for token_id in &reconciliation.stale_deposits {
+        NFT_DEPOSITS.remove(deps.storage, token_id.to_string());
+    }
+
+    // NFTs that have left the pair take their share of the cost basis with them
+    if !reconciliation.stale_deposits.is_empty() {
+        let mut pair_stats = PAIR_STATS.may_load(deps.storage)?.unwrap_or_default();
+        pair_stats.remove_cost_basis(
+            reconciliation.stale_deposits.len() as u64,
+            pair.internal.total_nfts,
+        );
+        PAIR_STATS.save(deps.storage, &pair_stats)?;
+    }
+
+    // Only the owner can add NFTs sent directly to the pair to its inventory
+    let is_owner = info.sender == pair.immutable.owner;
+    if is_owner {
+        for token_id in &reconciliation.missing_deposits {
+            NFT_DEPOSITS.save(deps.storage, token_id.to_string(), &None)?;
+        }
+    }
+
+    let num_deposits = NFT_DEPOSITS.keys(deps.storage, None, None, Order::Ascending).count();
+    pair.internal.total_nfts = num_deposits as u64;
+
+    let mut event = Event::new("reconcile-nfts")
+        .add_attribute("total_nfts_before", reconciliation.total_nfts.to_string())
+        .add_attributes(pair.get_event_attrs(vec!["total_nfts"]))
+        .add_attributes(
+            reconciliation.stale_deposits.iter().map(|token_id| ("removed_token_id", token_id)),
+        );
+    if is_owner {
+        event = event.add_attributes(
+            reconciliation.missing_deposits.iter().map(|token_id| ("added_token_id", token_id)),
+        );
+    }
+
+    Ok((pair, Response::new().add_event(event)))
+}
+
+pub fn execute_refresh_quotes(
+    _deps: DepsMut,
+    _info: MessageInfo,
+    _env: Env,
     pair: Pair,
-    asset_recipient: Option<Addr>,
 ) -> Result<(Pair, Response), ContractError> {
-    let token_id = NFT_DEPOSITS
-        .range(deps.storage, None, None, Order::Ascending)
-        .take(1)
-        .map(|item| item.map(|(k, _)| k))
-        .collect::<StdResult<Vec<String>>>()?
-        .pop()
-        .ok_or(ContractError::InvalidPair("pair does not have any NFTs".to_string()))?;
+    // Quotes are recomputed with the latest payout context, and the indices are updated,
+    // after every execute message, so no further handling is required here.
+    let response = Response::new().add_event(Event::new("refresh-quotes"));
+
+    Ok((pair, response))
+}
+
+pub fn execute_migrate_liquidity_to(
+    deps: DepsMut,
+    _info: MessageInfo,
+    env: Env,
+    mut pair: Pair,
+    new_pair: Addr,
+) -> Result<(Pair, Response), ContractError> {
+    ensure!(
+        new_pair != env.contract.address,
+        InfinityError::InvalidInput("cannot migrate liquidity to the same pair".to_string())
+    );
+    let target_pair = load_owner_pair(deps.as_ref(), &new_pair, &pair)?;
+
+    let token_ids = NFT_DEPOSITS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<TokenId>>>()?;
+    for token_id in &token_ids {
+        NFT_DEPOSITS.remove(deps.storage, token_id.clone());
+    }
+
+    let mut response = Response::new();
+    let asset_recipient = pair.asset_recipient();
 
-    execute_swap_tokens_for_specific_nft(deps, info, env, pair, token_id, asset_recipient)
+    // NFTs are sent with an empty message, which the target pair handles as a deposit
+    let same_collection = target_pair.immutable.collection == pair.immutable.collection;
+    for token_id in &token_ids {
+        response = if same_collection {
+            response.add_message(WasmMsg::Execute {
+                contract_addr: pair.immutable.collection.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::SendNft {
+                    contract: new_pair.to_string(),
+                    token_id: token_id.clone(),
+                    msg: Binary::default(),
+                })?,
+                funds: vec![],
+            })
+        } else {
+            transfer_nft(&pair.immutable.collection, token_id, &asset_recipient, response)
+        };
+    }
+
+    if !token_ids.is_empty() {
+        response = response.add_event(
+            NftTransferEvent {
+                ty: "migrate-nfts",
+                pair: &pair,
+                token_ids: &token_ids,
+            }
+            .into(),
+        );
+    }
+
+    if !pair.internal.total_tokens.is_zero() {
+        let funds = coin(pair.internal.total_tokens.u128(), &pair.immutable.denom);
+        response = response.add_event(
+            TokenTransferEvent {
+                ty: "migrate-tokens",
+                funds: &funds,
+            }
+            .into(),
+        );
+        response = if target_pair.immutable.denom == pair.immutable.denom {
+            response.add_message(WasmMsg::Execute {
+                contract_addr: new_pair.to_string(),
+                msg: to_binary(&ExecuteMsg::DepositTokens {})?,
+                funds: vec![funds],
+            })
+        } else {
+            transfer_coins(vec![funds], &asset_recipient, response)
+        };
+    }
+
+    // The migrated NFTs take the cost basis of the pair with them
+    let mut pair_stats = PAIR_STATS.may_load(deps.storage)?.unwrap_or_default();
+    pair_stats.remove_cost_basis(pair.internal.total_nfts, pair.internal.total_nfts);
+    PAIR_STATS.save(deps.storage, &pair_stats)?;
+
+    pair.internal.total_nfts = 0u64;
+    pair.internal.total_tokens = Uint128::zero();
+    pair.config.is_active = false;
+
+    response = response.add_event(
+        Event::new("migrate-liquidity")
+            .add_attribute("new_pair", new_pair.to_string())
+            .add_attribute("num_nfts", token_ids.len().to_string()),
+    );
+
+    Ok((pair, response))
 }
@@ -1,19 +1,40 @@
 use crate::{
+    constants::RECONCILE_PAGE_LIMIT,
+    msg::{NftReconciliationResponse, QueryMsg},
     pair::Pair,
-    state::{QuoteSummary, TokenPayment, PAIR_CONFIG, PAIR_IMMUTABLE, PAIR_INTERNAL},
+    state::{
+        BondingCurve, NftPrice, QuoteSummary, TokenId, TokenPayment, INFINITY_GLOBAL, NFT_DEPOSITS,
+        NFT_SELECTION_NONCE, PAIR_CONFIG, PAIR_IMMUTABLE, PAIR_INTERNAL,
+    },
     ContractError,
 };
 
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    ensure_eq, Addr, Coin, Decimal, Deps, MessageInfo, QuerierWrapper, Storage, Uint128,
+    ensure, ensure_eq, Addr, BlockInfo, Coin, Decimal, Deps, Env, MessageInfo, Order, StdResult,
+    Storage, Uint128,
+};
+use cw721::{Cw721QueryMsg, TokensResponse};
+use infinity_global::{
+    load_global_config, load_min_price, load_royalty_settings, state::GlobalConfig, RoyaltySettings,
 };
-use infinity_global::{load_global_config, load_min_price, state::GlobalConfig};
 use infinity_shared::InfinityError;
+use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
+use sha2::{Digest, Sha256};
 use stargaze_royalty_registry::{
     msg::{QueryMsg as RoyaltyRegistryQueryMsg, RoyaltyPaymentResponse},
     state::RoyaltyEntry,
 };
-use std::cmp::min;
+use std::cmp::{max, min};
+use std::collections::BTreeSet;
+
+/// Loads the global config for a swap, erroring when trading is paused
+pub fn load_unpaused_global_config(deps: Deps) -> Result<GlobalConfig<Addr>, ContractError> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let global_config = load_global_config(&deps.querier, &infinity_global)?;
+    ensure!(!global_config.paused, ContractError::InvalidPair("trading is paused".to_string()));
+    Ok(global_config)
+}
 
 pub fn only_pair_owner(info: &MessageInfo, pair: &Pair) -> Result<(), ContractError> {
     ensure_eq!(
@@ -24,62 +45,320 @@ pub fn only_pair_owner(info: &MessageInfo, pair: &Pair) -> Result<(), ContractEr
     Ok(())
 }
 
-pub fn only_active(pair: &Pair) -> Result<(), ContractError> {
+/// The pair owner, or another infinity pair of the same owner migrating its liquidity,
+/// can deposit into the pair
+pub fn only_pair_owner_or_owner_pair(
+    deps: Deps,
+    sender: &Addr,
+    pair: &Pair,
+) -> Result<(), ContractError> {
+    if sender == pair.immutable.owner {
+        return Ok(());
+    }
+    load_owner_pair(deps, sender, pair).map_err(|_| {
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string())
+    })?;
+    Ok(())
+}
+
+/// Loads another infinity pair owned by the owner of the pair, erroring when the address
+/// is not a pair created by the infinity factory or when it is owned by someone else
+pub fn load_owner_pair(deps: Deps, address: &Addr, pair: &Pair) -> Result<Pair, ContractError> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let global_config = load_global_config(&deps.querier, &infinity_global)?;
+    let contract_info = deps.querier.query_wasm_contract_info(address)?;
+    ensure_eq!(
+        global_config.infinity_factory,
+        contract_info.creator,
+        ContractError::InvalidPair("address is not an infinity pair".to_string())
+    );
+
+    let other_pair = deps.querier.query_wasm_smart::<Pair>(address, &QueryMsg::Pair {})?;
+    ensure_eq!(
+        other_pair.immutable.owner,
+        pair.immutable.owner,
+        ContractError::InvalidPair("pair is owned by another owner".to_string())
+    );
+
+    Ok(other_pair)
+}
+
+/// The cheapest listing prices of the NFTs held by the pair
+#[derive(Default)]
+pub struct CheapestNftPrices {
+    /// The smallest premium over the pair price, NFTs without a listing price have a zero premium
+    pub premium: Option<Uint128>,
+    /// The smallest fixed listing price
+    pub fixed: Option<Uint128>,
+}
+
+pub fn load_cheapest_nft_prices(storage: &dyn Storage) -> StdResult<CheapestNftPrices> {
+    let mut cheapest_nft_prices = CheapestNftPrices::default();
+
+    for nft_price in NFT_DEPOSITS.range(storage, None, None, Order::Ascending) {
+        let (premium, fixed) = match nft_price?.1 {
+            None => (Some(Uint128::zero()), None),
+            Some(NftPrice::Premium(premium)) => (Some(premium), None),
+            Some(NftPrice::Fixed(price)) => (None, Some(price)),
+        };
+        cheapest_nft_prices.premium = min_option(cheapest_nft_prices.premium, premium);
+        cheapest_nft_prices.fixed = min_option(cheapest_nft_prices.fixed, fixed);
+    }
+
+    Ok(cheapest_nft_prices)
+}
+
+/// The listing prices of the NFTs held by the pair, used to simulate consecutive swaps.
+/// NFTs are removed from the simulated inventory as they are bought from the pair, so that
+/// each simulated swap is quoted with the cheapest NFT that remains in the pair.
+#[cw_serde]
+#[derive(Default)]
+pub struct SimNftPrices {
+    /// The number of NFTs without a listing price
+    pub unlisted: u64,
+    /// The listing premiums over the pair price, in descending order
+    pub premiums: Vec<Uint128>,
+    /// The fixed listing prices, in descending order
+    pub fixed_prices: Vec<Uint128>,
+}
+
+impl SimNftPrices {
+    pub fn new(nft_prices: impl IntoIterator<Item = Option<NftPrice>>) -> Self {
+        let mut sim_nft_prices = SimNftPrices::default();
+
+        for nft_price in nft_prices {
+            match nft_price {
+                None => sim_nft_prices.unlisted += 1u64,
+                Some(NftPrice::Premium(premium)) => sim_nft_prices.premiums.push(premium),
+                Some(NftPrice::Fixed(price)) => sim_nft_prices.fixed_prices.push(price),
+            }
+        }
+
+        sim_nft_prices.premiums.sort_by(|a, b| b.cmp(a));
+        sim_nft_prices.fixed_prices.sort_by(|a, b| b.cmp(a));
+
+        sim_nft_prices
+    }
+
+    /// Simulates a pair holding the given number of NFTs, none of which have a listing price
+    pub fn unlisted(num_nfts: u64) -> Self {
+        SimNftPrices {
+            unlisted: num_nfts,
+            ..SimNftPrices::default()
+        }
+    }
+
+    pub fn cheapest(&self) -> CheapestNftPrices {
+        CheapestNftPrices {
+            premium: if self.unlisted > 0 {
+                Some(Uint128::zero())
+            } else {
+                self.premiums.last().copied()
+            },
+            fixed: self.fixed_prices.last().copied(),
+        }
+    }
+
+    /// Removes the cheapest NFT with the given listing price from the simulated inventory
+    pub fn remove(&mut self, nft_price: &NftPrice) {
+        match nft_price {
+            NftPrice::Premium(premium) if premium.is_zero() && self.unlisted > 0 => {
+                self.unlisted -= 1u64;
+            },
+            NftPrice::Premium(_) => {
+                self.premiums.pop();
+            },
+            NftPrice::Fixed(_) => {
+                self.fixed_prices.pop();
+            },
+        }
+    }
+
+    /// Adds an NFT without a listing price to the simulated inventory
+    pub fn add_unlisted(&mut self) {
+        self.unlisted += 1u64;
+    }
+}
+
+pub fn load_sim_nft_prices(storage: &dyn Storage) -> StdResult<SimNftPrices> {
+    let nft_prices = NFT_DEPOSITS
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(_, nft_price)| nft_price))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(SimNftPrices::new(nft_prices))
+}
+
+fn min_option(a: Option<Uint128>, b: Option<Uint128>) -> Option<Uint128> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(min(a, b)),
+        _ => a.or(b),
+    }
+}
+
+pub fn only_active(pair: &Pair, block: &BlockInfo) -> Result<(), ContractError> {
     ensure_eq!(
         pair.config.is_active,
         true,
         ContractError::InvalidPair("pair is inactive".to_string())
     );
+    ensure!(
+        pair.config.schedule.contains(block),
+        ContractError::InvalidPair("pair is outside of its schedule".to_string())
+    );
+    ensure!(
+        !matches!(pair.config.bonding_curve, BondingCurve::OracleAnchored { .. })
+            || pair.internal.reference_price.is_some(),
+        ContractError::InvalidPair("pair reference price is stale".to_string())
+    );
     Ok(())
 }
 
-pub fn load_pair(
-    contract: &Addr,
-    storage: &dyn Storage,
-    querier: &QuerierWrapper,
-) -> Result<Pair, ContractError> {
+pub fn load_pair(storage: &dyn Storage) -> Result<Pair, ContractError> {
     let immutable = PAIR_IMMUTABLE.load(storage)?;
     let config = PAIR_CONFIG.load(storage)?;
     let internal = PAIR_INTERNAL.load(storage)?;
-    let total_tokens = querier.query_balance(contract, immutable.denom.clone())?.amount;
-    Ok(Pair::new(immutable, config, internal, total_tokens))
+    Ok(Pair::new(immutable, config, internal))
+}
+
+/// Compares the NFT deposits recorded by the pair with the NFTs the pair owns in the collection
+pub fn load_nft_reconciliation(
+    deps: Deps,
+    pair_address: &Addr,
+    pair: &Pair,
+) -> StdResult<NftReconciliationResponse> {
+    let mut owned_token_ids: BTreeSet<TokenId> = BTreeSet::new();
+    let mut start_after: Option<TokenId> = None;
+    loop {
+        let tokens = deps
+            .querier
+            .query_wasm_smart::<TokensResponse>(
+                &pair.immutable.collection,
+                &Cw721QueryMsg::Tokens {
+                    owner: pair_address.to_string(),
+                    start_after: start_after.clone(),
+                    limit: Some(RECONCILE_PAGE_LIMIT),
+                },
+            )?
+            .tokens;
+
+        let num_tokens = tokens.len();
+        start_after = tokens.last().cloned();
+        owned_token_ids.extend(tokens);
+
+        if num_tokens < RECONCILE_PAGE_LIMIT as usize {
+            break;
+        }
+    }
+
+    let deposit_token_ids = NFT_DEPOSITS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<BTreeSet<TokenId>>>()?;
+
+    Ok(NftReconciliationResponse {
+        total_nfts: pair.internal.total_nfts,
+        num_deposits: deposit_token_ids.len() as u64,
+        num_owned: owned_token_ids.len() as u64,
+        missing_deposits: owned_token_ids.difference(&deposit_token_ids).cloned().collect(),
+        stale_deposits: deposit_token_ids.difference(&owned_token_ids).cloned().collect(),
+    })
+}
+
+/// Selects an index below `len` using entropy derived from the block, the transaction index,
+/// the sender and a nonce stored by the pair. The nonce is incremented on each selection,
+/// so that selections within the same transaction differ.
+pub fn select_random_index(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    len: usize,
+) -> StdResult<usize> {
+    let nonce = NFT_SELECTION_NONCE.may_load(storage)?.unwrap_or_default();
+    NFT_SELECTION_NONCE.save(storage, &(nonce + 1))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(env.block.height.to_be_bytes());
+    hasher.update(env.block.time.nanos().to_be_bytes());
+    hasher.update(env.transaction.as_ref().map_or(0u32, |t| t.index).to_be_bytes());
+    hasher.update(sender.as_bytes());
+    hasher.update(nonce.to_be_bytes());
+    let hash = hasher.finalize();
+
+    let mut seed = [0u8; 8];
+    seed.copy_from_slice(&hash[0..8]);
+    Ok((u64::from_be_bytes(seed) % len as u64) as usize)
 }
 
 pub struct PayoutContext {
     pub global_config: GlobalConfig<Addr>,
     pub royalty_entry: Option<RoyaltyEntry>,
+    pub royalty_settings: Option<RoyaltySettings<Addr>>,
     pub min_price: Coin,
     pub infinity_global: Addr,
     pub denom: String,
+    /// Whether the royalty info of the collection could not be loaded, in which case the
+    /// quotes pay no royalties
+    pub royalty_skipped: bool,
 }
 
 impl PayoutContext {
+    fn _derive_royalty_payments(&self, sale_ammount: Uint128) -> Vec<TokenPayment> {
+        let (splits, min_royalty_fee_percent) = match &self.royalty_settings {
+            Some(royalty_settings) => {
+                (royalty_settings.splits.as_slice(), royalty_settings.min_royalty_fee_percent)
+            },
+            None => (&[][..], None),
+        };
+
+        let mut royalty_fee_percent =
+            self.royalty_entry.as_ref().map_or(Decimal::zero(), |r| r.share);
+        if let Some(min_royalty_fee_percent) = min_royalty_fee_percent {
+            royalty_fee_percent = max(royalty_fee_percent, min_royalty_fee_percent);
+        }
+        royalty_fee_percent = min(royalty_fee_percent, self.global_config.max_royalty_fee_percent);
+
+        if royalty_fee_percent.is_zero() {
+            return vec![];
+        }
+
+        let royalty_amount = sale_ammount.mul_ceil(royalty_fee_percent);
+
+        if splits.is_empty() {
+            return self.royalty_entry.as_ref().map_or(vec![], |royalty_entry| {
+                vec![TokenPayment {
+                    recipient: royalty_entry.recipient.clone(),
+                    amount: royalty_amount,
+                }]
+            });
+        }
+
+        // Each recipient receives its weighted share of the royalty,
+        // any remainder from rounding is paid to the first recipient.
+        let mut royalties = splits
+            .iter()
+            .map(|split| TokenPayment {
+                recipient: split.recipient.clone(),
+                amount: royalty_amount.mul_floor(split.weight),
+            })
+            .collect::<Vec<TokenPayment>>();
+        let remainder = royalty_amount - royalties.iter().map(|r| r.amount).sum::<Uint128>();
+        royalties[0].amount += remainder;
+        royalties.retain(|r| !r.amount.is_zero());
+
+        royalties
+    }
+
     fn _derive_quote_summary_parts(
         &self,
         pair: &Pair,
         sale_ammount: Uint128,
-    ) -> (TokenPayment, Option<TokenPayment>, Option<TokenPayment>) {
+    ) -> (TokenPayment, Vec<TokenPayment>, Option<TokenPayment>) {
         let fair_burn = TokenPayment {
             recipient: self.global_config.fair_burn.clone(),
             amount: sale_ammount.mul_ceil(self.global_config.fair_burn_fee_percent),
         };
 
-        let royalty = if let Some(royalty_entry) = &self.royalty_entry {
-            let royalty_fee_percent = min(
-                self.royalty_entry.as_ref().map_or(Decimal::zero(), |r| r.share),
-                self.global_config.max_royalty_fee_percent,
-            );
-            if royalty_fee_percent > Decimal::zero() {
-                Some(TokenPayment {
-                    recipient: royalty_entry.recipient.clone(),
-                    amount: sale_ammount.mul_ceil(royalty_fee_percent),
-                })
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let royalties = self._derive_royalty_payments(sale_ammount);
 
         let swap_fee_percent =
             min(pair.swap_fee_percent(), self.global_config.max_swap_fee_percent);
@@ -92,7 +371,7 @@ impl PayoutContext {
             None
         };
 
-        (fair_burn, royalty, swap)
+        (fair_burn, royalties, swap)
     }
 
     pub fn build_buy_from_pair_quote_summary(
@@ -100,11 +379,11 @@ impl PayoutContext {
         pair: &Pair,
         sale_ammount: Uint128,
     ) -> Option<QuoteSummary> {
-        if sale_ammount < self.min_price.amount {
+        if self.global_config.paused || sale_ammount < self.min_price.amount {
             return None;
         }
 
-        let (fair_burn, royalty, swap) = self._derive_quote_summary_parts(pair, sale_ammount);
+        let (fair_burn, royalties, swap) = self._derive_quote_summary_parts(pair, sale_ammount);
 
         // The seller (pair owner) receives the full sale amount when buying a user buys an NFT from the pair.
         // Fees are added on top of the sale amount, and are paid by the buyer.
@@ -112,7 +391,7 @@ impl PayoutContext {
 
         Some(QuoteSummary {
             fair_burn,
-            royalty,
+            royalties,
             swap,
             seller_amount,
         })
@@ -123,27 +402,33 @@ impl PayoutContext {
         pair: &Pair,
         sale_ammount: Uint128,
     ) -> Option<QuoteSummary> {
-        if sale_ammount < self.min_price.amount {
+        if self.global_config.paused || sale_ammount < self.min_price.amount {
             return None;
         }
 
-        let (fair_burn, royalty, swap) = self._derive_quote_summary_parts(pair, sale_ammount);
+        let (fair_burn, royalties, swap) = self._derive_quote_summary_parts(pair, sale_ammount);
 
         // The seller (user) receives the the sale amount minus the fees, when selling an NFT to the pair.
         let seller_amount = sale_ammount
             - fair_burn.amount
-            - royalty.as_ref().map_or(Uint128::zero(), |r| r.amount)
+            - royalties.iter().map(|r| r.amount).sum::<Uint128>()
             - swap.as_ref().map_or(Uint128::zero(), |s| s.amount);
 
         Some(QuoteSummary {
             fair_burn,
-            royalty,
+            royalties,
             swap,
             seller_amount,
         })
     }
 }
 
+/// Loads the payout context for the collection and denom of the pair
+pub fn load_pair_payout_context(deps: Deps, pair: &Pair) -> Result<PayoutContext, ContractError> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    load_payout_context(deps, &infinity_global, &pair.immutable.collection, &pair.immutable.denom)
+}
+
 pub fn load_payout_context(
     deps: Deps,
     infinity_global: &Addr,
@@ -163,23 +448,63 @@ pub fn load_payout_context(
         },
     )?;
 
-    let royalty_entry = if let Some(royalty_protocol) = royalty_payment_response.royalty_protocol {
-        Some(royalty_protocol.royalty_entry)
+    let (royalty_entry, royalty_skipped) = if let Some(royalty_protocol) =
+        royalty_payment_response.royalty_protocol
+    {
+        (Some(royalty_protocol.royalty_entry), false)
     } else if let Some(royalty_default) = royalty_payment_response.royalty_default {
-        Some(RoyaltyEntry {
-            recipient: royalty_default.royalty_entry.recipient,
-            share: global_config.default_royalty_fee_percent,
-            updated: None,
-        })
+        (
+            Some(RoyaltyEntry {
+                recipient: royalty_default.royalty_entry.recipient,
+                share: global_config.default_royalty_fee_percent,
+                updated: None,
+            }),
+            false,
+        )
     } else {
-        None
+        // Fall back to the royalty info stored on the collection when the registry has no entry.
+        // Without royalty info the collection creator is the recipient with a zero share, so that
+        // a minimum royalty set through the royalty settings is still paid out. Collections that
+        // do not answer the sg721 collection info query pay no royalties, which is recorded in
+        // the payout context so that it can be reported.
+        let royalty_entry = deps
+            .querier
+            .query_wasm_smart::<CollectionInfoResponse>(
+                collection,
+                &Sg721QueryMsg::CollectionInfo {},
+            )
+            .ok()
+            .and_then(|collection_info| match collection_info.royalty_info {
+                Some(royalty_info) => {
+                    deps.api.addr_validate(&royalty_info.payment_address).ok().map(|recipient| {
+                        RoyaltyEntry {
+                            recipient,
+                            share: royalty_info.share,
+                            updated: None,
+                        }
+                    })
+                },
+                None => deps.api.addr_validate(&collection_info.creator).ok().map(|recipient| {
+                    RoyaltyEntry {
+                        recipient,
+                        share: Decimal::zero(),
+                        updated: None,
+                    }
+                }),
+            });
+        let royalty_skipped = royalty_entry.is_none();
+        (royalty_entry, royalty_skipped)
     };
 
+    let royalty_settings = load_royalty_settings(&deps.querier, infinity_global, collection)?;
+
     Ok(PayoutContext {
         global_config,
         royalty_entry,
+        royalty_settings,
         min_price,
         infinity_global: infinity_global.clone(),
         denom: denom.to_string(),
+        royalty_skipped,
     })
 }
@@ -11,7 +11,7 @@ use crate::{
 use cosmwasm_std::{ensure_eq, DepsMut, Env, MessageInfo, Uint128};
 use cw2::set_contract_version;
 use cw_utils::may_pay;
-use infinity_global::{load_global_config, load_min_price};
+use infinity_global::{load_global_config, load_min_price, load_royalty_settings};
 use infinity_shared::InfinityError;
 use sg_marketplace_common::nft::only_tradable;
 use sg_std::Response;
@@ -73,14 +73,22 @@ pub fn instantiate(
         );
     }
 
+    let royalty_settings =
+        load_royalty_settings(&deps.querier, &infinity_global, &pair.immutable.collection)?;
+
     let payout_context = PayoutContext {
         global_config,
         royalty_entry,
+        royalty_settings,
         min_price,
         infinity_global,
         denom: pair.immutable.denom.clone(),
+        royalty_skipped: false,
     };
 
+    pair.update_limit_window(&env.block);
+    pair.update_schedule(&env.block);
+    pair.update_reference_price(&deps.querier, &env.block);
     response = pair.save_and_update_indices(deps.storage, &payout_context, response)?;
 
     response = response
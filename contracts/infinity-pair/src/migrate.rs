@@ -1,12 +1,18 @@
 use crate::{
-    constants::{CONTRACT_NAME, CONTRACT_VERSION},
+    constants::{TopKey, CONTRACT_NAME, CONTRACT_VERSION},
     error::ContractError,
     events::{PairInternalEvent, UpdatePairEvent},
     helpers::{load_pair, load_payout_context},
-    state::INFINITY_GLOBAL,
+    msg::{MigrateMsg, StateTransform},
+    state::{
+        LimitWindow, PairInternal, QuoteSummary, TokenId, TokenPayment, INFINITY_GLOBAL,
+        NFT_DEPOSITS, PAIR_CONFIG, PAIR_IMMUTABLE, PAIR_INTERNAL,
+    },
 };
 
-use cosmwasm_std::{ensure, DepsMut, Empty, Env, Event, StdError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{ensure, DepsMut, Env, Event, Order, StdError, StdResult, Uint128};
+use cw_storage_plus::{Item, Map};
 use semver::Version;
 use sg_std::Response;
 
@@ -15,7 +21,7 @@ use cosmwasm_std::entry_point;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 #[allow(clippy::cmp_owned)]
-pub fn migrate(deps: DepsMut, env: Env, _msg: Empty) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let prev_contract_version = cw2::get_contract_version(deps.storage)?;
 
     let valid_contract_names = [CONTRACT_NAME.to_string()];
@@ -32,7 +38,12 @@ pub fn migrate(deps: DepsMut, env: Env, _msg: Empty) -> Result<Response, Contrac
 
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    let mut pair = load_pair(&env.contract.address, deps.storage, &deps.querier)?;
+    apply_state_transforms(deps.branch(), &env, &msg.state_transforms)?;
+
+    let mut pair = load_pair(deps.storage)?;
+    pair.update_limit_window(&env.block);
+    pair.update_schedule(&env.block);
+    pair.update_reference_price(&deps.querier, &env.block);
 
     let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
 
@@ -52,7 +63,12 @@ pub fn migrate(deps: DepsMut, env: Env, _msg: Empty) -> Result<Response, Contrac
                 .add_attribute("from_name", prev_contract_version.contract)
                 .add_attribute("from_version", prev_contract_version.version)
                 .add_attribute("to_name", CONTRACT_NAME)
-                .add_attribute("to_version", CONTRACT_VERSION),
+                .add_attribute("to_version", CONTRACT_VERSION)
+                .add_attributes(
+                    msg.state_transforms
+                        .iter()
+                        .map(|state_transform| ("state_transform", state_transform.as_str())),
+                ),
         )
         .add_event(
             UpdatePairEvent {
@@ -64,9 +80,104 @@ pub fn migrate(deps: DepsMut, env: Env, _msg: Empty) -> Result<Response, Contrac
         .add_event(
             PairInternalEvent {
                 pair: &pair,
+                royalty_skipped: payout_context.royalty_skipped,
             }
             .into(),
         );
 
     Ok(response)
 }
+
+// NFT deposits as stored before listing prices were introduced
+const LEGACY_NFT_DEPOSITS: Map<TokenId, bool> = Map::new(TopKey::NftDeposits.as_str());
+
+/// QuoteSummary as stored before royalties were split between multiple recipients
+#[cw_serde]
+struct LegacyQuoteSummary {
+    fair_burn: TokenPayment,
+    royalty: Option<TokenPayment>,
+    swap: Option<TokenPayment>,
+    seller_amount: Uint128,
+}
+
+impl From<LegacyQuoteSummary> for QuoteSummary {
+    fn from(legacy: LegacyQuoteSummary) -> Self {
+        QuoteSummary {
+            fair_burn: legacy.fair_burn,
+            royalties: legacy.royalty.into_iter().collect(),
+            swap: legacy.swap,
+            seller_amount: legacy.seller_amount,
+        }
+    }
+}
+
+/// PairInternal as stored by the first version of the pair
+#[cw_serde]
+struct LegacyPairInternal {
+    total_nfts: u64,
+    sell_to_pair_quote_summary: Option<LegacyQuoteSummary>,
+    buy_from_pair_quote_summary: Option<LegacyQuoteSummary>,
+}
+
+const LEGACY_PAIR_INTERNAL: Item<LegacyPairInternal> = Item::new(TopKey::PairInternal.as_str());
+
+/// Applies the state transformations in order. Each transformation leaves state already
+/// stored in the current format unchanged, so that it can be applied more than once.
+pub fn apply_state_transforms(
+    deps: DepsMut,
+    env: &Env,
+    state_transforms: &[StateTransform],
+) -> StdResult<()> {
+    let storage = deps.storage;
+
+    for state_transform in state_transforms {
+        match state_transform {
+            StateTransform::ConvertNftDeposits => {
+                let token_ids = NFT_DEPOSITS
+                    .keys(storage, None, None, Order::Ascending)
+                    .collect::<StdResult<Vec<_>>>()?;
+                for token_id in token_ids {
+                    if LEGACY_NFT_DEPOSITS.load(storage, token_id.clone()).is_ok() {
+                        NFT_DEPOSITS.save(storage, token_id, &None)?;
+                    }
+                }
+            },
+            StateTransform::BackfillPairConfig => {
+                let pair_config = PAIR_CONFIG.load(storage)?;
+                PAIR_CONFIG.save(storage, &pair_config)?;
+            },
+            StateTransform::ConvertPairInternal => {
+                if PAIR_INTERNAL.load(storage).is_err() {
+                    let legacy = LEGACY_PAIR_INTERNAL.load(storage)?;
+
+                    // Legacy pairs hold their token reserves as the contract balance,
+                    // so the tracked reserves are backfilled from the balance.
+                    let denom = PAIR_IMMUTABLE.load(storage)?.denom;
+                    let total_tokens =
+                        deps.querier.query_balance(&env.contract.address, denom)?.amount;
+
+                    PAIR_INTERNAL.save(
+                        storage,
+                        &PairInternal {
+                            total_nfts: legacy.total_nfts,
+                            sell_to_pair_quote_summary: legacy
+                                .sell_to_pair_quote_summary
+                                .map(QuoteSummary::from),
+                            buy_from_pair_quote_summary: legacy
+                                .buy_from_pair_quote_summary
+                                .map(QuoteSummary::from),
+                            reference_price: None,
+                            total_tokens,
+                            limit_window: LimitWindow::default(),
+                            outside_schedule: false,
+                            sell_to_pair_halted: false,
+                            buy_from_pair_halted: false,
+                        },
+                    )?;
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
@@ -1,11 +1,16 @@
 #[cfg_attr(not(debug_assertions), allow(unused_imports))]
 use crate::{
+    helpers::SimNftPrices,
     pair::Pair,
-    state::{BondingCurve, PairConfig, PairImmutable, PairType, TokenId},
+    state::{
+        BondingCurve, NftPrice, NftSelection, PairConfig, PairImmutable, PairLimits, PairSchedule,
+        PairStats, PairTriggers, PairType, SweepPolicy, TokenId,
+    },
 };
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Coin, Uint128};
+use cw721::Cw721ReceiveMsg;
 use sg_index_query::QueryOptions;
 
 /// Defines whether the end user is buying or selling NFTs
@@ -61,6 +66,11 @@ pub enum ExecuteMsg {
         pair_type: Option<PairType>,
         bonding_curve: Option<BondingCurve>,
         asset_recipient: Option<String>,
+        nft_selection: Option<NftSelection>,
+        limits: Option<PairLimits>,
+        schedule: Option<PairSchedule>,
+        sweep_policy: Option<SweepPolicy>,
+        triggers: Option<PairTriggers>,
     },
     // Swap NFT for Tokens at the pair price
     SwapNftForTokens {
@@ -68,15 +78,104 @@ pub enum ExecuteMsg {
         min_output: Coin,
         asset_recipient: Option<String>,
     },
-    // Swap Tokens for a specific NFT at the pair price
+    // Swap Tokens for a specific NFT at the listing price of the NFT,
+    // or at the pair price when the NFT has no listing price
     SwapTokensForSpecificNft {
         token_id: String,
         asset_recipient: Option<String>,
     },
-    // Swap Tokens for any NFT at the pair price
+    // Swap Tokens for the cheapest NFT held by the pair,
+    // selected according to the NFT selection mode of the pair
     SwapTokensForAnyNft {
         asset_recipient: Option<String>,
     },
+    /// Handle an NFT sent to the pair with cw721 `SendNft`, the embedded message is a `ReceiveNftMsg`
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Set the listing price of NFTs held by the pair, a price of `None` removes the listing price
+    SetNftPrices {
+        token_ids: Vec<TokenId>,
+        nft_price: Option<NftPrice>,
+    },
+    /// Withdraw tokens held by the pair in excess of its tracked reserves,
+    /// for example tokens sent directly to the pair
+    SkimExcess {
+        asset_recipient: Option<String>,
+    },
+    /// Reconcile the NFT deposits with the NFTs owned by the pair, callable by anyone.
+    /// Deposits no longer owned by the pair are removed. NFTs owned by the pair but missing
+    /// from the deposits are only added when called by the pair owner.
+    Reconcile {},
+    /// Recompute the pair quotes and update the indices, callable by anyone.
+    RefreshQuotes {},
+    /// Move all tokens and NFTs held by the pair into another pair owned by the same owner,
+    /// and deactivate the pair. The deposits are performed on the target pair. NFTs of another
+    /// collection than the target's, and tokens of another denom than the target's, are sent
+    /// to the asset recipient of the pair instead.
+    MigrateLiquidityTo {
+        new_pair: String,
+    },
+}
+
+/// The message embedded in a cw721 `SendNft` to the pair
+#[cw_serde]
+pub enum ReceiveNftMsg {
+    /// Deposit the NFT into the pair, only the pair owner can deposit NFTs.
+    /// An empty message is treated as a deposit.
+    DepositNft {},
+    /// Swap the NFT for tokens at the pair price
+    SwapNftForTokens {
+        min_output: Coin,
+        asset_recipient: Option<String>,
+    },
+}
+
+/// The data set on the response of a swap with the pair
+#[cw_serde]
+pub struct SwapData {
+    /// The address of the pair
+    pub pair: Addr,
+    pub transaction_type: TransactionType,
+    pub token_id: TokenId,
+    /// The price of the NFT paid by the buyer, fees included
+    pub price: Coin,
+    pub fair_burn_fee: Uint128,
+    pub royalty_fee: Uint128,
+    pub swap_fee: Uint128,
+    /// The amount paid out to the seller of the NFT
+    pub seller_amount: Uint128,
+}
+
+/// A transformation of the pair state applied during a migration, converting state stored
+/// by previous versions of the pair
+#[cw_serde]
+pub enum StateTransform {
+    /// Converts NFT deposits stored as `true`, before listing prices were introduced,
+    /// to deposits without a listing price
+    ConvertNftDeposits,
+    /// Re-saves the pair config, back-filling the fields added since the pair was created
+    /// with their defaults
+    BackfillPairConfig,
+    /// Converts the pair internal state stored with a single royalty payment per quote,
+    /// back-filling the fields added since the pair was created with their defaults
+    ConvertPairInternal,
+}
+
+impl StateTransform {
+    pub fn as_str(&self) -> &str {
+        match self {
+            StateTransform::ConvertNftDeposits => "convert_nft_deposits",
+            StateTransform::BackfillPairConfig => "backfill_pair_config",
+            StateTransform::ConvertPairInternal => "convert_pair_internal",
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrateMsg {
+    /// The state transformations to apply, in order
+    #[serde(default)]
+    pub state_transforms: Vec<StateTransform>,
 }
 
 #[cw_serde]
@@ -88,6 +187,17 @@ pub enum QueryMsg {
     NftDeposits {
         query_options: Option<QueryOptions<String>>,
     },
+    #[returns(Option<NftPrice>)]
+    NftPrice {
+        token_id: TokenId,
+    },
+    #[returns(NftReconciliationResponse)]
+    NftReconciliation {},
+    #[returns(PairStats)]
+    PairStats {},
+    /// The listing prices of the NFTs held by the pair, used to simulate consecutive swaps
+    #[returns(SimNftPrices)]
+    SimNftPrices {},
     #[returns(QuotesResponse)]
     SimSellToPairSwaps {
         limit: u32,
@@ -104,6 +214,28 @@ pub struct NftDepositsResponse {
     pub token_ids: Vec<TokenId>,
 }
 
+#[cw_serde]
+pub struct NftReconciliationResponse {
+    /// The number of NFTs the pair reports holding in its internal state
+    pub total_nfts: u64,
+    /// The number of NFTs recorded as deposits
+    pub num_deposits: u64,
+    /// The number of NFTs owned by the pair in the collection
+    pub num_owned: u64,
+    /// NFTs owned by the pair that are not recorded as deposits
+    pub missing_deposits: Vec<TokenId>,
+    /// NFTs recorded as deposits that are not owned by the pair
+    pub stale_deposits: Vec<TokenId>,
+}
+
+impl NftReconciliationResponse {
+    pub fn is_reconciled(&self) -> bool {
+        self.missing_deposits.is_empty()
+            && self.stale_deposits.is_empty()
+            && self.total_nfts == self.num_deposits
+    }
+}
+
 #[cw_serde]
 pub struct QuotesResponse {
     pub denom: String,
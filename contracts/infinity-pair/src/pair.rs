@@ -1,15 +1,21 @@
 use crate::error::ContractError;
-use crate::helpers::PayoutContext;
+use crate::helpers::{load_cheapest_nft_prices, CheapestNftPrices, PayoutContext, SimNftPrices};
 use crate::math;
 use crate::msg::TransactionType;
 use crate::state::{
-    BondingCurve, PairConfig, PairImmutable, PairInternal, PairType, QuoteSummary, PAIR_CONFIG,
-    PAIR_IMMUTABLE, PAIR_INTERNAL,
+    BondingCurve, LimitWindow, NftPrice, NftSelection, PairConfig, PairImmutable, PairInternal,
+    PairStats, PairTrigger, PairType, QuoteSummary, SweepPolicy, PAIR_CONFIG, PAIR_IMMUTABLE,
+    PAIR_INTERNAL,
 };
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{attr, coin, to_binary, Addr, Attribute, Decimal, Storage, Uint128, WasmMsg};
-use infinity_index::msg::ExecuteMsg as InfinityIndexExecuteMsg;
+use cosmwasm_std::{
+    attr, coin, to_binary, Addr, Attribute, BlockInfo, Decimal, QuerierWrapper, Storage, Uint128,
+    WasmMsg,
+};
+use infinity_index::msg::{
+    ExecuteMsg as InfinityIndexExecuteMsg, QueryMsg as InfinityIndexQueryMsg, TwapResponse,
+};
 use sg_marketplace_common::address::address_or;
 use sg_marketplace_common::coin::transfer_coins;
 use sg_std::Response;
@@ -18,11 +24,15 @@ use stargaze_fair_burn::append_fair_burn_msg;
 impl QuoteSummary {
     pub fn total(&self) -> Uint128 {
         self.fair_burn.amount
-            + self.royalty.as_ref().map_or(Uint128::zero(), |p| p.amount)
+            + self.royalty_amount()
             + self.swap.as_ref().map_or(Uint128::zero(), |p| p.amount)
             + self.seller_amount
     }
 
+    pub fn royalty_amount(&self) -> Uint128 {
+        self.royalties.iter().map(|p| p.amount).sum()
+    }
+
     pub fn payout(
         &self,
         denom: &String,
@@ -36,7 +46,7 @@ impl QuoteSummary {
             response,
         );
 
-        if let Some(royalty) = &self.royalty {
+        for royalty in &self.royalties {
             response = transfer_coins(
                 vec![coin(royalty.amount.u128(), denom)],
                 &royalty.recipient,
@@ -59,12 +69,61 @@ impl QuoteSummary {
     }
 }
 
+impl PairStats {
+    /// Records an NFT sold to the pair, NFTs held by the pair add their price to the cost basis
+    pub fn record_sell_to_pair(&mut self, quote_summary: &QuoteSummary, nft_held: bool) {
+        let quote_total = quote_summary.total();
+
+        self.sell_to_pair_count += 1u64;
+        self.sell_to_pair_volume += quote_total;
+        self.record_fees(quote_summary);
+
+        if nft_held {
+            self.nft_cost_basis += quote_total;
+        }
+    }
+
+    /// Records an NFT bought from the pair, realizing the difference between the sale amount
+    /// and the average cost basis of the `total_nfts` held by the pair before the sale
+    pub fn record_buy_from_pair(&mut self, quote_summary: &QuoteSummary, total_nfts: u64) {
+        self.buy_from_pair_count += 1u64;
+        self.buy_from_pair_volume += quote_summary.total();
+        self.record_fees(quote_summary);
+
+        let nft_cost = self.remove_cost_basis(1u64, total_nfts);
+        if quote_summary.seller_amount >= nft_cost {
+            self.realized_gains += quote_summary.seller_amount - nft_cost;
+        } else {
+            self.realized_losses += nft_cost - quote_summary.seller_amount;
+        }
+    }
+
+    /// Removes the average cost basis of `num_nfts` out of the `total_nfts` held by the pair,
+    /// returning the removed cost basis
+    pub fn remove_cost_basis(&mut self, num_nfts: u64, total_nfts: u64) -> Uint128 {
+        if total_nfts == 0u64 {
+            return Uint128::zero();
+        }
+
+        let cost = self.nft_cost_basis.multiply_ratio(num_nfts.min(total_nfts), total_nfts);
+        self.nft_cost_basis -= cost;
+        cost
+    }
+
+    fn record_fees(&mut self, quote_summary: &QuoteSummary) {
+        self.fair_burn_paid += quote_summary.fair_burn.amount;
+        self.royalties_paid += quote_summary.royalty_amount();
+        if let Some(swap) = &quote_summary.swap {
+            self.swap_fees_earned += swap.amount;
+        }
+    }
+}
+
 #[cw_serde]
 pub struct Pair {
     pub immutable: PairImmutable<Addr>,
     pub config: PairConfig<Addr>,
     pub internal: PairInternal,
-    pub total_tokens: Uint128,
 }
 
 impl Pair {
@@ -82,8 +141,13 @@ impl Pair {
                 total_nfts: 0u64,
                 buy_from_pair_quote_summary: None,
                 sell_to_pair_quote_summary: None,
+                reference_price: None,
+                total_tokens: Uint128::zero(),
+                limit_window: LimitWindow::default(),
+                outside_schedule: false,
+                sell_to_pair_halted: false,
+                buy_from_pair_halted: false,
             },
-            Uint128::zero(),
         ))
     }
 
@@ -91,13 +155,11 @@ impl Pair {
         immutable: PairImmutable<Addr>,
         config: PairConfig<Addr>,
         internal: PairInternal,
-        total_tokens: Uint128,
     ) -> Self {
         Self {
             immutable,
             config,
             internal,
-            total_tokens,
         }
     }
 
@@ -108,7 +170,10 @@ impl Pair {
         mut response: Response,
     ) -> Result<Response, ContractError> {
         self.update_sell_to_pair_quote_summary(payout_context);
-        self.update_buy_from_pair_quote_summary(payout_context);
+        self.update_nft_buy_from_pair_quote_summary(
+            payout_context,
+            &load_cheapest_nft_prices(storage)?,
+        );
 
         PAIR_CONFIG.save(storage, &self.config)?;
         PAIR_INTERNAL.save(storage, &self.internal)?;
@@ -118,6 +183,11 @@ impl Pair {
         Ok(response)
     }
 
+    /// Whether the pair accepts trades, as of the last schedule update
+    pub fn is_trading(&self) -> bool {
+        self.config.is_active && !self.internal.outside_schedule
+    }
+
     pub fn asset_recipient(&self) -> Addr {
         address_or(self.config.asset_recipient.as_ref(), &self.immutable.owner)
     }
@@ -152,37 +222,124 @@ impl Pair {
         }
     }
 
-    pub fn swap_nft_for_tokens(&mut self) {
-        self.total_tokens -= self.internal.sell_to_pair_quote_summary.as_ref().unwrap().total();
+    pub fn swap_nft_for_tokens(&mut self, pair_stats: &PairStats) -> Vec<PairTrigger> {
+        let quote_total = self.internal.sell_to_pair_quote_summary.as_ref().unwrap().total();
+        self.internal.total_tokens -= quote_total;
+        self.internal.limit_window.nfts_bought += 1u64;
+        self.internal.limit_window.tokens_spent += quote_total;
 
         if self.reinvest_nfts() {
             self.internal.total_nfts += 1u64;
         };
 
         self.update_spot_price(TransactionType::UserSubmitsNfts);
+        self.apply_triggers(pair_stats)
     }
 
-    pub fn sim_swap_nft_for_tokens(&mut self, payout_context: &PayoutContext) {
-        self.swap_nft_for_tokens();
+    pub fn sim_swap_nft_for_tokens(
+        &mut self,
+        payout_context: &PayoutContext,
+        pair_stats: &mut PairStats,
+        sim_nft_prices: &mut SimNftPrices,
+    ) {
+        let quote_summary = self.internal.sell_to_pair_quote_summary.as_ref().unwrap();
+        pair_stats.record_sell_to_pair(quote_summary, self.reinvest_nfts());
+        if self.reinvest_nfts() {
+            sim_nft_prices.add_unlisted();
+        }
+        self.swap_nft_for_tokens(pair_stats);
         self.update_sell_to_pair_quote_summary(payout_context);
-        self.update_buy_from_pair_quote_summary(payout_context);
+        self.update_nft_buy_from_pair_quote_summary(payout_context, &sim_nft_prices.cheapest());
     }
 
-    pub fn swap_tokens_for_nft(&mut self) {
+    pub fn swap_tokens_for_nft(
+        &mut self,
+        seller_amount: Uint128,
+        pair_stats: &PairStats,
+    ) -> Vec<PairTrigger> {
         self.internal.total_nfts -= 1u64;
 
         if self.reinvest_tokens() {
-            self.total_tokens +=
-                self.internal.buy_from_pair_quote_summary.as_ref().unwrap().seller_amount;
+            self.internal.total_tokens += seller_amount;
         };
 
         self.update_spot_price(TransactionType::UserSubmitsTokens);
+        self.apply_triggers(pair_stats)
     }
 
-    pub fn sim_swap_tokens_for_nft(&mut self, payout_context: &PayoutContext) {
-        self.swap_tokens_for_nft();
+    /// Simulates buying the cheapest NFT held by the pair, removing it from the simulated
+    /// inventory so that the next swap is quoted with the cheapest remaining NFT
+    pub fn sim_swap_tokens_for_nft(
+        &mut self,
+        payout_context: &PayoutContext,
+        pair_stats: &mut PairStats,
+        sim_nft_prices: &mut SimNftPrices,
+    ) {
+        let (nft_price, quote_summary) = self
+            .cheapest_nft_buy_from_pair_quote(payout_context, &sim_nft_prices.cheapest())
+            .unwrap();
+        sim_nft_prices.remove(&nft_price);
+
+        let seller_amount = quote_summary.seller_amount;
+        pair_stats.record_buy_from_pair(&quote_summary, self.internal.total_nfts);
+        self.swap_tokens_for_nft(seller_amount, pair_stats);
+        self.sweep_tokens();
         self.update_sell_to_pair_quote_summary(payout_context);
-        self.update_buy_from_pair_quote_summary(payout_context);
+        self.update_nft_buy_from_pair_quote_summary(payout_context, &sim_nft_prices.cheapest());
+    }
+
+    /// Removes the tokens held above the sweep threshold from the pair reserves,
+    /// returning the amount to be sent to the asset recipient
+    pub fn sweep_tokens(&mut self) -> Option<Uint128> {
+        match self.config.sweep_policy {
+            SweepPolicy::Threshold {
+                max_total_tokens,
+            } if self.internal.total_tokens > max_total_tokens => {
+                let sweep_amount = self.internal.total_tokens - max_total_tokens;
+                self.internal.total_tokens = max_total_tokens;
+                Some(sweep_amount)
+            },
+            _ => None,
+        }
+    }
+
+    /// Stops trading on the sides of the pair whose triggers have fired since the last swap,
+    /// returning the triggers that fired
+    fn apply_triggers(&mut self, pair_stats: &PairStats) -> Vec<PairTrigger> {
+        let mut fired_triggers = vec![];
+
+        if let Some(min_spot_price) = self.config.triggers.min_spot_price {
+            let below_min_spot_price = match self.sell_to_pair_sale_amount() {
+                Some(spot_price) => spot_price < min_spot_price,
+                None => false,
+            };
+            if !self.internal.sell_to_pair_halted && below_min_spot_price {
+                self.internal.sell_to_pair_halted = true;
+                fired_triggers.push(PairTrigger::MinSpotPrice);
+            }
+        }
+
+        if let Some(min_inventory) = self.config.triggers.min_inventory {
+            if !self.internal.buy_from_pair_halted && self.internal.total_nfts < min_inventory {
+                self.internal.buy_from_pair_halted = true;
+                fired_triggers.push(PairTrigger::MinInventory);
+            }
+        }
+
+        if let Some(max_realized_losses) = self.config.triggers.max_realized_losses {
+            if self.config.is_active && pair_stats.realized_losses > max_realized_losses {
+                self.config.is_active = false;
+                fired_triggers.push(PairTrigger::MaxRealizedLosses);
+            }
+        }
+
+        fired_triggers
+    }
+
+    /// Clears the sides of the pair stopped by triggers
+    pub fn clear_triggers(&mut self) {
+        self.internal.sell_to_pair_halted = false;
+        self.internal.buy_from_pair_halted = false;
     }
 
     fn update_spot_price(&mut self, tx_type: TransactionType) {
@@ -235,17 +392,115 @@ impl Pair {
                     },
                 }
             },
-            BondingCurve::ConstantProduct => {},
+            BondingCurve::ConstantProduct
+            | BondingCurve::OracleAnchored {
+                ..
+            } => {},
+        };
+    }
+
+    /// Records whether the block time is outside of the pair schedule
+    pub fn update_schedule(&mut self, block: &BlockInfo) {
+        self.internal.outside_schedule = !self.config.schedule.contains(block);
+    }
+
+    /// Starts a new limit window, resetting the purchase counters, once the current window
+    /// has elapsed
+    pub fn update_limit_window(&mut self, block: &BlockInfo) {
+        let window_seconds = self.config.limits.window_seconds;
+        if window_seconds == 0 {
+            return;
+        }
+
+        let now = block.time.seconds();
+        let window_start = now - now % window_seconds;
+        if window_start != self.internal.limit_window.start {
+            self.internal.limit_window = LimitWindow {
+                start: window_start,
+                nfts_bought: 0u64,
+                tokens_spent: Uint128::zero(),
+            };
+        }
+    }
+
+    /// Whether the pair can buy another NFT for `quote_total` tokens without exceeding its limits
+    pub fn within_limits(&self, quote_total: Uint128) -> bool {
+        let limits = &self.config.limits;
+        let limit_window = &self.internal.limit_window;
+
+        if let Some(max_inventory) = limits.max_inventory {
+            if self.internal.total_nfts >= max_inventory {
+                return false;
+            }
+        }
+
+        if let Some(max_nfts_bought) = limits.max_nfts_bought_per_window {
+            if limit_window.nfts_bought >= max_nfts_bought {
+                return false;
+            }
+        }
+
+        if let Some(max_tokens_spent) = limits.max_tokens_spent_per_window {
+            if limit_window.tokens_spent + quote_total > max_tokens_spent {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Reads the reference price from the oracle for oracle anchored curves.
+    /// The reference price is cleared when the oracle has no trades, when the last trade is
+    /// older than the max staleness, or when the oracle cannot be queried.
+    pub fn update_reference_price(&mut self, querier: &QuerierWrapper, block: &BlockInfo) {
+        self.internal.reference_price = match &self.config.bonding_curve {
+            BondingCurve::OracleAnchored {
+                oracle,
+                twap_window_seconds,
+                max_staleness_seconds,
+                ..
+            } => querier
+                .query_wasm_smart::<Option<TwapResponse>>(
+                    oracle,
+                    &InfinityIndexQueryMsg::Twap {
+                        collection: self.immutable.collection.to_string(),
+                        denom: self.immutable.denom.clone(),
+                        window_seconds: *twap_window_seconds,
+                    },
+                )
+                .ok()
+                .flatten()
+                .filter(|twap_response| {
+                    twap_response.twap.denom == self.immutable.denom
+                        && block.time.seconds().saturating_sub(twap_response.last_trade_timestamp)
+                            <= *max_staleness_seconds
+                })
+                .map(|twap_response| twap_response.twap.amount),
+            _ => None,
         };
     }
 
     pub fn update_sell_to_pair_quote_summary(&mut self, payout_context: &PayoutContext) {
-        if !self.config.is_active || self.config.pair_type == PairType::Nft {
+        if !self.is_trading()
+            || self.internal.sell_to_pair_halted
+            || self.config.pair_type == PairType::Nft
+        {
             self.internal.sell_to_pair_quote_summary = None;
             return;
         }
 
-        let sale_amount_option = match self.config.bonding_curve {
+        self.internal.sell_to_pair_quote_summary = match self.sell_to_pair_sale_amount() {
+            Some(sale_amount) if sale_amount <= self.internal.total_tokens => payout_context
+                .build_sell_to_pair_quote_summary(self, sale_amount)
+                .filter(|quote_summary| self.within_limits(quote_summary.total())),
+            _ => None,
+        };
+    }
+
+    /// The sale amount of the next "sell to" trade derived from the bonding curve,
+    /// before any fees are applied
+    fn sell_to_pair_sale_amount(&self) -> Option<Uint128> {
+        match self.config.bonding_curve {
             BondingCurve::Linear {
                 spot_price,
                 ..
@@ -254,30 +509,103 @@ impl Pair {
                 spot_price,
                 ..
             } => Some(spot_price),
-            BondingCurve::ConstantProduct => {
-                math::calc_cp_trade_sell_to_pair_price(self.total_tokens, self.internal.total_nfts)
-                    .ok()
-            },
-        };
+            BondingCurve::ConstantProduct => math::calc_cp_trade_sell_to_pair_price(
+                self.internal.total_tokens,
+                self.internal.total_nfts,
+            )
+            .ok(),
+            BondingCurve::OracleAnchored {
+                sell_to_pair_spread,
+                ..
+            } => self.internal.reference_price.map(|reference_price| {
+                reference_price.mul_floor(Decimal::one() - sell_to_pair_spread)
+            }),
+        }
+    }
+
+    pub fn update_buy_from_pair_quote_summary(&mut self, payout_context: &PayoutContext) {
+        self.internal.buy_from_pair_quote_summary =
+            self.buy_from_pair_sale_amount().and_then(|sale_amount| {
+                payout_context.build_buy_from_pair_quote_summary(self, sale_amount)
+            });
+    }
 
-        self.internal.sell_to_pair_quote_summary = match sale_amount_option {
-            Some(sale_amount) if sale_amount <= self.total_tokens => {
-                payout_context.build_sell_to_pair_quote_summary(self, sale_amount)
+    /// Sets the buy from pair quote to the quote of the cheapest NFT held by the pair,
+    /// applying the listing prices of the NFTs. This is the quote stored in the index
+    /// and charged when swapping for any NFT.
+    pub fn update_nft_buy_from_pair_quote_summary(
+        &mut self,
+        payout_context: &PayoutContext,
+        cheapest_nft_prices: &CheapestNftPrices,
+    ) {
+        self.internal.buy_from_pair_quote_summary = self
+            .cheapest_nft_buy_from_pair_quote(payout_context, cheapest_nft_prices)
+            .map(|(_, quote_summary)| quote_summary);
+    }
+
+    /// Returns the listing price and the quote of the cheapest NFT held by the pair
+    fn cheapest_nft_buy_from_pair_quote(
+        &self,
+        payout_context: &PayoutContext,
+        cheapest_nft_prices: &CheapestNftPrices,
+    ) -> Option<(NftPrice, QuoteSummary)> {
+        let premium_quote = cheapest_nft_prices.premium.and_then(|premium| {
+            let nft_price = NftPrice::Premium(premium);
+            self.build_nft_buy_from_pair_quote_summary(payout_context, &Some(nft_price.clone()))
+                .map(|quote_summary| (nft_price, quote_summary))
+        });
+        let fixed_quote = cheapest_nft_prices.fixed.and_then(|price| {
+            let nft_price = NftPrice::Fixed(price);
+            self.build_nft_buy_from_pair_quote_summary(payout_context, &Some(nft_price.clone()))
+                .map(|quote_summary| (nft_price, quote_summary))
+        });
+
+        premium_quote
+            .into_iter()
+            .chain(fixed_quote)
+            .min_by_key(|(_, quote_summary)| quote_summary.total())
+    }
+
+    /// Builds the quote for buying a specific NFT from the pair, applying the listing price
+    /// of the NFT. NFTs without a listing price are quoted at the pair price.
+    pub fn build_nft_buy_from_pair_quote_summary(
+        &self,
+        payout_context: &PayoutContext,
+        nft_price: &Option<NftPrice>,
+    ) -> Option<QuoteSummary> {
+        match nft_price {
+            None => self.buy_from_pair_sale_amount().and_then(|sale_amount| {
+                payout_context.build_buy_from_pair_quote_summary(self, sale_amount)
+            }),
+            Some(NftPrice::Premium(premium)) => {
+                self.buy_from_pair_sale_amount().and_then(|sale_amount| {
+                    payout_context.build_buy_from_pair_quote_summary(self, sale_amount + premium)
+                })
             },
-            _ => None,
-        };
+            Some(NftPrice::Fixed(price)) => {
+                if !self.is_trading()
+                    || self.internal.buy_from_pair_halted
+                    || self.config.pair_type == PairType::Token
+                {
+                    return None;
+                }
+                payout_context.build_buy_from_pair_quote_summary(self, *price)
+            },
+        }
     }
 
-    pub fn update_buy_from_pair_quote_summary(&mut self, payout_context: &PayoutContext) {
-        if !self.config.is_active
+    /// The sale amount of the next "buy from" trade derived from the bonding curve,
+    /// before any fees are applied
+    fn buy_from_pair_sale_amount(&self) -> Option<Uint128> {
+        if !self.is_trading()
+            || self.internal.buy_from_pair_halted
             || self.internal.total_nfts == 0u64
             || self.config.pair_type == PairType::Token
         {
-            self.internal.buy_from_pair_quote_summary = None;
-            return;
+            return None;
         }
 
-        let sale_amount_option = match (&self.config.pair_type, &self.config.bonding_curve) {
+        match (&self.config.pair_type, &self.config.bonding_curve) {
             (
                 PairType::Nft,
                 BondingCurve::Linear {
@@ -312,19 +640,25 @@ impl Pair {
                     ..
                 },
                 BondingCurve::ConstantProduct,
-            ) => {
-                math::calc_cp_trade_buy_from_pair_price(self.total_tokens, self.internal.total_nfts)
-                    .ok()
-            },
-            _ => None,
-        };
-
-        self.internal.buy_from_pair_quote_summary = match sale_amount_option {
-            Some(sale_amount) => {
-                payout_context.build_buy_from_pair_quote_summary(self, sale_amount)
-            },
+            ) => math::calc_cp_trade_buy_from_pair_price(
+                self.internal.total_tokens,
+                self.internal.total_nfts,
+            )
+            .ok(),
+            (
+                PairType::Nft
+                | PairType::Trade {
+                    ..
+                },
+                BondingCurve::OracleAnchored {
+                    buy_from_pair_spread,
+                    ..
+                },
+            ) => self.internal.reference_price.map(|reference_price| {
+                reference_price.mul_ceil(Decimal::one() + *buy_from_pair_spread)
+            }),
             _ => None,
-        };
+        }
     }
 
     fn update_index(&self, infinity_index: &Addr, response: Response) -> Response {
@@ -347,6 +681,35 @@ impl Pair {
         })
     }
 
+    /// Records the trade price with the oracle. Trades where the counterparty is the pair owner
+    /// or asset recipient are not recorded, nor are trades of oracle anchored pairs, so that a
+    /// pair cannot move the oracle price it is quoting against.
+    pub fn record_trade(
+        &self,
+        infinity_index: &Addr,
+        counterparty: &Addr,
+        price: Uint128,
+        response: Response,
+    ) -> Response {
+        if counterparty == self.immutable.owner
+            || *counterparty == self.asset_recipient()
+            || matches!(self.config.bonding_curve, BondingCurve::OracleAnchored { .. })
+        {
+            return response;
+        }
+
+        response.add_message(WasmMsg::Execute {
+            contract_addr: infinity_index.to_string(),
+            msg: to_binary(&InfinityIndexExecuteMsg::RecordTrade {
+                collection: self.immutable.collection.to_string(),
+                denom: self.immutable.denom.clone(),
+                price,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    }
+
     pub fn get_event_attrs(&self, attr_keys: Vec<&str>) -> Vec<Attribute> {
         let mut attributes = vec![];
 
@@ -393,6 +756,9 @@ impl Pair {
                     BondingCurve::ConstantProduct {} => {
                         Some(attr("bonding_curve", "constant_product".to_string()))
                     },
+                    BondingCurve::OracleAnchored {
+                        ..
+                    } => Some(attr("bonding_curve", "oracle_anchored".to_string())),
                 },
                 "spot_price" => match self.config.bonding_curve {
                     BondingCurve::Linear {
@@ -405,6 +771,9 @@ impl Pair {
                     } => Some(attr("spot_price", spot_price.to_string())),
                     BondingCurve::ConstantProduct {
                         ..
+                    }
+                    | BondingCurve::OracleAnchored {
+                        ..
                     } => None,
                 },
                 "delta" => match self.config.bonding_curve {
@@ -418,15 +787,41 @@ impl Pair {
                     } => Some(attr("delta", delta.to_string())),
                     BondingCurve::ConstantProduct {
                         ..
+                    }
+                    | BondingCurve::OracleAnchored {
+                        ..
                     } => None,
                 },
                 "is_active" => Some(attr("is_active", self.config.is_active.to_string())),
+                "sell_to_pair_halted" => {
+                    Some(attr("sell_to_pair_halted", self.internal.sell_to_pair_halted.to_string()))
+                },
+                "buy_from_pair_halted" => Some(attr(
+                    "buy_from_pair_halted",
+                    self.internal.buy_from_pair_halted.to_string(),
+                )),
+                "active_from" => self
+                    .config
+                    .schedule
+                    .active_from
+                    .map(|active_from| attr("active_from", active_from.to_string())),
+                "expires_at" => self
+                    .config
+                    .schedule
+                    .expires_at
+                    .map(|expires_at| attr("expires_at", expires_at.to_string())),
+                "nft_selection" => match self.config.nft_selection {
+                    NftSelection::Ordered => Some(attr("nft_selection", "ordered".to_string())),
+                    NftSelection::Random => Some(attr("nft_selection", "random".to_string())),
+                },
                 "asset_recipient" => self
                     .config
                     .asset_recipient
                     .as_ref()
                     .map(|asset_recipient| attr("asset_recipient", asset_recipient.to_string())),
-                "total_tokens" => Some(attr("total_tokens", self.total_tokens.to_string())),
+                "total_tokens" => {
+                    Some(attr("total_tokens", self.internal.total_tokens.to_string()))
+                },
                 "total_nfts" => Some(attr("total_nfts", self.internal.total_nfts.to_string())),
                 "sell_to_pair_quote" => {
                     self.internal.sell_to_pair_quote_summary.as_ref().map(|quote_summary| {
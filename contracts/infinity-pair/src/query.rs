@@ -1,8 +1,12 @@
 use crate::{
-    helpers::{load_pair, load_payout_context},
-    msg::{NftDepositsResponse, QueryMsg, QuotesResponse},
+    helpers::{
+        load_nft_reconciliation, load_pair, load_payout_context, load_sim_nft_prices, SimNftPrices,
+    },
+    msg::{NftDepositsResponse, NftReconciliationResponse, QueryMsg, QuotesResponse},
     pair::Pair,
-    state::{INFINITY_GLOBAL, NFT_DEPOSITS, PAIR_IMMUTABLE},
+    state::{
+        NftPrice, PairStats, TokenId, INFINITY_GLOBAL, NFT_DEPOSITS, PAIR_IMMUTABLE, PAIR_STATS,
+    },
 };
 
 use cosmwasm_std::{to_binary, Binary, Deps, Env, StdError, StdResult, Uint128};
@@ -18,6 +22,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::NftDeposits {
             query_options,
         } => to_binary(&query_nft_deposits(deps, query_options.unwrap_or_default())?),
+        QueryMsg::NftPrice {
+            token_id,
+        } => to_binary(&query_nft_price(deps, token_id)?),
+        QueryMsg::NftReconciliation {} => to_binary(&query_nft_reconciliation(deps, env)?),
+        QueryMsg::PairStats {} => to_binary(&query_pair_stats(deps)?),
+        QueryMsg::SimNftPrices {} => to_binary(&query_sim_nft_prices(deps)?),
         QueryMsg::SimSellToPairSwaps {
             limit,
         } => to_binary(&query_sim_sell_to_pair_swaps(deps, env, limit)?),
@@ -27,8 +37,8 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     }
 }
 
-pub fn query_pair(deps: Deps, env: Env) -> StdResult<Pair> {
-    let pair = load_pair(&env.contract.address, deps.storage, &deps.querier)
+pub fn query_pair(deps: Deps, _env: Env) -> StdResult<Pair> {
+    let pair = load_pair(deps.storage)
         .map_err(|_| StdError::generic_err("failed to load pair".to_string()))?;
 
     Ok(pair)
@@ -59,8 +69,27 @@ pub fn query_nft_deposits(
     })
 }
 
+pub fn query_nft_price(deps: Deps, token_id: TokenId) -> StdResult<Option<NftPrice>> {
+    Ok(NFT_DEPOSITS.may_load(deps.storage, token_id)?.flatten())
+}
+
+pub fn query_pair_stats(deps: Deps) -> StdResult<PairStats> {
+    Ok(PAIR_STATS.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn query_sim_nft_prices(deps: Deps) -> StdResult<SimNftPrices> {
+    load_sim_nft_prices(deps.storage)
+}
+
+pub fn query_nft_reconciliation(deps: Deps, env: Env) -> StdResult<NftReconciliationResponse> {
+    let pair = load_pair(deps.storage)
+        .map_err(|_| StdError::generic_err("failed to load pair".to_string()))?;
+
+    load_nft_reconciliation(deps, &env.contract.address, &pair)
+}
+
 pub fn query_sim_sell_to_pair_swaps(deps: Deps, env: Env, limit: u32) -> StdResult<QuotesResponse> {
-    let mut pair = load_pair(&env.contract.address, deps.storage, &deps.querier)
+    let mut pair = load_pair(deps.storage)
         .map_err(|_| StdError::generic_err("failed to load pair".to_string()))?;
 
     let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
@@ -72,8 +101,15 @@ pub fn query_sim_sell_to_pair_swaps(deps: Deps, env: Env, limit: u32) -> StdResu
     )
     .map_err(|_| StdError::generic_err("failed to load payout context".to_string()))?;
 
+    pair.update_limit_window(&env.block);
+    pair.update_schedule(&env.block);
+    pair.update_reference_price(&deps.querier, &env.block);
+    let mut sim_nft_prices = load_sim_nft_prices(deps.storage)?;
+
     pair.update_sell_to_pair_quote_summary(&payout_context);
-    pair.update_buy_from_pair_quote_summary(&payout_context);
+    pair.update_nft_buy_from_pair_quote_summary(&payout_context, &sim_nft_prices.cheapest());
+
+    let mut pair_stats = PAIR_STATS.may_load(deps.storage)?.unwrap_or_default();
 
     let mut sell_to_pair_quotes: Vec<Uint128> = vec![];
     let mut buy_from_pair_quotes: Vec<Uint128> = vec![];
@@ -91,7 +127,7 @@ pub fn query_sim_sell_to_pair_swaps(deps: Deps, env: Env, limit: u32) -> StdResu
             break;
         }
 
-        pair.sim_swap_nft_for_tokens(&payout_context);
+        pair.sim_swap_nft_for_tokens(&payout_context, &mut pair_stats, &mut sim_nft_prices);
 
         idx += 1;
     }
@@ -108,7 +144,7 @@ pub fn query_sim_buy_from_pair_swaps(
     env: Env,
     limit: u32,
 ) -> StdResult<QuotesResponse> {
-    let mut pair = load_pair(&env.contract.address, deps.storage, &deps.querier)
+    let mut pair = load_pair(deps.storage)
         .map_err(|_| StdError::generic_err("failed to load pair".to_string()))?;
 
     let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
@@ -120,8 +156,15 @@ pub fn query_sim_buy_from_pair_swaps(
     )
     .map_err(|_| StdError::generic_err("failed to load payout context".to_string()))?;
 
+    pair.update_limit_window(&env.block);
+    pair.update_schedule(&env.block);
+    pair.update_reference_price(&deps.querier, &env.block);
+    let mut sim_nft_prices = load_sim_nft_prices(deps.storage)?;
+
     pair.update_sell_to_pair_quote_summary(&payout_context);
-    pair.update_buy_from_pair_quote_summary(&payout_context);
+    pair.update_nft_buy_from_pair_quote_summary(&payout_context, &sim_nft_prices.cheapest());
+
+    let mut pair_stats = PAIR_STATS.may_load(deps.storage)?.unwrap_or_default();
 
     let mut sell_to_pair_quotes: Vec<Uint128> = vec![];
     let mut buy_from_pair_quotes: Vec<Uint128> = vec![];
@@ -138,7 +181,7 @@ pub fn query_sim_buy_from_pair_swaps(
             break;
         }
 
-        pair.sim_swap_tokens_for_nft(&payout_context);
+        pair.sim_swap_tokens_for_nft(&payout_context, &mut pair_stats, &mut sim_nft_prices);
 
         idx += 1;
     }
@@ -1,10 +1,11 @@
 use crate::{constants::TopKey, ContractError};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Api, Decimal, Uint128};
+use cosmwasm_std::{ensure, Addr, Api, BlockInfo, Decimal, Timestamp, Uint128};
 use cw_address_like::AddressLike;
 use cw_storage_plus::{Item, Map};
 use cw_utils::maybe_addr;
+use infinity_shared::InfinityError;
 
 pub type Denom = String;
 pub type TokenId = String;
@@ -12,8 +13,17 @@ pub type TokenId = String;
 // The address of the infinity global contract
 pub const INFINITY_GLOBAL: Item<Addr> = Item::new(TopKey::InfinityGlobal.as_str());
 
-// A map of all NFT token ids held by the pair
-pub const NFT_DEPOSITS: Map<TokenId, bool> = Map::new(TopKey::NftDeposits.as_str());
+// A map of all NFT token ids held by the pair, to their optional listing price
+pub const NFT_DEPOSITS: Map<TokenId, Option<NftPrice>> = Map::new(TopKey::NftDeposits.as_str());
+
+/// NftPrice refers to the listing price of an individual NFT held by the pair
+/// * Premium: An amount added on top of the pair price
+/// * Fixed: A fixed price that replaces the pair price
+#[cw_serde]
+pub enum NftPrice {
+    Premium(Uint128),
+    Fixed(Uint128),
+}
 
 /// PairType refers to the assets held by the pair
 /// * Token: A pair that holds fungible tokens
@@ -38,6 +48,7 @@ pub enum PairType {
 /// * Linear: A linear curve that increments by a constant amount (delta)
 /// * Exponential: An exponential curve that increments by a percentage amount (delta)
 /// * ConstantProduct: A constant product curve that maintains a constant product of the two assets
+/// * OracleAnchored: A curve that quotes around a reference price read from an oracle contract
 #[cw_serde]
 pub enum BondingCurve {
     Linear {
@@ -59,6 +70,53 @@ pub enum BondingCurve {
         delta: Decimal,
     },
     ConstantProduct,
+    OracleAnchored {
+        /// The address of the oracle contract providing the reference price,
+        /// the time weighted average trade price for the pair collection and denom
+        oracle: Addr,
+        /// The number of seconds over which the reference price is averaged
+        twap_window_seconds: u64,
+        /// The maximum number of seconds since the last oracle trade,
+        /// after which the reference price is considered stale and the pair stops quoting
+        max_staleness_seconds: u64,
+        /// The percentage below the reference price at which the pair buys NFTs
+        sell_to_pair_spread: Decimal,
+        /// The percentage above the reference price at which the pair sells NFTs
+        buy_from_pair_spread: Decimal,
+    },
+}
+
+impl BondingCurve {
+    pub fn validate(&self, api: &dyn Api) -> Result<(), ContractError> {
+        if let BondingCurve::OracleAnchored {
+            oracle,
+            twap_window_seconds,
+            sell_to_pair_spread,
+            buy_from_pair_spread,
+            ..
+        } = self
+        {
+            api.addr_validate(oracle.as_str())?;
+            ensure!(
+                *twap_window_seconds > 0,
+                InfinityError::InvalidInput("twap window must be greater than zero".to_string())
+            );
+            ensure!(
+                *sell_to_pair_spread < Decimal::one(),
+                InfinityError::InvalidInput(
+                    "sell to pair spread must be less than one".to_string()
+                )
+            );
+            ensure!(
+                *buy_from_pair_spread <= Decimal::one(),
+                InfinityError::InvalidInput(
+                    "buy from pair spread must be less than or equal to one".to_string()
+                )
+            );
+        }
+
+        Ok(())
+    }
 }
 
 #[cw_serde]
@@ -81,6 +139,124 @@ impl PairImmutable<String> {
     }
 }
 
+/// NftSelection refers to how the pair selects the NFT sold in a `SwapTokensForAnyNft` swap,
+/// among the NFTs with the cheapest quote
+/// * Ordered: The NFT with the lowest token id is selected
+/// * Random: An NFT is selected using on-chain entropy, so that buyers cannot predict
+///   which NFT will be sold
+#[cw_serde]
+#[derive(Default)]
+pub enum NftSelection {
+    #[default]
+    Ordered,
+    Random,
+}
+
+/// PairLimits refers to the optional limits on the NFTs the pair buys, set by the pair owner.
+/// Purchase limits are counted within fixed time windows of `window_seconds`.
+/// The counters are reset and the quotes recomputed before every message the pair handles,
+/// so the pair resumes buying as soon as the window elapses.
+#[cw_serde]
+#[derive(Default)]
+pub struct PairLimits {
+    /// The length of the window over which purchases are counted, in seconds
+    pub window_seconds: u64,
+    /// The maximum number of NFTs the pair buys within a window
+    pub max_nfts_bought_per_window: Option<u64>,
+    /// The maximum amount of tokens the pair spends buying NFTs within a window
+    pub max_tokens_spent_per_window: Option<Uint128>,
+    /// The maximum number of NFTs held by the pair, once reached the pair stops buying NFTs
+    pub max_inventory: Option<u64>,
+}
+
+impl PairLimits {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.max_nfts_bought_per_window.is_some() || self.max_tokens_spent_per_window.is_some() {
+            ensure!(
+                self.window_seconds > 0,
+                InfinityError::InvalidInput("window seconds must be greater than zero".to_string())
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// SweepPolicy refers to how tokens reinvested into the pair are sent to the asset recipient
+/// * Disabled: Reinvested tokens remain in the pair
+/// * Threshold: Tokens held by the pair above `max_total_tokens` are sent to the asset recipient
+#[cw_serde]
+#[derive(Default)]
+pub enum SweepPolicy {
+    #[default]
+    Disabled,
+    Threshold {
+        max_total_tokens: Uint128,
+    },
+}
+
+/// PairSchedule refers to the optional period during which the pair accepts trades,
+/// in addition to the `is_active` toggle
+#[cw_serde]
+#[derive(Default)]
+pub struct PairSchedule {
+    /// The time from which the pair accepts trades
+    pub active_from: Option<Timestamp>,
+    /// The time from which the pair no longer accepts trades
+    pub expires_at: Option<Timestamp>,
+}
+
+impl PairSchedule {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if let (Some(active_from), Some(expires_at)) = (self.active_from, self.expires_at) {
+            ensure!(
+                active_from < expires_at,
+                InfinityError::InvalidInput("active_from must be before expires_at".to_string())
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn contains(&self, block: &BlockInfo) -> bool {
+        if let Some(active_from) = self.active_from {
+            if block.time < active_from {
+                return false;
+            }
+        }
+
+        if let Some(expires_at) = self.expires_at {
+            if block.time >= expires_at {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// PairTriggers refers to the optional conditions, evaluated after each swap, under which the
+/// pair stops trading. Setting `is_active` to `true` in `UpdatePairConfig` resumes trading.
+/// * min_spot_price: Stops "sell to" trades once the "sell to" price, before fees, falls below
+///   this amount. For linear and exponential curves this is the spot price.
+/// * min_inventory: Stops "buy from" trades once the pair holds fewer NFTs than this amount
+/// * max_realized_losses: Deactivates the pair once its realized losses exceed this amount
+#[cw_serde]
+#[derive(Default)]
+pub struct PairTriggers {
+    pub min_spot_price: Option<Uint128>,
+    pub min_inventory: Option<u64>,
+    pub max_realized_losses: Option<Uint128>,
+}
+
+/// PairTrigger refers to a trigger that stopped the pair from trading
+#[cw_serde]
+pub enum PairTrigger {
+    MinSpotPrice,
+    MinInventory,
+    MaxRealizedLosses,
+}
+
 pub const PAIR_IMMUTABLE: Item<PairImmutable<Addr>> = Item::new(TopKey::PairImmutable.as_str());
 
 /// PairConfig represents the configuration parameters for a pair, set by the user
@@ -94,15 +270,39 @@ pub struct PairConfig<T: AddressLike> {
     pub is_active: bool,
     /// The address of the recipient of assets traded into the pair
     pub asset_recipient: Option<T>,
+    /// How the NFT sold in a `SwapTokensForAnyNft` swap is selected
+    #[serde(default)]
+    pub nft_selection: NftSelection,
+    /// The limits on the NFTs the pair buys
+    #[serde(default)]
+    pub limits: PairLimits,
+    /// The period during which the pair accepts trades
+    #[serde(default)]
+    pub schedule: PairSchedule,
+    /// How tokens reinvested into the pair are sent to the asset recipient
+    #[serde(default)]
+    pub sweep_policy: SweepPolicy,
+    /// The conditions under which the pair stops trading
+    #[serde(default)]
+    pub triggers: PairTriggers,
 }
 
 impl PairConfig<String> {
     pub fn str_to_addr(self, api: &dyn Api) -> Result<PairConfig<Addr>, ContractError> {
+        self.bonding_curve.validate(api)?;
+        self.limits.validate()?;
+        self.schedule.validate()?;
+
         Ok(PairConfig {
             pair_type: self.pair_type,
             bonding_curve: self.bonding_curve,
             is_active: self.is_active,
             asset_recipient: maybe_addr(api, self.asset_recipient)?,
+            nft_selection: self.nft_selection,
+            limits: self.limits,
+            schedule: self.schedule,
+            sweep_policy: self.sweep_policy,
+            triggers: self.triggers,
         })
     }
 }
@@ -120,14 +320,26 @@ pub struct TokenPayment {
 pub struct QuoteSummary {
     // The amount of tokens that will be paid out to the FairBurn contract
     pub fair_burn: TokenPayment,
-    // The amount of tokens that will be paid out in royalties
-    pub royalty: Option<TokenPayment>,
+    // The amount of tokens that will be paid out in royalties, to each royalty recipient
+    pub royalties: Vec<TokenPayment>,
     // The amount of tokens that will be paid out to pool owner LPs
     pub swap: Option<TokenPayment>,
     // The amount of tokens that will be paid out to the NFT seller
     pub seller_amount: Uint128,
 }
 
+/// LimitWindow tracks the NFTs bought by the pair within the current limit window
+#[cw_serde]
+#[derive(Default)]
+pub struct LimitWindow {
+    /// The timestamp, in seconds, at which the window started
+    pub start: u64,
+    /// The number of NFTs bought by the pair within the window
+    pub nfts_bought: u64,
+    /// The amount of tokens spent by the pair buying NFTs within the window
+    pub tokens_spent: Uint128,
+}
+
 /// PairInternal represents the internal state of the pair, not directly set by the user
 #[cw_serde]
 pub struct PairInternal {
@@ -139,6 +351,59 @@ pub struct PairInternal {
     /// A breakdown of the fees to be paid out for the next "buy from" trade
     /// When set to `None`, the pair is not accepting "buy from" trades.
     pub buy_from_pair_quote_summary: Option<QuoteSummary>,
+    /// The latest reference price read from the oracle, only used by oracle anchored curves.
+    /// When set to `None`, the oracle price is missing or stale and the pair does not quote.
+    #[serde(default)]
+    pub reference_price: Option<Uint128>,
+    /// The amount of tokens held by the pair, tracked by deposits, swaps and withdrawals.
+    /// Tokens sent directly to the pair are not included, and can be removed with `SkimExcess`.
+    #[serde(default)]
+    pub total_tokens: Uint128,
+    /// The purchases made by the pair within the current limit window
+    #[serde(default)]
+    pub limit_window: LimitWindow,
+    /// Whether the block time was outside of the pair schedule at the last update.
+    /// When set to `true`, the pair does not quote.
+    #[serde(default)]
+    pub outside_schedule: bool,
+    /// Whether "sell to" trades were stopped by the min spot price trigger
+    #[serde(default)]
+    pub sell_to_pair_halted: bool,
+    /// Whether "buy from" trades were stopped by the min inventory trigger
+    #[serde(default)]
+    pub buy_from_pair_halted: bool,
 }
 
 pub const PAIR_INTERNAL: Item<PairInternal> = Item::new(TopKey::PairInternal.as_str());
+
+// A counter mixed into the entropy used for random NFT selection, incremented on each use
+pub const NFT_SELECTION_NONCE: Item<u64> = Item::new(TopKey::NftSelectionNonce.as_str());
+
+/// PairStats represents the lifetime trading history of the pair
+#[cw_serde]
+#[derive(Default)]
+pub struct PairStats {
+    /// The number of NFTs sold to the pair
+    pub sell_to_pair_count: u64,
+    /// The amount of tokens paid by the pair for NFTs sold to the pair, including fees
+    pub sell_to_pair_volume: Uint128,
+    /// The number of NFTs bought from the pair
+    pub buy_from_pair_count: u64,
+    /// The amount of tokens paid by buyers for NFTs bought from the pair, including fees
+    pub buy_from_pair_volume: Uint128,
+    /// The amount of tokens earned by the pair owner in swap fees
+    pub swap_fees_earned: Uint128,
+    /// The amount of tokens paid in royalties
+    pub royalties_paid: Uint128,
+    /// The amount of tokens paid to the fair burn contract
+    pub fair_burn_paid: Uint128,
+    /// The amount of tokens paid by the pair for the NFTs it holds.
+    /// Deposited NFTs have no cost basis.
+    pub nft_cost_basis: Uint128,
+    /// The sale amount of NFTs bought from the pair in excess of their average cost basis
+    pub realized_gains: Uint128,
+    /// The average cost basis of NFTs bought from the pair in excess of their sale amount
+    pub realized_losses: Uint128,
+}
+
+pub const PAIR_STATS: Item<PairStats> = Item::new(TopKey::PairStats.as_str());
@@ -16,4 +16,7 @@ pub enum ContractError {
 
     #[error("SwapError: {0}")]
     SwapError(String),
+
+    #[error("CallbackError: {0}")]
+    CallbackError(String),
 }
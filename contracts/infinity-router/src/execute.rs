@@ -1,27 +1,31 @@
 use crate::error::ContractError;
-use crate::helpers::approve_nft;
-use crate::msg::{ExecuteMsg, SellOrder, SwapParams};
+use crate::helpers::{append_callback_msg, validate_referral, Referral};
+use crate::msg::{ExecuteMsg, SellOrder, SwapLeg, SwapParams};
 use crate::nfts_for_tokens_iterators::{
     iter::NftsForTokens,
     types::{NftForTokensQuote, NftForTokensSource},
 };
-use crate::state::INFINITY_GLOBAL;
+use crate::reply::{PAIR_SEND_NFT_REPLY_ID, PAIR_SWAP_REPLY_ID};
+use crate::state::{PendingSwaps, INFINITY_GLOBAL, PENDING_SWAPS};
 use crate::tokens_for_nfts_iterators::{
     types::TokensForNftQuote,
     {iter::TokensForNfts, types::TokensForNftSource},
 };
 
 use cosmwasm_std::{
-    attr, coin, ensure, ensure_eq, to_binary, Addr, CosmosMsg, DepsMut, Env, Event, MessageInfo,
+    attr, coin, ensure, ensure_eq, to_binary, Addr, Deps, DepsMut, Env, Event, MessageInfo, SubMsg,
     Uint128, WasmMsg,
 };
+use cw721::Cw721ExecuteMsg;
 use cw_utils::{must_pay, nonpayable};
-use infinity_pair::msg::ExecuteMsg as PairExecuteMsg;
+use infinity_global::load_global_config;
+use infinity_pair::msg::{ExecuteMsg as PairExecuteMsg, ReceiveNftMsg as PairReceiveNftMsg};
 use infinity_shared::{only_nft_owner, InfinityError};
 use sg_marketplace_common::address::address_or;
 use sg_marketplace_common::coin::transfer_coin;
 use sg_marketplace_common::nft::transfer_nft;
 use sg_std::Response;
+use std::collections::{BTreeMap, BTreeSet};
 use std::iter::zip;
 
 #[cfg(not(feature = "library"))]
@@ -69,9 +73,95 @@ pub fn execute(
             swap_params.unwrap_or_default().str_to_addr(api)?,
             filter_sources.unwrap_or_default(),
         ),
+        ExecuteMsg::MultiSwap {
+            legs,
+            swap_params,
+        } => execute_multi_swap(
+            deps,
+            env,
+            info,
+            legs,
+            swap_params.unwrap_or_default().str_to_addr(api)?,
+        ),
+    }
+}
+
+/// SwapContext holds the parameters shared by the swap legs of a router swap
+struct SwapContext {
+    infinity_global: Addr,
+    asset_recipient: Addr,
+    referral: Option<Referral>,
+    robust: bool,
+}
+
+impl SwapContext {
+    fn load(
+        deps: Deps,
+        info: &MessageInfo,
+        swap_params: &SwapParams<Addr>,
+    ) -> Result<Self, ContractError> {
+        let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+        let global_config = load_global_config(&deps.querier, &infinity_global)?;
+        let referral = validate_referral(swap_params, &global_config)?;
+
+        Ok(SwapContext {
+            infinity_global,
+            asset_recipient: address_or(swap_params.asset_recipient.as_ref(), &info.sender),
+            referral,
+            robust: swap_params.robust.unwrap_or(false),
+        })
     }
 }
 
+/// SwapLegOutcome summarizes the swaps executed by a swap leg
+struct SwapLegOutcome {
+    num_swaps: u32,
+    /// The tokens spent by the router on behalf of the sender, fees included
+    spent_amount: Uint128,
+    /// The denoms of the pair swaps dispatched with `SendNft`, in dispatch order
+    sent_nft_denoms: Vec<String>,
+    event: Event,
+}
+
+/// Finalizes a router swap once its swap legs have been appended to the response.
+/// The pair swaps reply with their data, which is collected into the router swap data,
+/// and the callback is executed after every other message of the response.
+fn finalize_swap(
+    deps: DepsMut,
+    info: &MessageInfo,
+    swap_params: &SwapParams<Addr>,
+    num_swaps: u32,
+    sent_nft_denoms: Vec<String>,
+    mut event: Event,
+    mut response: Response,
+) -> Result<Response, ContractError> {
+    ensure!(num_swaps > 0, ContractError::SwapError("no swaps were executed".to_string()));
+
+    // The pending swaps are removed by the last pair swap reply, so they only exist here when
+    // the router is re-entered while another router swap is in progress
+    ensure!(
+        !PENDING_SWAPS.exists(deps.storage),
+        ContractError::SwapError("another router swap is in progress".to_string())
+    );
+    PENDING_SWAPS.save(
+        deps.storage,
+        &PendingSwaps {
+            num_swaps,
+            sent_nft_denoms,
+            swaps: vec![],
+        },
+    )?;
+
+    if let Some(callback) = &swap_params.callback {
+        event = event.add_attribute("callback_contract", &callback.contract);
+    }
+
+    response = response.add_event(event);
+    response = append_callback_msg(&swap_params.callback, &info.sender, response)?;
+
+    Ok(response)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_swap_nfts_for_tokens(
     deps: DepsMut,
@@ -85,10 +175,244 @@ pub fn execute_swap_nfts_for_tokens(
 ) -> Result<Response, ContractError> {
     nonpayable(&info)?;
 
-    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
-    let iterator = NftsForTokens::initialize(
+    let swap_context = SwapContext::load(deps.as_ref(), &info, &swap_params)?;
+
+    let (response, outcome) = swap_nfts_for_tokens(
+        deps.as_ref(),
+        &env,
+        &info,
+        &swap_context,
+        collection,
+        denom,
+        sell_orders,
+        filter_sources,
+        Response::new(),
+    )?;
+
+    finalize_swap(
+        deps,
+        &info,
+        &swap_params,
+        outcome.num_swaps,
+        outcome.sent_nft_denoms,
+        outcome.event,
+        response,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_swap_tokens_for_nfts(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    denom: String,
+    max_inputs: Vec<Uint128>,
+    swap_params: SwapParams<Addr>,
+    filter_sources: Vec<TokensForNftSource>,
+) -> Result<Response, ContractError> {
+    let received_amount = must_pay(&info, &denom)?;
+    let expected_amount = max_inputs.iter().sum::<Uint128>();
+    ensure_eq!(
+        received_amount,
+        expected_amount,
+        InfinityError::InsufficientFunds {
+            expected: coin(expected_amount.u128(), &denom),
+        }
+    );
+
+    let swap_context = SwapContext::load(deps.as_ref(), &info, &swap_params)?;
+
+    let (mut response, outcome) = swap_tokens_for_nfts(
         deps.as_ref(),
-        &infinity_global,
+        &swap_context,
+        collection,
+        denom.clone(),
+        max_inputs,
+        filter_sources,
+        Response::new(),
+    )?;
+
+    let refund_amount = received_amount.checked_sub(outcome.spent_amount).unwrap();
+    if !refund_amount.is_zero() {
+        response = transfer_coin(
+            coin(refund_amount.u128(), &denom),
+            &swap_context.asset_recipient,
+            response,
+        );
+    }
+
+    finalize_swap(
+        deps,
+        &info,
+        &swap_params,
+        outcome.num_swaps,
+        outcome.sent_nft_denoms,
+        outcome.event,
+        response,
+    )
+}
+
+pub fn execute_multi_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    legs: Vec<SwapLeg>,
+    swap_params: SwapParams<Addr>,
+) -> Result<Response, ContractError> {
+    ensure!(!legs.is_empty(), InfinityError::InvalidInput("legs should not be empty".to_string()));
+
+    // Each leg is quoted independently of the others, so two legs on the same side of the same
+    // collection and denom would be quoted against the same pairs, and the second leg would fail
+    let mut leg_keys: BTreeSet<(&str, &String, &String)> = BTreeSet::new();
+    for leg in &legs {
+        let leg_key = match leg {
+            SwapLeg::NftsForTokens {
+                collection,
+                denom,
+                ..
+            } => ("nfts_for_tokens", collection, denom),
+            SwapLeg::TokensForNfts {
+                collection,
+                denom,
+                ..
+            } => ("tokens_for_nfts", collection, denom),
+        };
+        ensure!(
+            leg_keys.insert(leg_key),
+            InfinityError::InvalidInput(format!(
+                "duplicate swap leg for collection {} and denom {}",
+                leg_key.1, leg_key.2
+            ))
+        );
+    }
+
+    // The funds sent must cover the max inputs of every tokens for nfts leg, in each denom
+    let mut expected_funds: BTreeMap<String, Uint128> = BTreeMap::new();
+    for leg in &legs {
+        if let SwapLeg::TokensForNfts {
+            denom,
+            max_inputs,
+            ..
+        } = leg
+        {
+            *expected_funds.entry(denom.clone()).or_default() += max_inputs.iter().sum::<Uint128>();
+        }
+    }
+    expected_funds.retain(|_, amount| !amount.is_zero());
+
+    let received_funds = info
+        .funds
+        .iter()
+        .map(|fund| (fund.denom.clone(), fund.amount))
+        .collect::<BTreeMap<String, Uint128>>();
+    for (denom, expected_amount) in &expected_funds {
+        ensure_eq!(
+            received_funds.get(denom).cloned().unwrap_or_default(),
+            *expected_amount,
+            InfinityError::InsufficientFunds {
+                expected: coin(expected_amount.u128(), denom),
+            }
+        );
+    }
+    ensure!(
+        received_funds.keys().all(|denom| expected_funds.contains_key(denom)),
+        InfinityError::InvalidInput("received funds in a denom not used by any leg".to_string())
+    );
+
+    let swap_context = SwapContext::load(deps.as_ref(), &info, &swap_params)?;
+
+    let api = deps.api;
+    let num_legs = legs.len();
+    let mut response = Response::new();
+    let mut num_swaps = 0u32;
+    let mut sent_nft_denoms: Vec<String> = vec![];
+    let mut spent_funds: BTreeMap<String, Uint128> = BTreeMap::new();
+    for leg in legs {
+        let outcome = match leg {
+            SwapLeg::NftsForTokens {
+                collection,
+                denom,
+                sell_orders,
+                filter_sources,
+            } => {
+                let (leg_response, outcome) = swap_nfts_for_tokens(
+                    deps.as_ref(),
+                    &env,
+                    &info,
+                    &swap_context,
+                    api.addr_validate(&collection)?,
+                    denom,
+                    sell_orders,
+                    filter_sources.unwrap_or_default(),
+                    response,
+                )?;
+                response = leg_response;
+                outcome
+            },
+            SwapLeg::TokensForNfts {
+                collection,
+                denom,
+                max_inputs,
+                filter_sources,
+            } => {
+                let (leg_response, outcome) = swap_tokens_for_nfts(
+                    deps.as_ref(),
+                    &swap_context,
+                    api.addr_validate(&collection)?,
+                    denom.clone(),
+                    max_inputs,
+                    filter_sources.unwrap_or_default(),
+                    response,
+                )?;
+                *spent_funds.entry(denom).or_default() += outcome.spent_amount;
+                response = leg_response;
+                outcome
+            },
+        };
+        num_swaps += outcome.num_swaps;
+        sent_nft_denoms.extend(outcome.sent_nft_denoms);
+        response = response.add_event(outcome.event);
+    }
+
+    // Funds that were not spent by the legs are refunded, in each denom
+    for (denom, received_amount) in received_funds {
+        let spent_amount = spent_funds.get(&denom).cloned().unwrap_or_default();
+        let refund_amount = received_amount.checked_sub(spent_amount).unwrap();
+        if !refund_amount.is_zero() {
+            response = transfer_coin(
+                coin(refund_amount.u128(), &denom),
+                &swap_context.asset_recipient,
+                response,
+            );
+        }
+    }
+
+    let event = Event::new("router-multi-swap").add_attributes(vec![
+        attr("sender_recipient", &swap_context.asset_recipient),
+        attr("num_legs", num_legs.to_string()),
+        attr("num_swaps", num_swaps.to_string()),
+    ]);
+
+    finalize_swap(deps, &info, &swap_params, num_swaps, sent_nft_denoms, event, response)
+}
+
+/// Swaps NFTs of a collection for tokens, appending the swaps to the response
+#[allow(clippy::too_many_arguments)]
+fn swap_nfts_for_tokens(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    swap_context: &SwapContext,
+    collection: Addr,
+    denom: String,
+    sell_orders: Vec<SellOrder>,
+    filter_sources: Vec<NftForTokensSource>,
+    mut response: Response,
+) -> Result<(Response, SwapLegOutcome), ContractError> {
+    let iterator = NftsForTokens::initialize(
+        deps,
+        &swap_context.infinity_global,
         &collection,
         &denom,
         filter_sources,
@@ -97,86 +421,120 @@ pub fn execute_swap_nfts_for_tokens(
     let requested_swaps = sell_orders.len();
     let quotes = iterator.take(requested_swaps).collect::<Vec<NftForTokensQuote>>();
 
-    let mut response = Response::new();
+    let referral = swap_context.referral.as_ref();
+    let asset_recipient = &swap_context.asset_recipient;
 
-    let asset_recipient = address_or(swap_params.asset_recipient.as_ref(), &info.sender);
+    // When a referral fee is charged, the router collects the swap proceeds
+    // so that it can split them between the referrer and the seller.
+    let proceeds_recipient = if referral.is_some() {
+        env.contract.address.clone()
+    } else {
+        asset_recipient.clone()
+    };
 
     let mut num_swaps = 0u32;
     let mut volume = Uint128::zero();
+    let mut referral_fee = Uint128::zero();
     for (sell_order, quote) in zip(sell_orders, quotes) {
-        if quote.amount < sell_order.min_output {
+        let quote_referral_fee = referral.map_or(Uint128::zero(), |r| r.fee(quote.amount));
+        if quote.amount - quote_referral_fee < sell_order.min_output {
             break;
         }
 
-        only_nft_owner(&deps.querier, &info, &collection, &sell_order.input_token_id)?;
+        only_nft_owner(&deps.querier, info, &collection, &sell_order.input_token_id)?;
         response =
             transfer_nft(&collection, &sell_order.input_token_id, &env.contract.address, response);
 
+        // If the router is collecting proceeds, the pair must pay out at least the quoted amount
+        let min_output = if referral.is_some() {
+            quote.amount
+        } else {
+            sell_order.min_output
+        };
+
         match quote.source {
             NftForTokensSource::Infinity => {
-                response =
-                    approve_nft(&collection, &quote.address, &sell_order.input_token_id, response);
-                response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: quote.address.to_string(),
-                    msg: to_binary(&PairExecuteMsg::SwapNftForTokens {
-                        token_id: sell_order.input_token_id,
-                        min_output: coin(sell_order.min_output.u128(), &denom),
-                        asset_recipient: Some(asset_recipient.to_string()),
-                    })?,
-                    funds: vec![],
-                }))
+                // The NFT is sent to the pair, which swaps it on receipt
+                response = response.add_submessage(SubMsg::reply_on_success(
+                    WasmMsg::Execute {
+                        contract_addr: collection.to_string(),
+                        msg: to_binary(&Cw721ExecuteMsg::SendNft {
+                            contract: quote.address.to_string(),
+                            token_id: sell_order.input_token_id.clone(),
+                            msg: to_binary(&PairReceiveNftMsg::SwapNftForTokens {
+                                min_output: coin(min_output.u128(), &denom),
+                                asset_recipient: Some(proceeds_recipient.to_string()),
+                            })?,
+                        })?,
+                        funds: vec![],
+                    },
+                    PAIR_SEND_NFT_REPLY_ID,
+                ));
             },
         }
 
         num_swaps += 1;
         volume += quote.amount;
+        referral_fee += quote_referral_fee;
     }
 
-    ensure!(num_swaps > 0, ContractError::SwapError("no swaps were executed".to_string()));
-
-    if num_swaps < (requested_swaps as u32) && !swap_params.robust.unwrap_or(false) {
+    if num_swaps < (requested_swaps as u32) && !swap_context.robust {
         return Err(ContractError::SwapError(format!(
             "unable to swap all nfts for tokens, requested swaps: {}, actual swaps: {}",
             requested_swaps, num_swaps
         )));
     }
 
-    response = response.add_event(Event::new("router-swap-nfts-for-tokens").add_attributes(vec![
+    let mut event = Event::new("router-swap-nfts-for-tokens").add_attributes(vec![
         attr("collection", collection),
-        attr("denom", denom),
+        attr("denom", &denom),
         attr("sender_recipient", asset_recipient),
         attr("num_swaps", num_swaps.to_string()),
         attr("volume", volume),
-    ]));
+    ]);
 
-    Ok(response)
+    if let Some(referral) = referral {
+        if !referral_fee.is_zero() {
+            response =
+                transfer_coin(coin(referral_fee.u128(), &denom), &referral.referrer, response);
+        }
+        if !volume.is_zero() {
+            response = transfer_coin(
+                coin((volume - referral_fee).u128(), &denom),
+                asset_recipient,
+                response,
+            );
+        }
+        event = event.add_attributes(vec![
+            attr("referrer", &referral.referrer),
+            attr("referral_fee", referral_fee),
+        ]);
+    }
+
+    Ok((
+        response,
+        SwapLegOutcome {
+            num_swaps,
+            spent_amount: Uint128::zero(),
+            sent_nft_denoms: vec![denom; num_swaps as usize],
+            event,
+        },
+    ))
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn execute_swap_tokens_for_nfts(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
+/// Swaps tokens for NFTs of a collection, appending the swaps to the response
+fn swap_tokens_for_nfts(
+    deps: Deps,
+    swap_context: &SwapContext,
     collection: Addr,
     denom: String,
     max_inputs: Vec<Uint128>,
-    swap_params: SwapParams<Addr>,
     filter_sources: Vec<TokensForNftSource>,
-) -> Result<Response, ContractError> {
-    let received_amount = must_pay(&info, &denom)?;
-    let expected_amount = max_inputs.iter().sum::<Uint128>();
-    ensure_eq!(
-        received_amount,
-        expected_amount,
-        InfinityError::InsufficientFunds {
-            expected: coin(expected_amount.u128(), &denom),
-        }
-    );
-
-    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    mut response: Response,
+) -> Result<(Response, SwapLegOutcome), ContractError> {
     let iterator = TokensForNfts::initialize(
-        deps.as_ref(),
-        &infinity_global,
+        deps,
+        &swap_context.infinity_global,
         &collection,
         &denom,
         filter_sources,
@@ -185,54 +543,72 @@ pub fn execute_swap_tokens_for_nfts(
     let requested_swaps = max_inputs.len();
     let quotes = iterator.take(requested_swaps).collect::<Vec<TokensForNftQuote>>();
 
-    let mut response = Response::new();
-
-    let asset_recipient = address_or(swap_params.asset_recipient.as_ref(), &info.sender);
+    let referral = swap_context.referral.as_ref();
+    let asset_recipient = &swap_context.asset_recipient;
 
     let mut num_swaps = 0u32;
     let mut paid_amount = Uint128::zero();
+    let mut referral_fee = Uint128::zero();
     for (max_input, quote) in zip(max_inputs, quotes) {
-        if max_input < quote.amount {
+        // The referral fee is added on top of the quote, and is paid by the buyer
+        let quote_referral_fee = referral.map_or(Uint128::zero(), |r| r.fee(quote.amount));
+        if max_input < quote.amount + quote_referral_fee {
             break;
         }
 
         match quote.source {
             TokensForNftSource::Infinity => {
-                response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: quote.address.to_string(),
-                    msg: to_binary(&PairExecuteMsg::SwapTokensForAnyNft {
-                        asset_recipient: Some(asset_recipient.to_string()),
-                    })?,
-                    funds: vec![coin(quote.amount.u128(), &denom)],
-                }))
+                response = response.add_submessage(SubMsg::reply_on_success(
+                    WasmMsg::Execute {
+                        contract_addr: quote.address.to_string(),
+                        msg: to_binary(&PairExecuteMsg::SwapTokensForAnyNft {
+                            asset_recipient: Some(asset_recipient.to_string()),
+                        })?,
+                        funds: vec![coin(quote.amount.u128(), &denom)],
+                    },
+                    PAIR_SWAP_REPLY_ID,
+                ));
             },
         }
 
         paid_amount += quote.amount;
+        referral_fee += quote_referral_fee;
         num_swaps += 1;
     }
 
-    ensure!(num_swaps > 0, ContractError::SwapError("no swaps were executed".to_string()));
-
-    if num_swaps < (requested_swaps as u32) && !swap_params.robust.unwrap_or(false) {
+    if num_swaps < (requested_swaps as u32) && !swap_context.robust {
         return Err(ContractError::SwapError(format!(
             "unable to swap all tokens for nfts, requested swaps: {}, actual swaps: {}",
             requested_swaps, num_swaps
         )));
     }
 
-    let refund_amount = received_amount.checked_sub(paid_amount).unwrap();
-    if !refund_amount.is_zero() {
-        response = transfer_coin(coin(refund_amount.u128(), &denom), &asset_recipient, response);
-    }
-
-    response = response.add_event(Event::new("router-swap-tokens-for-nfts").add_attributes(vec![
+    let mut event = Event::new("router-swap-tokens-for-nfts").add_attributes(vec![
         attr("collection", collection),
-        attr("denom", denom),
+        attr("denom", &denom),
         attr("sender_recipient", asset_recipient),
         attr("num_swaps", num_swaps.to_string()),
         attr("volume", paid_amount), // volume is the amount of tokens paid
-    ]));
+    ]);
 
-    Ok(response)
+    if let Some(referral) = referral {
+        if !referral_fee.is_zero() {
+            response =
+                transfer_coin(coin(referral_fee.u128(), &denom), &referral.referrer, response);
+        }
+        event = event.add_attributes(vec![
+            attr("referrer", &referral.referrer),
+            attr("referral_fee", referral_fee),
+        ]);
+    }
+
+    Ok((
+        response,
+        SwapLegOutcome {
+            num_swaps,
+            spent_amount: paid_amount + referral_fee,
+            sent_nft_denoms: vec![],
+            event,
+        },
+    ))
 }
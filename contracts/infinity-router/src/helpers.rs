@@ -1,21 +1,76 @@
-use cosmwasm_std::{to_binary, Addr, SubMsg, WasmMsg};
-use cw721::Cw721ExecuteMsg;
+use crate::msg::{ReceiveSwapCallbackMsg, SwapCallback, SwapParams};
+use crate::reply::CALLBACK_REPLY_ID;
+use crate::ContractError;
+
+use cosmwasm_std::{ensure, Addr, Decimal, SubMsg, Uint128, WasmMsg};
+use infinity_global::GlobalConfig;
+use infinity_shared::InfinityError;
 use sg_std::Response;
 
-pub fn approve_nft(
-    collection: &Addr,
-    spender: &Addr,
-    token_id: &String,
+/// Executes the swap callback, if any, after the messages already in the response.
+/// The callback message is wrapped in a `ReceiveSwapCallbackMsg`, and is replied to
+/// so that the router can assert that it succeeded.
+pub fn append_callback_msg(
+    callback: &Option<SwapCallback<Addr>>,
+    sender: &Addr,
     response: Response,
-) -> Response {
-    response.add_submessage(SubMsg::new(WasmMsg::Execute {
-        contract_addr: collection.to_string(),
-        msg: to_binary(&Cw721ExecuteMsg::Approve {
-            spender: spender.to_string(),
-            token_id: token_id.to_string(),
-            expires: None,
-        })
-        .unwrap(),
-        funds: vec![],
-    }))
+) -> Result<Response, ContractError> {
+    match callback {
+        Some(callback) => Ok(response.add_submessage(SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: callback.contract.to_string(),
+                msg: ReceiveSwapCallbackMsg {
+                    sender: sender.to_string(),
+                    msg: callback.msg.clone(),
+                }
+                .into_binary()?,
+                funds: vec![],
+            },
+            CALLBACK_REPLY_ID,
+        ))),
+        None => Ok(response),
+    }
+}
+
+/// Referral is the validated referrer of a router swap, and the fee they are owed
+pub struct Referral {
+    pub referrer: Addr,
+    pub fee_percent: Decimal,
+}
+
+impl Referral {
+    /// The referral fee owed on a single swap of the given amount
+    pub fn fee(&self, amount: Uint128) -> Uint128 {
+        amount.mul_ceil(self.fee_percent)
+    }
+}
+
+/// Validate the referral fields of the swap params against the global config.
+/// Returns `None` when no referral fee should be charged.
+pub fn validate_referral(
+    swap_params: &SwapParams<Addr>,
+    global_config: &GlobalConfig<Addr>,
+) -> Result<Option<Referral>, ContractError> {
+    match (&swap_params.referrer, swap_params.referral_fee_percent) {
+        (None, None) => Ok(None),
+        (Some(referrer), Some(fee_percent)) => {
+            ensure!(
+                fee_percent <= global_config.max_referral_fee_percent,
+                InfinityError::InvalidInput(
+                    "referral fee percent exceeds max referral fee percent".to_string()
+                )
+            );
+            if fee_percent.is_zero() {
+                return Ok(None);
+            }
+            Ok(Some(Referral {
+                referrer: referrer.clone(),
+                fee_percent,
+            }))
+        },
+        _ => Err(InfinityError::InvalidInput(
+            "referrer and referral fee percent must be set together".to_string(),
+        )
+        .into()),
+    }
 }
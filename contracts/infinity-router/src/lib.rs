@@ -5,6 +5,7 @@ pub mod migrate;
 pub mod msg;
 pub mod nfts_for_tokens_iterators;
 pub mod query;
+pub mod reply;
 pub mod state;
 pub mod tokens_for_nfts_iterators;
 
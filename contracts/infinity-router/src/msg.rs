@@ -6,9 +6,10 @@ use crate::{
 };
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Api, Uint128};
+use cosmwasm_std::{to_binary, Addr, Api, Binary, Decimal, StdResult, Uint128};
 use cw_address_like::AddressLike;
 use cw_utils::maybe_addr;
+use infinity_pair::msg::SwapData;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -24,6 +25,46 @@ pub struct SwapParams<T: AddressLike> {
     pub robust: Option<bool>,
     /// The address to receive the assets from the swap, if not specified is set to sender
     pub asset_recipient: Option<T>,
+    /// The address of the frontend or integrator that referred the swap
+    pub referrer: Option<T>,
+    /// The percentage of each swap paid to the referrer, capped by the global config
+    pub referral_fee_percent: Option<Decimal>,
+    /// A message executed on a contract once the assets of the swap have been delivered,
+    /// the swap is reverted when the callback fails
+    pub callback: Option<SwapCallback<T>>,
+}
+
+/// SwapCallback is a message executed by the router after a swap
+#[cw_serde]
+pub struct SwapCallback<T: AddressLike> {
+    /// The address of the contract to execute
+    pub contract: T,
+    /// The message passed to the contract, wrapped in a `ReceiveSwapCallbackMsg`
+    pub msg: Binary,
+}
+
+/// ReceiveSwapCallbackMsg is sent by the router to the callback contract of a swap.
+/// Callback contracts must handle `ExecuteMsg::ReceiveSwapCallback(ReceiveSwapCallbackMsg)`,
+/// so that the router never executes arbitrary messages on contracts that have not opted in,
+/// such as collections where the sender approved the router.
+#[cw_serde]
+pub struct ReceiveSwapCallbackMsg {
+    /// The sender of the router swap
+    pub sender: String,
+    /// The message set in the swap callback
+    pub msg: Binary,
+}
+
+impl ReceiveSwapCallbackMsg {
+    pub fn into_binary(self) -> StdResult<Binary> {
+        to_binary(&SwapCallbackExecuteMsg::ReceiveSwapCallback(self))
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[cw_serde]
+enum SwapCallbackExecuteMsg {
+    ReceiveSwapCallback(ReceiveSwapCallbackMsg),
 }
 
 impl SwapParams<String> {
@@ -31,6 +72,15 @@ impl SwapParams<String> {
         Ok(SwapParams {
             robust: self.robust,
             asset_recipient: maybe_addr(api, self.asset_recipient.clone())?,
+            referrer: maybe_addr(api, self.referrer.clone())?,
+            referral_fee_percent: self.referral_fee_percent,
+            callback: match &self.callback {
+                Some(callback) => Some(SwapCallback {
+                    contract: api.addr_validate(&callback.contract)?,
+                    msg: callback.msg.clone(),
+                }),
+                None => None,
+            },
         })
     }
 }
@@ -57,6 +107,37 @@ pub enum ExecuteMsg {
         swap_params: Option<SwapParams<String>>,
         filter_sources: Option<Vec<TokensForNftSource>>,
     },
+    /// Execute several swap legs, each bound to its own collection and denom, in one swap.
+    /// The funds sent must equal the sum of the max inputs of the tokens for nfts legs in
+    /// each denom, unspent funds are refunded. The swap params apply to every leg.
+    /// At most one leg of each kind is allowed per collection and denom.
+    MultiSwap {
+        legs: Vec<SwapLeg>,
+        swap_params: Option<SwapParams<String>>,
+    },
+}
+
+/// SwapLeg is a swap within a single collection and denom, executed as part of a `MultiSwap`
+#[cw_serde]
+pub enum SwapLeg {
+    NftsForTokens {
+        collection: String,
+        denom: String,
+        sell_orders: Vec<SellOrder>,
+        filter_sources: Option<Vec<NftForTokensSource>>,
+    },
+    TokensForNfts {
+        collection: String,
+        denom: String,
+        max_inputs: Vec<Uint128>,
+        filter_sources: Option<Vec<TokensForNftSource>>,
+    },
+}
+
+/// The data set on the response of a router swap, describing each pair swap executed
+#[cw_serde]
+pub struct SwapResponse {
+    pub swaps: Vec<SwapData>,
 }
 
 #[cw_serde]
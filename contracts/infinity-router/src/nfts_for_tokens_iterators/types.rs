@@ -1,6 +1,8 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
+use infinity_pair::helpers::SimNftPrices;
 use infinity_pair::pair::Pair;
+use infinity_pair::state::PairStats;
 use std::cmp::Ordering;
 
 #[cw_serde]
@@ -10,7 +12,7 @@ pub enum NftForTokensSource {
 
 #[cw_serde]
 pub enum NftForTokensSourceData {
-    Infinity(Pair),
+    Infinity(Pair, PairStats, SimNftPrices),
 }
 
 #[cw_serde]
@@ -47,7 +49,7 @@ impl From<&NftForTokensInternal> for NftForTokensQuote {
             address: internal.address.clone(),
             amount: internal.amount,
             source: match &internal.source_data {
-                NftForTokensSourceData::Infinity(_) => NftForTokensSource::Infinity,
+                NftForTokensSourceData::Infinity(..) => NftForTokensSource::Infinity,
             },
         }
     }
@@ -0,0 +1,120 @@
+use crate::msg::SwapResponse;
+use crate::state::PENDING_SWAPS;
+use crate::ContractError;
+
+use cosmwasm_std::{
+    coin, ensure, from_binary, to_binary, Addr, DepsMut, Env, Event, Reply, SubMsgResult, Uint128,
+};
+use cw_utils::parse_reply_execute_data;
+use infinity_pair::msg::{SwapData, TransactionType};
+use infinity_shared::events::{
+    FAIR_BURN_FEE_ATTR, ROYALTY_FEE_ATTR, SELLER_AMOUNT_ATTR, SWAP_FEE_ATTR,
+    SWAP_NFT_FOR_TOKENS_EVENT, TOKEN_ID_ATTR,
+};
+use infinity_shared::InfinityError;
+use sg_std::Response;
+use std::str::FromStr;
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+pub const PAIR_SWAP_REPLY_ID: u64 = 1;
+pub const CALLBACK_REPLY_ID: u64 = 2;
+pub const PAIR_SEND_NFT_REPLY_ID: u64 = 3;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        PAIR_SWAP_REPLY_ID => {
+            let data = parse_reply_execute_data(msg)
+                .map_err(|err| InfinityError::InternalError(err.to_string()))?
+                .data
+                .ok_or_else(|| {
+                    InfinityError::InternalError("pair swap did not return data".to_string())
+                })?;
+            let swap_data = from_binary::<SwapData>(&data)?;
+
+            record_pair_swap(deps, swap_data)
+        },
+        PAIR_SEND_NFT_REPLY_ID => {
+            let events = msg.result.into_result().map_err(InfinityError::InternalError)?.events;
+
+            // The pair swap is executed by the collection on `SendNft`, so its data does not
+            // reach the router. The swap data is built from the pair swap event instead.
+            let mut pending_swaps = PENDING_SWAPS.load(deps.storage)?;
+            ensure!(
+                !pending_swaps.sent_nft_denoms.is_empty(),
+                InfinityError::InternalError("no pending nft for tokens swap".to_string())
+            );
+            let denom = pending_swaps.sent_nft_denoms.remove(0);
+            PENDING_SWAPS.save(deps.storage, &pending_swaps)?;
+
+            let swap_data = parse_swap_nft_for_tokens_event(&events, &denom)?;
+
+            record_pair_swap(deps, swap_data)
+        },
+        CALLBACK_REPLY_ID => match msg.result {
+            SubMsgResult::Ok(_) => Ok(Response::new()),
+            SubMsgResult::Err(err) => Err(ContractError::CallbackError(err)),
+        },
+        id => Err(InfinityError::InternalError(format!("unknown reply id {}", id)).into()),
+    }
+}
+
+/// Collects the data of a pair swap. The data collected once every pair swap has replied
+/// becomes the data of the router swap.
+fn record_pair_swap(deps: DepsMut, swap_data: SwapData) -> Result<Response, ContractError> {
+    let mut pending_swaps = PENDING_SWAPS.load(deps.storage)?;
+    pending_swaps.swaps.push(swap_data);
+
+    if pending_swaps.swaps.len() < pending_swaps.num_swaps as usize {
+        PENDING_SWAPS.save(deps.storage, &pending_swaps)?;
+        return Ok(Response::new());
+    }
+
+    PENDING_SWAPS.remove(deps.storage);
+    Ok(Response::new().set_data(to_binary(&SwapResponse {
+        swaps: pending_swaps.swaps,
+    })?))
+}
+
+/// Builds the swap data of an NFT for tokens pair swap from the swap event emitted by the pair
+fn parse_swap_nft_for_tokens_event(
+    events: &[Event],
+    denom: &str,
+) -> Result<SwapData, ContractError> {
+    // Events emitted by contracts are prefixed with `wasm-`
+    let event_ty = format!("wasm-{}", SWAP_NFT_FOR_TOKENS_EVENT);
+    let event = events.iter().find(|event| event.ty == event_ty).ok_or_else(|| {
+        InfinityError::InternalError("pair swap did not emit a swap event".to_string())
+    })?;
+
+    // The royalty and swap fees are only emitted when they are charged
+    let fair_burn_fee = Uint128::from_str(required_event_attr(event, FAIR_BURN_FEE_ATTR)?)?;
+    let seller_amount = Uint128::from_str(required_event_attr(event, SELLER_AMOUNT_ATTR)?)?;
+    let royalty_fee =
+        event_attr(event, ROYALTY_FEE_ATTR).map_or(Ok(Uint128::zero()), Uint128::from_str)?;
+    let swap_fee =
+        event_attr(event, SWAP_FEE_ATTR).map_or(Ok(Uint128::zero()), Uint128::from_str)?;
+    let price = fair_burn_fee + royalty_fee + swap_fee + seller_amount;
+
+    Ok(SwapData {
+        pair: Addr::unchecked(required_event_attr(event, "_contract_address")?),
+        transaction_type: TransactionType::UserSubmitsNfts,
+        token_id: required_event_attr(event, TOKEN_ID_ATTR)?.to_string(),
+        price: coin(price.u128(), denom),
+        fair_burn_fee,
+        royalty_fee,
+        swap_fee,
+        seller_amount,
+    })
+}
+
+fn event_attr<'a>(event: &'a Event, key: &str) -> Option<&'a str> {
+    event.attributes.iter().find(|attr| attr.key == key).map(|attr| attr.value.as_str())
+}
+
+fn required_event_attr<'a>(event: &'a Event, key: &str) -> Result<&'a str, InfinityError> {
+    event_attr(event, key)
+        .ok_or_else(|| InfinityError::InternalError(format!("pair swap event is missing {}", key)))
+}
@@ -1,5 +1,24 @@
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Addr;
 use cw_storage_plus::Item;
+use infinity_pair::msg::SwapData;
 
 // The address of the infinity global contract
 pub const INFINITY_GLOBAL: Item<Addr> = Item::new("g");
+
+/// PendingSwaps collects the data of the pair swaps dispatched by a router swap,
+/// from the replies of the pair swaps
+#[cw_serde]
+pub struct PendingSwaps {
+    /// The number of pair swaps dispatched by the router swap
+    pub num_swaps: u32,
+    /// The denoms of the pair swaps dispatched with `SendNft`, in dispatch order.
+    /// Their replies carry no data, so the swap data is built from the pair swap event.
+    pub sent_nft_denoms: Vec<String>,
+    /// The data of the pair swaps that have replied
+    pub swaps: Vec<SwapData>,
+}
+
+// The pair swaps of the router swap in progress, removed once every pair swap has replied.
+// Only one router swap can be in progress at a time.
+pub const PENDING_SWAPS: Item<PendingSwaps> = Item::new("p");
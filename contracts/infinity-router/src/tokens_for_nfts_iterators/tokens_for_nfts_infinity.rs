@@ -8,7 +8,11 @@ use infinity_index::{
 };
 use infinity_pair::helpers::load_payout_context;
 use infinity_pair::pair::Pair;
-use infinity_pair::{helpers::PayoutContext, msg::QueryMsg as PairQueryMsg};
+use infinity_pair::state::PairStats;
+use infinity_pair::{
+    helpers::{PayoutContext, SimNftPrices},
+    msg::QueryMsg as PairQueryMsg,
+};
 use sg_index_query::{QueryBound, QueryOptions};
 use std::collections::BTreeSet;
 
@@ -78,10 +82,27 @@ impl<'a> TokensForNftsInfinity<'a> {
                 .map_err(|_| StdError::generic_err("pair not found"))
                 .unwrap();
 
+            // Pairs that predate pair stats are simulated without a trading history
+            let pair_stats = self
+                .deps
+                .querier
+                .query_wasm_smart::<PairStats>(&pair_quote.address, &PairQueryMsg::PairStats {})
+                .unwrap_or_default();
+
+            // Pairs that predate listing prices are simulated with unlisted NFTs
+            let sim_nft_prices = self
+                .deps
+                .querier
+                .query_wasm_smart::<SimNftPrices>(
+                    &pair_quote.address,
+                    &PairQueryMsg::SimNftPrices {},
+                )
+                .unwrap_or_else(|_| SimNftPrices::unlisted(pair.internal.total_nfts));
+
             self.quotes.insert(TokensForNftInternal {
                 address: pair_quote.address,
                 amount: pair_quote.quote.amount,
-                source_data: TokensForNftSourceData::Infinity(pair),
+                source_data: TokensForNftSourceData::Infinity(pair, pair_stats, sim_nft_prices),
             });
         } else {
             self.cursor = None;
@@ -104,8 +125,12 @@ impl<'a> Iterator for TokensForNftsInfinity<'a> {
             }
 
             match next_quote.source_data {
-                TokensForNftSourceData::Infinity(ref mut pair) => {
-                    pair.sim_swap_tokens_for_nft(&self.payout_context);
+                TokensForNftSourceData::Infinity(
+                    ref mut pair,
+                    ref mut pair_stats,
+                    ref mut sim_nft_prices,
+                ) => {
+                    pair.sim_swap_tokens_for_nft(&self.payout_context, pair_stats, sim_nft_prices);
 
                     if let Some(summary) = &pair.internal.buy_from_pair_quote_summary {
                         next_quote.amount = summary.total();
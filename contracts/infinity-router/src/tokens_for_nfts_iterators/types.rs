@@ -1,6 +1,8 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
+use infinity_pair::helpers::SimNftPrices;
 use infinity_pair::pair::Pair;
+use infinity_pair::state::PairStats;
 use std::cmp::Ordering;
 
 #[cw_serde]
@@ -10,7 +12,7 @@ pub enum TokensForNftSource {
 
 #[cw_serde]
 pub enum TokensForNftSourceData {
-    Infinity(Pair),
+    Infinity(Pair, PairStats, SimNftPrices),
 }
 
 #[cw_serde]
@@ -47,7 +49,7 @@ impl From<&TokensForNftInternal> for TokensForNftQuote {
             address: internal.address.clone(),
             amount: internal.amount,
             source: match &internal.source_data {
-                TokensForNftSourceData::Infinity(_) => TokensForNftSource::Infinity,
+                TokensForNftSourceData::Infinity(..) => TokensForNftSource::Infinity,
             },
         }
     }
@@ -0,0 +1,16 @@
+//! The event types and attribute keys of the pair swap events, shared between the pair that
+//! emits them and the contracts that read them from the events of a sub message reply
+
+/// The type of the event emitted by a pair when it buys an NFT
+pub const SWAP_NFT_FOR_TOKENS_EVENT: &str = "swap-nft-for-tokens";
+/// The type of the event emitted by a pair when it sells an NFT
+pub const SWAP_TOKENS_FOR_NFT_EVENT: &str = "swap-tokens-for-nft";
+
+pub const TOKEN_ID_ATTR: &str = "token_id";
+pub const SENDER_RECIPIENT_ATTR: &str = "sender_recipient";
+pub const FAIR_BURN_FEE_ATTR: &str = "fair_burn_fee";
+pub const SELLER_AMOUNT_ATTR: &str = "seller_amount";
+/// The sum of the royalties paid to every royalty recipient, only emitted when royalties are paid
+pub const ROYALTY_FEE_ATTR: &str = "royalty_fee";
+/// Only emitted when a swap fee is charged
+pub const SWAP_FEE_ATTR: &str = "swap_fee";
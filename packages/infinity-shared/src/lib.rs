@@ -1,6 +1,7 @@
 pub use crate::error::InfinityError;
 
 mod error;
+pub mod events;
 
 use cosmwasm_std::{ensure_eq, Addr, Empty, MessageInfo, QuerierWrapper, StdResult};
 use cw721::OwnerOfResponse;
@@ -0,0 +1,37 @@
+use cosmwasm_std::Addr;
+use cw_multi_test::Executor;
+use infinity_global::msg::{
+    ExecuteMsg as InfinityGlobalExecuteMsg, QueryMsg as InfinityGlobalQueryMsg,
+    SudoMsg as InfinityGlobalSudoMsg,
+};
+use infinity_global::ConfigUpdate;
+use sg_multi_test::StargazeApp;
+
+/// Proposes a global config update, and executes it once the config timelock has elapsed
+pub fn update_global_config(
+    router: &mut StargazeApp,
+    infinity_global: &Addr,
+    config_update: ConfigUpdate<String>,
+) {
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::ProposeConfig {
+            config_update,
+        },
+    );
+    assert!(response.is_ok());
+
+    let timelock_seconds = router
+        .wrap()
+        .query_wasm_smart::<u64>(infinity_global, &InfinityGlobalQueryMsg::ConfigTimelock {})
+        .unwrap();
+    router.update_block(|block| block.time = block.time.plus_seconds(timelock_seconds));
+
+    let response = router.execute_contract(
+        Addr::unchecked("executor"),
+        infinity_global.clone(),
+        &InfinityGlobalExecuteMsg::ExecutePendingConfig {},
+        &[],
+    );
+    assert!(response.is_ok());
+}
@@ -1,5 +1,6 @@
 pub mod constants;
 // pub mod fixtures;
+pub mod global_functions;
 // pub mod marketplace_functions;
 pub mod nft_functions;
 pub mod pair_functions;
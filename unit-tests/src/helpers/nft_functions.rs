@@ -1,6 +1,7 @@
-use cosmwasm_std::{coins, Addr, Empty};
+use anyhow::Error;
+use cosmwasm_std::{coins, Addr, Binary, Empty};
 use cw721::{Cw721QueryMsg, OwnerOfResponse};
-use cw_multi_test::Executor;
+use cw_multi_test::{AppResponse, Executor};
 use sg721::ExecuteMsg as Sg721ExecuteMsg;
 use sg721_base::msg::CollectionInfoResponse;
 use sg_multi_test::StargazeApp;
@@ -82,6 +83,22 @@ pub fn transfer(
     assert!(response.is_ok());
 }
 
+pub fn send_nft(
+    router: &mut StargazeApp,
+    sender: &Addr,
+    collection: &Addr,
+    contract: &Addr,
+    token_id: &String,
+    msg: Binary,
+) -> Result<AppResponse, Error> {
+    let send_msg: Sg721ExecuteMsg<Empty, Empty> = Sg721ExecuteMsg::SendNft {
+        contract: contract.to_string(),
+        token_id: token_id.to_string(),
+        msg,
+    };
+    router.execute_contract(sender.clone(), collection.clone(), &send_msg, &[])
+}
+
 pub fn _burn(router: &mut StargazeApp, creator: &Addr, collection: &Addr, token_id: u32) {
     let transfer_msg: Sg721ExecuteMsg<Empty, Empty> = Sg721ExecuteMsg::Burn {
         token_id: token_id.to_string(),
@@ -7,13 +7,32 @@ use infinity_global::GlobalConfig;
 use infinity_pair::{
     msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg},
     pair::Pair,
-    state::{BondingCurve, PairConfig, PairImmutable, PairType},
+    state::{
+        BondingCurve, NftSelection, PairConfig, PairImmutable, PairLimits, PairSchedule,
+        PairTriggers, PairType, SweepPolicy,
+    },
 };
 use sg_multi_test::StargazeApp;
 use sg_std::NATIVE_DENOM;
 
 use crate::helpers::nft_functions::{approve_all, mint_to};
 
+/// An active pair config that pays the owner and has no limits, schedule, sweep policy or triggers,
+/// so that tests only set the fields they exercise
+pub fn pair_config(pair_type: PairType, bonding_curve: BondingCurve) -> PairConfig<String> {
+    PairConfig {
+        pair_type,
+        bonding_curve,
+        is_active: true,
+        asset_recipient: None,
+        nft_selection: NftSelection::Ordered,
+        limits: PairLimits::default(),
+        schedule: PairSchedule::default(),
+        sweep_policy: SweepPolicy::Disabled,
+        triggers: PairTriggers::default(),
+    }
+}
+
 pub fn create_pair(
     router: &mut StargazeApp,
     infinity_global: &Addr,
@@ -36,13 +55,14 @@ pub fn create_pair(
     };
 
     let pair_config = PairConfig {
-        pair_type: PairType::Token,
-        bonding_curve: BondingCurve::Linear {
-            spot_price: Uint128::from(10_000_000u128),
-            delta: Uint128::from(1_000_000u128),
-        },
         is_active: false,
-        asset_recipient: None,
+        ..pair_config(
+            PairType::Token,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        )
     };
 
     let response = router.execute_contract(
@@ -95,6 +115,11 @@ pub fn create_pair_with_deposits(
             pair_type: Some(pair_config.pair_type),
             bonding_curve: Some(pair_config.bonding_curve),
             asset_recipient: pair_config.asset_recipient,
+            nft_selection: Some(pair_config.nft_selection),
+            limits: Some(pair_config.limits),
+            schedule: Some(pair_config.schedule),
+            sweep_policy: Some(pair_config.sweep_policy),
+            triggers: Some(pair_config.triggers),
         },
         &[],
     );
@@ -141,3 +166,19 @@ pub fn create_pair_with_deposits(
         pair,
     }
 }
+
+pub fn query_pair(router: &StargazeApp, pair_address: &Addr) -> Pair {
+    router.wrap().query_wasm_smart::<Pair>(pair_address, &InfinityPairQueryMsg::Pair {}).unwrap()
+}
+
+pub fn refresh_pair(router: &mut StargazeApp, sender: &Addr, pair_address: &Addr) -> Pair {
+    let response = router.execute_contract(
+        sender.clone(),
+        pair_address.clone(),
+        &InfinityPairExecuteMsg::RefreshQuotes {},
+        &[],
+    );
+    assert!(response.is_ok());
+
+    query_pair(router, pair_address)
+}
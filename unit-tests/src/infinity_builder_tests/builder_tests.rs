@@ -0,0 +1,715 @@
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::setup_infinity_contracts::{
+    contract_infinity_factory, contract_infinity_global, contract_infinity_index,
+    contract_infinity_pair, contract_infinity_router,
+};
+use crate::setup::templates::{
+    increment_number_in_string, setup_infinity_test, standard_minter_template, InfinityTestSetup,
+};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coin, from_binary, instantiate2_address, Addr, Api, Binary, CanonicalAddr, ContractResult,
+    CosmosMsg, Decimal, DepsMut, Env, HexBinary, MessageInfo, OwnedDeps, RecoverPubkeyError,
+    StdResult, SystemError, SystemResult, VerificationError, WasmMsg, WasmQuery,
+};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use infinity_builder::{
+    generate_salt, CodeIds, DeployedContract, DeployedContractsResponse,
+    ExecuteMsg as InfinityBuilderExecuteMsg, InfinityContracts,
+    InstantiateMsg as InfinityBuilderInstantiateMsg, QueryMsg as InfinityBuilderQueryMsg, CODE_IDS,
+    INFINITY_CONTRACTS,
+};
+use infinity_factory::msg::{
+    ExecuteMsg as InfinityFactoryExecuteMsg, InstantiateMsg as InfinityFactoryInstantiateMsg,
+    QueryMsg as InfinityFactoryQueryMsg, UnrestrictedMigrationsResponse,
+};
+use infinity_factory::ContractError as InfinityFactoryContractError;
+use infinity_global::constants::DEFAULT_CONFIG_TIMELOCK_SECONDS;
+use infinity_global::msg::{
+    ExecuteMsg as InfinityGlobalExecuteMsg, InstantiateMsg as InfinityGlobalInstantiateMsg,
+    QueryMsg as InfinityGlobalQueryMsg,
+};
+use infinity_global::{ContractError as InfinityGlobalContractError, GlobalConfig, PendingConfig};
+use infinity_index::msg::InstantiateMsg as InfinityIndexInstantiateMsg;
+use infinity_router::msg::InstantiateMsg as InfinityRouterInstantiateMsg;
+use infinity_shared::InfinityError;
+use sg_multi_test::StargazeApp;
+use sg_std::{Response, StargazeMsgWrapper, NATIVE_DENOM};
+use std::marker::PhantomData;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+/// An api that humanizes canonical addresses of any length, so that the addresses predicted
+/// by the builder for instantiate2 can be humanized
+struct Instantiate2Api(MockApi);
+
+impl Api for Instantiate2Api {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        Ok(Addr::unchecked(human))
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        Ok(CanonicalAddr::from(human.as_bytes()))
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        Ok(Addr::unchecked(HexBinary::from(canonical.as_slice()).to_hex()))
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.0.secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.0.debug(message)
+    }
+}
+
+fn code_checksum(code_id: u64) -> HexBinary {
+    HexBinary::from_hex(&format!("{:064x}", code_id)).unwrap()
+}
+
+fn setup_instantiate2_deps() -> OwnedDeps<MockStorage, Instantiate2Api, MockQuerier> {
+    let mut querier = MockQuerier::new(&[]);
+    querier.update_wasm(|query| match query {
+        WasmQuery::CodeInfo {
+            code_id,
+        } => SystemResult::Ok(ContractResult::Ok(Binary::from(
+            format!(
+                r#"{{"code_id":{},"creator":"creator","checksum":"{}"}}"#,
+                code_id,
+                code_checksum(*code_id).to_hex()
+            )
+            .into_bytes(),
+        ))),
+        _ => SystemResult::Err(SystemError::UnsupportedRequest {
+            kind: "wasm".to_string(),
+        }),
+    });
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: Instantiate2Api(MockApi::default()),
+        querier,
+        custom_query_type: PhantomData,
+    }
+}
+
+fn builder_instantiate_msg(
+    code_ids: CodeIds,
+    admin: Option<String>,
+) -> InfinityBuilderInstantiateMsg {
+    InfinityBuilderInstantiateMsg {
+        fair_burn: "fair_burn".to_string(),
+        royalty_registry: "royalty_registry".to_string(),
+        marketplace: "marketplace".to_string(),
+        pair_creation_fee: coin(1_000_000u128, NATIVE_DENOM),
+        fair_burn_fee_percent: Decimal::percent(1u64),
+        default_royalty_fee_percent: Decimal::percent(5u64),
+        max_royalty_fee_percent: Decimal::percent(10u64),
+        max_swap_fee_percent: Decimal::percent(5u64),
+        max_referral_fee_percent: Decimal::percent(2u64),
+        code_ids,
+        min_prices: vec![coin(10u128, NATIVE_DENOM)],
+        admin,
+    }
+}
+
+#[test]
+fn try_infinity_builder_instantiate() {
+    let mut deps = setup_instantiate2_deps();
+    let env = mock_env();
+
+    let code_ids = CodeIds {
+        infinity_global: 1,
+        infinity_factory: 2,
+        infinity_index: 3,
+        infinity_pair: 4,
+        infinity_router: 5,
+    };
+    let response = infinity_builder::instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        builder_instantiate_msg(code_ids.clone(), None),
+    )
+    .unwrap();
+
+    let builder_canonical = deps.api.addr_canonicalize(env.contract.address.as_str()).unwrap();
+    let predict_address = |label: &str, code_id: u64| {
+        let canonical = instantiate2_address(
+            code_checksum(code_id).as_slice(),
+            &builder_canonical,
+            &generate_salt(label),
+        )
+        .unwrap();
+        deps.api.addr_humanize(&canonical).unwrap()
+    };
+
+    // The builder records the predicted addresses of the core contracts
+    let infinity_contracts = InfinityContracts {
+        infinity_global: predict_address("infinity_global", code_ids.infinity_global),
+        infinity_factory: predict_address("infinity_factory", code_ids.infinity_factory),
+        infinity_index: predict_address("infinity_index", code_ids.infinity_index),
+        infinity_router: predict_address("infinity_router", code_ids.infinity_router),
+    };
+    assert_eq!(INFINITY_CONTRACTS.load(&deps.storage).unwrap(), infinity_contracts);
+    assert_eq!(CODE_IDS.load(&deps.storage).unwrap(), code_ids);
+
+    // Each core contract is instantiated at its predicted address, administered by the builder
+    let instantiate_msgs = response
+        .messages
+        .iter()
+        .map(|sub_msg| match &sub_msg.msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+                admin,
+                code_id,
+                msg,
+                salt,
+                ..
+            }) => (admin.clone(), *code_id, msg.clone(), salt.clone()),
+            _ => panic!("expected an instantiate2 message"),
+        })
+        .collect::<Vec<_>>();
+    let expected_instantiations = [
+        ("infinity_global", code_ids.infinity_global),
+        ("infinity_factory", code_ids.infinity_factory),
+        ("infinity_index", code_ids.infinity_index),
+        ("infinity_router", code_ids.infinity_router),
+    ];
+    assert_eq!(instantiate_msgs.len(), expected_instantiations.len());
+    for ((admin, code_id, _, salt), (label, expected_code_id)) in
+        instantiate_msgs.iter().zip(expected_instantiations)
+    {
+        assert_eq!(admin, &Some(env.contract.address.to_string()));
+        assert_eq!(*code_id, expected_code_id);
+        assert_eq!(salt, &generate_salt(label));
+    }
+
+    // The global config references the other core contracts and the pair code id
+    let global_instantiate_msg =
+        from_binary::<InfinityGlobalInstantiateMsg>(&instantiate_msgs[0].2).unwrap();
+    let global_config = global_instantiate_msg.global_config;
+    assert_eq!(global_config.infinity_factory, infinity_contracts.infinity_factory.to_string());
+    assert_eq!(global_config.infinity_index, infinity_contracts.infinity_index.to_string());
+    assert_eq!(global_config.infinity_router, infinity_contracts.infinity_router.to_string());
+    assert_eq!(global_config.infinity_pair_code_id, code_ids.infinity_pair);
+    assert_eq!(global_instantiate_msg.min_prices, vec![coin(10u128, NATIVE_DENOM)]);
+
+    let factory_instantiate_msg =
+        from_binary::<InfinityFactoryInstantiateMsg>(&instantiate_msgs[1].2).unwrap();
+    assert_eq!(
+        factory_instantiate_msg.infinity_global,
+        infinity_contracts.infinity_global.to_string()
+    );
+
+    // When an admin is set, the admin administers the core contracts instead of the builder
+    let mut deps = setup_instantiate2_deps();
+    let response = infinity_builder::instantiate(
+        deps.as_mut(),
+        env,
+        mock_info("creator", &[]),
+        builder_instantiate_msg(code_ids, Some("admin".to_string())),
+    )
+    .unwrap();
+    for sub_msg in response.messages {
+        match sub_msg.msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+                admin,
+                ..
+            }) => assert_eq!(admin, Some("admin".to_string())),
+            _ => panic!("expected an instantiate2 message"),
+        }
+    }
+}
+
+/// Instantiates a mock builder recording core contracts deployed ahead of the builder,
+/// since the multi test app does not execute instantiate2 messages
+#[cw_serde]
+struct MockBuilderInstantiateMsg {
+    infinity_contracts: InfinityContracts,
+    code_ids: CodeIds,
+}
+
+fn mock_builder_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockBuilderInstantiateMsg,
+) -> StdResult<Response> {
+    cw2::set_contract_version(
+        deps.storage,
+        infinity_builder::CONTRACT_NAME,
+        infinity_builder::CONTRACT_VERSION,
+    )?;
+    INFINITY_CONTRACTS.save(deps.storage, &msg.infinity_contracts)?;
+    CODE_IDS.save(deps.storage, &msg.code_ids)?;
+    Ok(Response::new())
+}
+
+fn contract_mock_infinity_builder() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new(
+        infinity_builder::execute,
+        mock_builder_instantiate,
+        infinity_builder::query,
+    );
+    Box::new(contract)
+}
+
+/// Instantiates the global contract at a previous contract version, so that it can be migrated
+fn legacy_global_instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InfinityGlobalInstantiateMsg,
+) -> Result<Response, InfinityGlobalContractError> {
+    let response = infinity_global::instantiate::instantiate(deps.branch(), env, info, msg)?;
+    cw2::set_contract_version(deps.storage, infinity_global::constants::CONTRACT_NAME, "0.1.0")?;
+    Ok(response)
+}
+
+fn contract_legacy_infinity_global() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new(
+        infinity_global::execute::execute,
+        legacy_global_instantiate,
+        infinity_global::query::query,
+    )
+    .with_sudo(infinity_global::sudo::sudo);
+    Box::new(contract)
+}
+
+/// Instantiates the factory contract at a previous contract version, so that it can be migrated
+fn legacy_factory_instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InfinityFactoryInstantiateMsg,
+) -> StdResult<Response> {
+    let response = infinity_factory::instantiate::instantiate(deps.branch(), env, info, msg)?;
+    cw2::set_contract_version(deps.storage, infinity_factory::instantiate::CONTRACT_NAME, "0.1.0")?;
+    Ok(response)
+}
+
+fn contract_legacy_infinity_factory() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new(
+        infinity_factory::execute::execute,
+        legacy_factory_instantiate,
+        infinity_factory::query::query,
+    )
+    .with_sudo(infinity_factory::sudo::sudo)
+    .with_reply(infinity_factory::reply::reply);
+    Box::new(contract)
+}
+
+struct BuilderTestSetup {
+    router: StargazeApp,
+    creator: Addr,
+    infinity_builder: Addr,
+    infinity_contracts: InfinityContracts,
+    code_ids: CodeIds,
+}
+
+/// Deploys a suite of core contracts administered by a builder, alongside the contracts
+/// of the infinity test setup
+fn setup_builder_test() -> BuilderTestSetup {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        ..
+                    },
+                ..
+            },
+        infinity_global,
+        infinity_pair_code_id,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    // The suite shares the fair burn, royalty registry and marketplace of the test setup
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            &infinity_global,
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let code_ids = CodeIds {
+        infinity_global: router.store_code(contract_legacy_infinity_global()),
+        infinity_factory: router.store_code(contract_legacy_infinity_factory()),
+        infinity_index: router.store_code(contract_infinity_index()),
+        infinity_pair: infinity_pair_code_id,
+        infinity_router: router.store_code(contract_infinity_router()),
+    };
+
+    // The core contracts are administered by the builder, which is instantiated last
+    let infinity_contracts = InfinityContracts {
+        infinity_factory: Addr::unchecked(increment_number_in_string(infinity_global.as_ref(), 1)),
+        infinity_index: Addr::unchecked(increment_number_in_string(infinity_global.as_ref(), 2)),
+        infinity_router: Addr::unchecked(increment_number_in_string(infinity_global.as_ref(), 3)),
+        infinity_global: Addr::unchecked(increment_number_in_string(infinity_global.as_ref(), 4)),
+    };
+    let infinity_builder = Addr::unchecked(increment_number_in_string(infinity_global.as_ref(), 5));
+    let admin = Some(infinity_builder.to_string());
+
+    let infinity_factory = router
+        .instantiate_contract(
+            code_ids.infinity_factory,
+            creator.clone(),
+            &InfinityFactoryInstantiateMsg {
+                infinity_global: infinity_contracts.infinity_global.to_string(),
+            },
+            &[],
+            "Infinity Factory",
+            admin.clone(),
+        )
+        .unwrap();
+    assert_eq!(infinity_factory, infinity_contracts.infinity_factory);
+
+    let infinity_index = router
+        .instantiate_contract(
+            code_ids.infinity_index,
+            creator.clone(),
+            &InfinityIndexInstantiateMsg {
+                infinity_global: infinity_contracts.infinity_global.to_string(),
+            },
+            &[],
+            "Infinity Index",
+            admin.clone(),
+        )
+        .unwrap();
+    assert_eq!(infinity_index, infinity_contracts.infinity_index);
+
+    let infinity_router = router
+        .instantiate_contract(
+            code_ids.infinity_router,
+            creator.clone(),
+            &InfinityRouterInstantiateMsg {
+                infinity_global: infinity_contracts.infinity_global.to_string(),
+            },
+            &[],
+            "Infinity Router",
+            admin.clone(),
+        )
+        .unwrap();
+    assert_eq!(infinity_router, infinity_contracts.infinity_router);
+
+    let infinity_global = router
+        .instantiate_contract(
+            code_ids.infinity_global,
+            creator.clone(),
+            &InfinityGlobalInstantiateMsg {
+                global_config: GlobalConfig {
+                    fair_burn: global_config.fair_burn.to_string(),
+                    royalty_registry: global_config.royalty_registry.to_string(),
+                    marketplace: global_config.marketplace.to_string(),
+                    infinity_factory: infinity_contracts.infinity_factory.to_string(),
+                    infinity_index: infinity_contracts.infinity_index.to_string(),
+                    infinity_router: infinity_contracts.infinity_router.to_string(),
+                    infinity_pair_code_id: code_ids.infinity_pair,
+                    pair_creation_fee: global_config.pair_creation_fee,
+                    fair_burn_fee_percent: global_config.fair_burn_fee_percent,
+                    default_royalty_fee_percent: global_config.default_royalty_fee_percent,
+                    max_royalty_fee_percent: global_config.max_royalty_fee_percent,
+                    max_swap_fee_percent: global_config.max_swap_fee_percent,
+                    max_referral_fee_percent: global_config.max_referral_fee_percent,
+                    paused: false,
+                },
+                min_prices: vec![coin(10u128, NATIVE_DENOM)],
+            },
+            &[],
+            "Infinity Global",
+            admin,
+        )
+        .unwrap();
+    assert_eq!(infinity_global, infinity_contracts.infinity_global);
+
+    let builder_code_id = router.store_code(contract_mock_infinity_builder());
+    let builder = router
+        .instantiate_contract(
+            builder_code_id,
+            creator.clone(),
+            &MockBuilderInstantiateMsg {
+                infinity_contracts: infinity_contracts.clone(),
+                code_ids: code_ids.clone(),
+            },
+            &[],
+            "Infinity Builder",
+            Some(creator.to_string()),
+        )
+        .unwrap();
+    assert_eq!(builder, infinity_builder);
+
+    BuilderTestSetup {
+        router,
+        creator,
+        infinity_builder,
+        infinity_contracts,
+        code_ids,
+    }
+}
+
+fn query_deployed_contracts(
+    router: &StargazeApp,
+    infinity_builder: &Addr,
+) -> DeployedContractsResponse {
+    router
+        .wrap()
+        .query_wasm_smart::<DeployedContractsResponse>(
+            infinity_builder,
+            &InfinityBuilderQueryMsg::DeployedContracts {},
+        )
+        .unwrap()
+}
+
+#[test]
+fn try_infinity_builder_deployed_contracts() {
+    let BuilderTestSetup {
+        router,
+        infinity_builder,
+        infinity_contracts,
+        code_ids,
+        ..
+    } = setup_builder_test();
+
+    let deployed_contracts = query_deployed_contracts(&router, &infinity_builder);
+    assert_eq!(
+        deployed_contracts,
+        DeployedContractsResponse {
+            infinity_global: DeployedContract {
+                address: infinity_contracts.infinity_global,
+                code_id: code_ids.infinity_global,
+                contract_name: infinity_global::constants::CONTRACT_NAME.to_string(),
+                contract_version: "0.1.0".to_string(),
+            },
+            infinity_factory: DeployedContract {
+                address: infinity_contracts.infinity_factory,
+                code_id: code_ids.infinity_factory,
+                contract_name: infinity_factory::instantiate::CONTRACT_NAME.to_string(),
+                contract_version: "0.1.0".to_string(),
+            },
+            infinity_index: DeployedContract {
+                address: infinity_contracts.infinity_index,
+                code_id: code_ids.infinity_index,
+                contract_name: infinity_index::instantiate::CONTRACT_NAME.to_string(),
+                contract_version: infinity_index::instantiate::CONTRACT_VERSION.to_string(),
+            },
+            infinity_router: DeployedContract {
+                address: infinity_contracts.infinity_router,
+                code_id: code_ids.infinity_router,
+                contract_name: infinity_router::instantiate::CONTRACT_NAME.to_string(),
+                contract_version: infinity_router::instantiate::CONTRACT_VERSION.to_string(),
+            },
+            infinity_pair_code_id: code_ids.infinity_pair,
+        }
+    );
+}
+
+#[test]
+fn try_infinity_builder_migrate_suite() {
+    let BuilderTestSetup {
+        mut router,
+        creator,
+        infinity_builder,
+        infinity_contracts,
+        code_ids,
+    } = setup_builder_test();
+
+    let infinity_pair_code_id = router.store_code(contract_infinity_pair());
+
+    // Only the admin of the builder can migrate the suite
+    let response = router.execute_contract(
+        Addr::unchecked("not_admin"),
+        infinity_builder.clone(),
+        &InfinityBuilderExecuteMsg::MigrateSuite {
+            code_ids: code_ids.clone(),
+            pair_migrate_msg: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the admin of the builder".to_string())
+            .to_string(),
+    );
+
+    let next_code_ids = CodeIds {
+        infinity_global: router.store_code(contract_infinity_global()),
+        infinity_factory: router.store_code(contract_infinity_factory()),
+        infinity_pair: infinity_pair_code_id,
+        ..code_ids.clone()
+    };
+    let response = router.execute_contract(
+        creator.clone(),
+        infinity_builder.clone(),
+        &InfinityBuilderExecuteMsg::MigrateSuite {
+            code_ids: next_code_ids.clone(),
+            pair_migrate_msg: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // The global and factory contracts are migrated, the index and router are unchanged
+    let deployed_contracts = query_deployed_contracts(&router, &infinity_builder);
+    assert_eq!(deployed_contracts.infinity_global.code_id, next_code_ids.infinity_global);
+    assert_eq!(
+        deployed_contracts.infinity_global.contract_version,
+        infinity_global::constants::CONTRACT_VERSION
+    );
+    assert_eq!(deployed_contracts.infinity_factory.code_id, next_code_ids.infinity_factory);
+    assert_eq!(
+        deployed_contracts.infinity_factory.contract_version,
+        infinity_factory::instantiate::CONTRACT_VERSION
+    );
+    assert_eq!(deployed_contracts.infinity_index.code_id, code_ids.infinity_index);
+    assert_eq!(deployed_contracts.infinity_router.code_id, code_ids.infinity_router);
+
+    // The pair code id is proposed to the global config, and only reported once executed
+    assert_eq!(deployed_contracts.infinity_pair_code_id, code_ids.infinity_pair);
+    let pending_config = router
+        .wrap()
+        .query_wasm_smart::<Option<PendingConfig>>(
+            &infinity_contracts.infinity_global,
+            &InfinityGlobalQueryMsg::PendingConfig {},
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(pending_config.config_update.infinity_pair_code_id, Some(infinity_pair_code_id));
+
+    let unrestricted_migrations = router
+        .wrap()
+        .query_wasm_smart::<UnrestrictedMigrationsResponse>(
+            &infinity_contracts.infinity_factory,
+            &InfinityFactoryQueryMsg::UnrestrictedMigrations {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(unrestricted_migrations, vec![(code_ids.infinity_pair, infinity_pair_code_id)]);
+
+    // Pairs cannot be migrated to the proposed pair code id before the config is executed
+    let migrate_all_pairs_msg = InfinityFactoryExecuteMsg::MigrateAllPairs {
+        from_code_id: code_ids.infinity_pair,
+        limit: None,
+        start_after: None,
+    };
+    let response = router.execute_contract(
+        Addr::unchecked("executor"),
+        infinity_contracts.infinity_factory.clone(),
+        &migrate_all_pairs_msg,
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityFactoryContractError::InvalidMigration(
+            "Target code id is not the pair code id of the global config".to_string(),
+        )
+        .to_string(),
+    );
+
+    router.update_block(|block| {
+        block.time = block.time.plus_seconds(DEFAULT_CONFIG_TIMELOCK_SECONDS)
+    });
+    let response = router.execute_contract(
+        Addr::unchecked("executor"),
+        infinity_contracts.infinity_global.clone(),
+        &InfinityGlobalExecuteMsg::ExecutePendingConfig {},
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        Addr::unchecked("executor"),
+        infinity_contracts.infinity_factory.clone(),
+        &migrate_all_pairs_msg,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let deployed_contracts = query_deployed_contracts(&router, &infinity_builder);
+    assert_eq!(deployed_contracts.infinity_pair_code_id, infinity_pair_code_id);
+
+    // The pair code id can be updated without deploying new global and factory code
+    let next_infinity_pair_code_id = router.store_code(contract_infinity_pair());
+    let response = router.execute_contract(
+        creator,
+        infinity_builder.clone(),
+        &InfinityBuilderExecuteMsg::MigrateSuite {
+            code_ids: CodeIds {
+                infinity_pair: next_infinity_pair_code_id,
+                ..next_code_ids.clone()
+            },
+            pair_migrate_msg: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let deployed_contracts = query_deployed_contracts(&router, &infinity_builder);
+    assert_eq!(deployed_contracts.infinity_global.code_id, next_code_ids.infinity_global);
+    assert_eq!(deployed_contracts.infinity_factory.code_id, next_code_ids.infinity_factory);
+
+    let pending_config = router
+        .wrap()
+        .query_wasm_smart::<Option<PendingConfig>>(
+            &infinity_contracts.infinity_global,
+            &InfinityGlobalQueryMsg::PendingConfig {},
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        pending_config.config_update.infinity_pair_code_id,
+        Some(next_infinity_pair_code_id)
+    );
+
+    // The migration starts from the pair code id of the global config
+    let unrestricted_migrations = router
+        .wrap()
+        .query_wasm_smart::<UnrestrictedMigrationsResponse>(
+            &infinity_contracts.infinity_factory,
+            &InfinityFactoryQueryMsg::UnrestrictedMigrations {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert!(unrestricted_migrations.contains(&(infinity_pair_code_id, next_infinity_pair_code_id)));
+}
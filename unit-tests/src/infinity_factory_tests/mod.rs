@@ -1,4 +1,8 @@
 #[cfg(test)]
+mod pair_registry_tests;
+#[cfg(test)]
+mod refresh_quotes_tests;
+#[cfg(test)]
 mod sim_pair_quotes_factory_tests;
 #[cfg(test)]
 mod sudo_tests;
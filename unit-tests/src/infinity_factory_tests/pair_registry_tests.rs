@@ -0,0 +1,270 @@
+use crate::helpers::pair_functions::create_pair;
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::Addr;
+use cw_multi_test::{AppResponse, Executor};
+use infinity_factory::msg::{
+    ExecuteMsg as InfinityFactoryExecuteMsg, MigrationProgressResponse,
+    QueryMsg as InfinityFactoryQueryMsg, SudoMsg as InfinityFactorySudoMsg,
+};
+use infinity_factory::ContractError;
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_shared::InfinityError;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn find_attribute(response: &AppResponse, key: &str) -> String {
+    response
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-factory-migrate-all-pairs")
+        .unwrap()
+        .attributes
+        .iter()
+        .find(|attribute| attribute.key == key)
+        .unwrap()
+        .value
+        .clone()
+}
+
+#[test]
+fn try_migrate_all_pairs() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection = collection_response_vec[0].collection.clone().unwrap();
+
+    // Pairs created by the factory are added to the pair registry
+    let mut pair_addresses = vec![];
+    for _ in 0..3 {
+        let (pair_address, _) =
+            create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &owner);
+        pair_addresses.push(pair_address);
+    }
+
+    let pairs = router
+        .wrap()
+        .query_wasm_smart::<Vec<(u64, Addr)>>(
+            infinity_factory.clone(),
+            &InfinityFactoryQueryMsg::Pairs {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        pairs,
+        pair_addresses
+            .iter()
+            .enumerate()
+            .map(|(idx, pair)| (idx as u64, pair.clone()))
+            .collect::<Vec<_>>()
+    );
+
+    // Registered pairs are not registered twice
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &InfinityFactoryExecuteMsg::RegisterPairs {
+            pair_addresses: vec![pair_addresses[0].to_string()],
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &InfinityFactoryExecuteMsg::RegisterPairs {
+            pair_addresses: vec![infinity_global.to_string()],
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!(
+            "pair {} was not created by the factory",
+            infinity_global
+        ))
+        .to_string(),
+    );
+
+    let GlobalConfig {
+        infinity_pair_code_id,
+        ..
+    } = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &InfinityFactoryExecuteMsg::MigrateAllPairs {
+            from_code_id: infinity_pair_code_id,
+            limit: None,
+            start_after: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidMigration("No unrestricted migration for code id".to_string())
+            .to_string(),
+    );
+
+    // Pairs that do not use the starting code id are walked without being migrated
+    let from_code_id = infinity_pair_code_id + 100;
+    let response = router.wasm_sudo(
+        infinity_factory.clone(),
+        &InfinityFactorySudoMsg::AddUnrestrictedMigration {
+            starting_code_id: from_code_id,
+            target_code_id: infinity_pair_code_id,
+            migrate_msg: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    let response = router
+        .execute_contract(
+            owner.clone(),
+            infinity_factory.clone(),
+            &InfinityFactoryExecuteMsg::MigrateAllPairs {
+                from_code_id,
+                limit: Some(2),
+                start_after: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(find_attribute(&response, "num_migrated"), "0");
+    assert_eq!(find_attribute(&response, "num_remaining"), "1");
+    assert_eq!(find_attribute(&response, "last_pair_id"), "1");
+
+    let migration_progress = router
+        .wrap()
+        .query_wasm_smart::<MigrationProgressResponse>(
+            infinity_factory.clone(),
+            &InfinityFactoryQueryMsg::MigrationProgress {
+                from_code_id,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        migration_progress,
+        MigrationProgressResponse {
+            from_code_id,
+            target_code_id: Some(infinity_pair_code_id),
+            last_pair_id: Some(1),
+            num_migrated: 0,
+            num_remaining: 1,
+        }
+    );
+
+    // The next batch resumes where the previous batch stopped
+    let response = router
+        .execute_contract(
+            owner.clone(),
+            infinity_factory.clone(),
+            &InfinityFactoryExecuteMsg::MigrateAllPairs {
+                from_code_id,
+                limit: Some(2),
+                start_after: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(find_attribute(&response, "num_remaining"), "0");
+    assert_eq!(find_attribute(&response, "last_pair_id"), "2");
+
+    let migration_progress = router
+        .wrap()
+        .query_wasm_smart::<MigrationProgressResponse>(
+            infinity_factory.clone(),
+            &InfinityFactoryQueryMsg::MigrationProgress {
+                from_code_id,
+            },
+        )
+        .unwrap();
+    assert_eq!(migration_progress.last_pair_id, Some(2));
+    assert_eq!(migration_progress.num_remaining, 0);
+
+    // A batch can start after a given pair id, overriding the stored progress
+    let response = router
+        .execute_contract(
+            owner.clone(),
+            infinity_factory.clone(),
+            &InfinityFactoryExecuteMsg::MigrateAllPairs {
+                from_code_id,
+                limit: Some(1),
+                start_after: Some(0),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(find_attribute(&response, "num_remaining"), "1");
+    assert_eq!(find_attribute(&response, "last_pair_id"), "1");
+
+    // Replacing the migration resets the progress, so a second pass walks every pair again
+    let response = router.wasm_sudo(
+        infinity_factory.clone(),
+        &InfinityFactorySudoMsg::RemoveUnrestrictedMigration {
+            starting_code_id: from_code_id,
+        },
+    );
+    assert!(response.is_ok());
+
+    let response = router.wasm_sudo(
+        infinity_factory.clone(),
+        &InfinityFactorySudoMsg::AddUnrestrictedMigration {
+            starting_code_id: from_code_id,
+            target_code_id: infinity_pair_code_id,
+            migrate_msg: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    let migration_progress = router
+        .wrap()
+        .query_wasm_smart::<MigrationProgressResponse>(
+            infinity_factory.clone(),
+            &InfinityFactoryQueryMsg::MigrationProgress {
+                from_code_id,
+            },
+        )
+        .unwrap();
+    assert_eq!(migration_progress.last_pair_id, None);
+    assert_eq!(migration_progress.num_remaining, 3);
+
+    let response = router
+        .execute_contract(
+            owner,
+            infinity_factory,
+            &InfinityFactoryExecuteMsg::MigrateAllPairs {
+                from_code_id,
+                limit: None,
+                start_after: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(find_attribute(&response, "num_remaining"), "0");
+    assert_eq!(find_attribute(&response, "last_pair_id"), "2");
+}
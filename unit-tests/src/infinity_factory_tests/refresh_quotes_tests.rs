@@ -0,0 +1,132 @@
+use crate::helpers::global_functions::update_global_config;
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use infinity_factory::msg::ExecuteMsg as InfinityFactoryExecuteMsg;
+use infinity_global::ConfigUpdate;
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairType};
+use infinity_shared::InfinityError;
+use sg_multi_test::StargazeApp;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn query_fair_burn_amount(router: &StargazeApp, pair_address: &Addr) -> Uint128 {
+    router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_address, &InfinityPairQueryMsg::Pair {})
+        .unwrap()
+        .internal
+        .sell_to_pair_quote_summary
+        .unwrap()
+        .fair_burn
+        .amount
+}
+
+#[test]
+fn try_refresh_pair_quotes() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let mut pair_addresses = vec![];
+    for _ in 0..2 {
+        let test_pair = create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            pair_config(
+                PairType::Token,
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            ),
+            0u64,
+            Uint128::from(100_000_000u128),
+        );
+        assert_eq!(
+            test_pair.pair.internal.sell_to_pair_quote_summary.unwrap().fair_burn.amount,
+            Uint128::from(100_000u128)
+        );
+        pair_addresses.push(test_pair.address);
+    }
+
+    update_global_config(
+        &mut router,
+        &infinity_global,
+        ConfigUpdate {
+            fair_burn_fee_percent: Some(Decimal::percent(2)),
+            ..ConfigUpdate::default()
+        },
+    );
+
+    // Quotes are stale until the pair is refreshed
+    assert_eq!(query_fair_burn_amount(&router, &pair_addresses[0]), Uint128::from(100_000u128));
+
+    // Anyone can refresh the quotes of a single pair
+    let response = router.execute_contract(
+        bidder.clone(),
+        pair_addresses[0].clone(),
+        &InfinityPairExecuteMsg::RefreshQuotes {},
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_eq!(query_fair_burn_amount(&router, &pair_addresses[0]), Uint128::from(200_000u128));
+    assert_eq!(query_fair_burn_amount(&router, &pair_addresses[1]), Uint128::from(100_000u128));
+
+    // Cannot refresh contracts that were not created by the factory
+    let response = router.execute_contract(
+        bidder.clone(),
+        infinity_factory.clone(),
+        &InfinityFactoryExecuteMsg::RefreshPairQuotes {
+            pair_addresses: vec![collection.to_string()],
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!("pair {} was not created by the factory", collection))
+            .to_string(),
+    );
+
+    // Anyone can refresh the quotes of many pairs through the factory
+    let response = router.execute_contract(
+        bidder,
+        infinity_factory,
+        &InfinityFactoryExecuteMsg::RefreshPairQuotes {
+            pair_addresses: pair_addresses.iter().map(|p| p.to_string()).collect(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    for pair_address in &pair_addresses {
+        assert_eq!(query_fair_burn_amount(&router, pair_address), Uint128::from(200_000u128));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
 use crate::setup::setup_accounts::MarketAccounts;
 use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
 
@@ -49,14 +49,15 @@ fn try_sim_sell_to_pair_quotes() {
         &creator,
         &owner,
         PairConfig {
-            pair_type: PairType::Trade {
-                swap_fee_percent: Decimal::percent(2),
-                reinvest_nfts: true,
-                reinvest_tokens: true,
-            },
-            bonding_curve: BondingCurve::ConstantProduct,
             is_active: false,
-            asset_recipient: None,
+            ..pair_config(
+                PairType::Trade {
+                    swap_fee_percent: Decimal::percent(2),
+                    reinvest_nfts: true,
+                    reinvest_tokens: true,
+                },
+                BondingCurve::ConstantProduct,
+            )
         },
         20u64,
         Uint128::from(100_000_000u128),
@@ -126,14 +127,15 @@ fn try_sim_buy_from_pair_quotes() {
         &creator,
         &owner,
         PairConfig {
-            pair_type: PairType::Trade {
-                swap_fee_percent: Decimal::percent(2),
-                reinvest_nfts: true,
-                reinvest_tokens: true,
-            },
-            bonding_curve: BondingCurve::ConstantProduct,
             is_active: false,
-            asset_recipient: None,
+            ..pair_config(
+                PairType::Trade {
+                    swap_fee_percent: Decimal::percent(2),
+                    reinvest_nfts: true,
+                    reinvest_tokens: true,
+                },
+                BondingCurve::ConstantProduct,
+            )
         },
         20u64,
         Uint128::from(100_000_000u128),
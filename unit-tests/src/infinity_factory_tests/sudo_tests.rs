@@ -2,6 +2,7 @@ use crate::setup::templates::{setup_infinity_test, standard_minter_template, Inf
 
 use infinity_factory::msg::SudoMsg as InfinityFactorySudoMsg;
 use infinity_factory::msg::{QueryMsg as InfinityFactoryQueryMsg, UnrestrictedMigrationsResponse};
+use infinity_pair::msg::{MigrateMsg as PairMigrateMsg, StateTransform};
 use test_suite::common_setup::msg::MinterTemplateResponse;
 
 #[test]
@@ -19,6 +20,7 @@ fn try_unrestricted_migrations() {
     let add_unrestricted_migration_msg = InfinityFactorySudoMsg::AddUnrestrictedMigration {
         starting_code_id: 0,
         target_code_id: 1,
+        migrate_msg: None,
     };
     let response = router.wasm_sudo(infinity_factory.clone(), &add_unrestricted_migration_msg);
     assert!(response.is_ok());
@@ -36,9 +38,16 @@ fn try_unrestricted_migrations() {
     assert_eq!(unrestricted_migrations.len(), 1);
     assert_eq!(unrestricted_migrations[0], (0, 1));
 
+    let migrate_msg = PairMigrateMsg {
+        state_transforms: vec![
+            StateTransform::ConvertNftDeposits,
+            StateTransform::ConvertPairInternal,
+        ],
+    };
     let add_unrestricted_migration_msg = InfinityFactorySudoMsg::AddUnrestrictedMigration {
         starting_code_id: 1,
         target_code_id: 3,
+        migrate_msg: Some(migrate_msg.clone()),
     };
     let response = router.wasm_sudo(infinity_factory.clone(), &add_unrestricted_migration_msg);
     assert!(response.is_ok());
@@ -56,6 +65,17 @@ fn try_unrestricted_migrations() {
     assert_eq!(unrestricted_migrations.len(), 2);
     assert_eq!(unrestricted_migrations, [(0, 1), (1, 3)]);
 
+    let pair_migrate_msg = router
+        .wrap()
+        .query_wasm_smart::<Option<PairMigrateMsg>>(
+            infinity_factory.clone(),
+            &InfinityFactoryQueryMsg::PairMigrateMsg {
+                starting_code_id: 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(pair_migrate_msg, Some(migrate_msg));
+
     let remove_unrestricted_migration_msg = InfinityFactorySudoMsg::RemoveUnrestrictedMigration {
         starting_code_id: 1,
     };
@@ -74,4 +94,15 @@ fn try_unrestricted_migrations() {
 
     assert_eq!(unrestricted_migrations.len(), 1);
     assert_eq!(unrestricted_migrations[0], (0, 1));
+
+    let pair_migrate_msg = router
+        .wrap()
+        .query_wasm_smart::<Option<PairMigrateMsg>>(
+            infinity_factory,
+            &InfinityFactoryQueryMsg::PairMigrateMsg {
+                starting_code_id: 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(pair_migrate_msg, None);
 }
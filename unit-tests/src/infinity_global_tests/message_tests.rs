@@ -1,12 +1,16 @@
+use crate::helpers::utils::assert_error;
 use crate::setup::setup_infinity_contracts::{contract_infinity_global, UOSMO};
 
 use cosmwasm_std::{coin, Addr, Coin, Decimal};
 use cw_multi_test::Executor;
 use infinity_global::{
-    msg::{InstantiateMsg, QueryMsg, SudoMsg},
-    GlobalConfig,
+    constants::DEFAULT_CONFIG_TIMELOCK_SECONDS,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg},
+    ConfigUpdate, ConfigVersion, GlobalConfig, PendingConfig,
 };
-use sg_multi_test::mock_deps;
+use infinity_shared::InfinityError;
+use sg_index_query::{QueryBound, QueryOptions};
+use sg_multi_test::{mock_deps, StargazeApp};
 use sg_std::NATIVE_DENOM;
 use test_suite::common_setup::contract_boxes::custom_mock_app;
 
@@ -37,6 +41,8 @@ fn try_infinity_global_init() {
         default_royalty_fee_percent: Decimal::percent(10u64),
         max_royalty_fee_percent: Decimal::percent(15u64),
         max_swap_fee_percent: Decimal::percent(10u64),
+        max_referral_fee_percent: Decimal::percent(1u64),
+        paused: false,
     };
 
     let min_prices = vec![coin(1_000_000u128, NATIVE_DENOM)];
@@ -103,6 +109,8 @@ fn try_infinity_global_update_config() {
         default_royalty_fee_percent: Decimal::percent(10u64),
         max_royalty_fee_percent: Decimal::percent(15u64),
         max_swap_fee_percent: Decimal::percent(10u64),
+        max_referral_fee_percent: Decimal::percent(1u64),
+        paused: false,
     };
 
     let min_prices = vec![coin(1_000_000u128, NATIVE_DENOM)];
@@ -122,29 +130,88 @@ fn try_infinity_global_update_config() {
     assert!(response.is_ok());
     let infinity_global = response.unwrap();
 
-    let update_config_msg = SudoMsg::UpdateConfig {
-        fair_burn: Some("fair_burn_new".to_string()),
-        royalty_registry: Some("royalty_registry_new".to_string()),
-        marketplace: Some("marketplace_new".to_string()),
-        infinity_factory: Some("infinity_factory_new".to_string()),
-        infinity_index: Some("infinity_index_new".to_string()),
-        infinity_router: Some("infinity_router_new".to_string()),
+    // Updated addresses must belong to contracts
+    let new_contracts = (0..6)
+        .map(|_| instantiate_infinity_global(&mut router, vec![]).to_string())
+        .collect::<Vec<_>>();
+    let config_update = ConfigUpdate {
+        fair_burn: Some(new_contracts[0].clone()),
+        royalty_registry: Some(new_contracts[1].clone()),
+        marketplace: Some(new_contracts[2].clone()),
+        infinity_factory: Some(new_contracts[3].clone()),
+        infinity_index: Some(new_contracts[4].clone()),
+        infinity_router: Some(new_contracts[5].clone()),
         infinity_pair_code_id: Some(2u64),
         pair_creation_fee: Some(coin(2_000_000u128, NATIVE_DENOM)),
         fair_burn_fee_percent: Some(Decimal::percent(2u64)),
         default_royalty_fee_percent: Some(Decimal::percent(1u64)),
         max_royalty_fee_percent: Some(Decimal::percent(20u64)),
         max_swap_fee_percent: Some(Decimal::percent(20u64)),
+        max_referral_fee_percent: Some(Decimal::percent(3u64)),
+        config_timelock_seconds: Some(100u64),
     };
-    let response = router.wasm_sudo(infinity_global.clone(), &update_config_msg);
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::ProposeConfig {
+            config_update: config_update.clone(),
+        },
+    );
     assert!(response.is_ok());
 
+    // The proposed config is pending until the timelock has elapsed
+    let pending_config = router
+        .wrap()
+        .query_wasm_smart::<Option<PendingConfig>>(
+            infinity_global.clone(),
+            &QueryMsg::PendingConfig {},
+        )
+        .unwrap()
+        .unwrap();
+    let deps = mock_deps();
+    assert_eq!(pending_config.config_update, config_update.clone().str_to_addr(&deps.api).unwrap());
+    assert_eq!(
+        pending_config.executable_at,
+        router.block_info().time.plus_seconds(DEFAULT_CONFIG_TIMELOCK_SECONDS)
+    );
+
+    let executor = Addr::unchecked("executor");
+    let response = router.execute_contract(
+        executor.clone(),
+        infinity_global.clone(),
+        &ExecuteMsg::ExecutePendingConfig {},
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("pending config timelock has not elapsed".to_string())
+            .to_string(),
+    );
+
     let global_config_response = router
         .wrap()
-        .query_wasm_smart::<GlobalConfig<Addr>>(infinity_global, &QueryMsg::GlobalConfig {})
+        .query_wasm_smart::<GlobalConfig<Addr>>(infinity_global.clone(), &QueryMsg::GlobalConfig {})
+        .unwrap();
+    assert_eq!(global_config_response.fair_burn, fair_burn);
+    assert_eq!(query_config_timelock(&router, &infinity_global), DEFAULT_CONFIG_TIMELOCK_SECONDS);
+
+    // Anyone can execute the pending config once the timelock has elapsed
+    router.update_block(|block| {
+        block.time = block.time.plus_seconds(DEFAULT_CONFIG_TIMELOCK_SECONDS)
+    });
+    let response = router.execute_contract(
+        executor.clone(),
+        infinity_global.clone(),
+        &ExecuteMsg::ExecutePendingConfig {},
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let global_config_response = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(infinity_global.clone(), &QueryMsg::GlobalConfig {})
         .unwrap();
 
-    if let SudoMsg::UpdateConfig {
+    let ConfigUpdate {
         fair_burn,
         royalty_registry,
         marketplace,
@@ -157,27 +224,111 @@ fn try_infinity_global_update_config() {
         default_royalty_fee_percent,
         max_royalty_fee_percent,
         max_swap_fee_percent,
-    } = update_config_msg
-    {
-        assert_eq!(fair_burn.unwrap(), global_config_response.fair_burn);
-        assert_eq!(royalty_registry.unwrap(), global_config_response.royalty_registry);
-        assert_eq!(marketplace.unwrap(), global_config_response.marketplace);
-        assert_eq!(infinity_factory.unwrap(), global_config_response.infinity_factory);
-        assert_eq!(infinity_index.unwrap(), global_config_response.infinity_index);
-        assert_eq!(infinity_router.unwrap(), global_config_response.infinity_router);
-        assert_eq!(infinity_pair_code_id.unwrap(), global_config_response.infinity_pair_code_id);
-        assert_eq!(pair_creation_fee.unwrap(), global_config_response.pair_creation_fee);
-        assert_eq!(fair_burn_fee_percent.unwrap(), global_config_response.fair_burn_fee_percent);
-        assert_eq!(
-            default_royalty_fee_percent.unwrap(),
-            global_config_response.default_royalty_fee_percent
-        );
-        assert_eq!(
-            max_royalty_fee_percent.unwrap(),
-            global_config_response.max_royalty_fee_percent
-        );
-        assert_eq!(max_swap_fee_percent.unwrap(), global_config_response.max_swap_fee_percent);
-    }
+        max_referral_fee_percent,
+        config_timelock_seconds,
+    } = config_update;
+    assert_eq!(fair_burn.unwrap(), global_config_response.fair_burn);
+    assert_eq!(royalty_registry.unwrap(), global_config_response.royalty_registry);
+    assert_eq!(marketplace.unwrap(), global_config_response.marketplace);
+    assert_eq!(infinity_factory.unwrap(), global_config_response.infinity_factory);
+    assert_eq!(infinity_index.unwrap(), global_config_response.infinity_index);
+    assert_eq!(infinity_router.unwrap(), global_config_response.infinity_router);
+    assert_eq!(infinity_pair_code_id.unwrap(), global_config_response.infinity_pair_code_id);
+    assert_eq!(pair_creation_fee.unwrap(), global_config_response.pair_creation_fee);
+    assert_eq!(fair_burn_fee_percent.unwrap(), global_config_response.fair_burn_fee_percent);
+    assert_eq!(
+        default_royalty_fee_percent.unwrap(),
+        global_config_response.default_royalty_fee_percent
+    );
+    assert_eq!(max_royalty_fee_percent.unwrap(), global_config_response.max_royalty_fee_percent);
+    assert_eq!(max_swap_fee_percent.unwrap(), global_config_response.max_swap_fee_percent);
+    assert_eq!(max_referral_fee_percent.unwrap(), global_config_response.max_referral_fee_percent);
+    assert_eq!(config_timelock_seconds.unwrap(), query_config_timelock(&router, &infinity_global));
+
+    // The pending config can only be executed once
+    let response = router.execute_contract(
+        executor,
+        infinity_global,
+        &ExecuteMsg::ExecutePendingConfig {},
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("no pending config to execute".to_string()).to_string(),
+    );
+}
+
+#[test]
+fn try_infinity_global_cancel_pending_config_and_pause() {
+    let mut router = custom_mock_app();
+    let infinity_global = instantiate_infinity_global(&mut router, vec![]);
+
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::ProposeConfig {
+            config_update: ConfigUpdate {
+                max_swap_fee_percent: Some(Decimal::percent(50u64)),
+                ..ConfigUpdate::default()
+            },
+        },
+    );
+    assert!(response.is_ok());
+
+    let pending_config = router
+        .wrap()
+        .query_wasm_smart::<Option<PendingConfig>>(
+            infinity_global.clone(),
+            &QueryMsg::PendingConfig {},
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        pending_config.executable_at,
+        router.block_info().time.plus_seconds(DEFAULT_CONFIG_TIMELOCK_SECONDS)
+    );
+
+    // Sudo can cancel the pending config
+    let response = router.wasm_sudo(infinity_global.clone(), &SudoMsg::CancelPendingConfig {});
+    assert!(response.is_ok());
+
+    let pending_config = router
+        .wrap()
+        .query_wasm_smart::<Option<PendingConfig>>(
+            infinity_global.clone(),
+            &QueryMsg::PendingConfig {},
+        )
+        .unwrap();
+    assert_eq!(pending_config, None);
+
+    router.update_block(|block| {
+        block.time = block.time.plus_seconds(DEFAULT_CONFIG_TIMELOCK_SECONDS)
+    });
+    let response = router.execute_contract(
+        Addr::unchecked("executor"),
+        infinity_global.clone(),
+        &ExecuteMsg::ExecutePendingConfig {},
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("no pending config to execute".to_string()).to_string(),
+    );
+
+    // Pauses take effect immediately
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::SetPaused {
+            paused: true,
+        },
+    );
+    assert!(response.is_ok());
+
+    let global_config_response = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(infinity_global, &QueryMsg::GlobalConfig {})
+        .unwrap();
+    assert!(global_config_response.paused);
+    assert_eq!(global_config_response.max_swap_fee_percent, Decimal::percent(10u64));
 }
 
 #[test]
@@ -207,6 +358,8 @@ fn try_infinity_global_add_remove_min_prices() {
         default_royalty_fee_percent: Decimal::percent(10u64),
         max_royalty_fee_percent: Decimal::percent(15u64),
         max_swap_fee_percent: Decimal::percent(10u64),
+        max_referral_fee_percent: Decimal::percent(1u64),
+        paused: false,
     };
 
     let min_prices = vec![coin(1_000_000u128, NATIVE_DENOM)];
@@ -263,3 +416,291 @@ fn try_infinity_global_add_remove_min_prices() {
         .unwrap();
     assert_eq!(None, min_price_response);
 }
+
+fn instantiate_infinity_global(router: &mut StargazeApp, min_prices: Vec<Coin>) -> Addr {
+    let infinity_global_code_id = router.store_code(contract_infinity_global());
+
+    let global_config = GlobalConfig {
+        fair_burn: "fair_burn".to_string(),
+        royalty_registry: "royalty_registry".to_string(),
+        marketplace: "marketplace".to_string(),
+        infinity_factory: "infinity_factory".to_string(),
+        infinity_index: "infinity_index".to_string(),
+        infinity_router: "infinity_router".to_string(),
+        infinity_pair_code_id: 1u64,
+        pair_creation_fee: coin(1_000_000u128, NATIVE_DENOM),
+        fair_burn_fee_percent: Decimal::percent(1u64),
+        default_royalty_fee_percent: Decimal::percent(10u64),
+        max_royalty_fee_percent: Decimal::percent(15u64),
+        max_swap_fee_percent: Decimal::percent(10u64),
+        max_referral_fee_percent: Decimal::percent(1u64),
+        paused: false,
+    };
+
+    router
+        .instantiate_contract(
+            infinity_global_code_id,
+            Addr::unchecked("creator"),
+            &InstantiateMsg {
+                global_config,
+                min_prices,
+            },
+            &[],
+            "Infinity Global",
+            None,
+        )
+        .unwrap()
+}
+
+fn query_config_timelock(router: &StargazeApp, infinity_global: &Addr) -> u64 {
+    router.wrap().query_wasm_smart::<u64>(infinity_global, &QueryMsg::ConfigTimelock {}).unwrap()
+}
+
+/// Proposes and executes a config timelock update, waiting out the current timelock
+fn update_config_timelock(
+    router: &mut StargazeApp,
+    infinity_global: &Addr,
+    config_timelock_seconds: u64,
+) {
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::ProposeConfig {
+            config_update: ConfigUpdate {
+                config_timelock_seconds: Some(config_timelock_seconds),
+                ..ConfigUpdate::default()
+            },
+        },
+    );
+    assert!(response.is_ok());
+
+    let current_timelock_seconds = query_config_timelock(router, infinity_global);
+    router.update_block(|block| block.time = block.time.plus_seconds(current_timelock_seconds));
+
+    let response = router.execute_contract(
+        Addr::unchecked("executor"),
+        infinity_global.clone(),
+        &ExecuteMsg::ExecutePendingConfig {},
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_eq!(query_config_timelock(router, infinity_global), config_timelock_seconds);
+}
+
+fn update_fee_percents(
+    router: &mut StargazeApp,
+    infinity_global: &Addr,
+    fair_burn_fee_percent: Option<Decimal>,
+    max_swap_fee_percent: Option<Decimal>,
+) {
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::ProposeConfig {
+            config_update: ConfigUpdate {
+                fair_burn_fee_percent,
+                max_swap_fee_percent,
+                ..ConfigUpdate::default()
+            },
+        },
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        Addr::unchecked("executor"),
+        infinity_global.clone(),
+        &ExecuteMsg::ExecutePendingConfig {},
+        &[],
+    );
+    assert!(response.is_ok());
+}
+
+#[test]
+fn try_infinity_global_min_prices_queries() {
+    let mut router = custom_mock_app();
+    let min_prices = vec![coin(4_000_000u128, UOSMO), coin(1_000_000u128, NATIVE_DENOM)];
+    let infinity_global = instantiate_infinity_global(&mut router, min_prices.clone());
+
+    let min_prices_response = router
+        .wrap()
+        .query_wasm_smart::<Vec<Coin>>(
+            infinity_global.clone(),
+            &QueryMsg::MinPrices {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(min_prices_response, min_prices);
+
+    // Denoms are paginated in order
+    let supported_denoms_response = router
+        .wrap()
+        .query_wasm_smart::<Vec<String>>(
+            infinity_global.clone(),
+            &QueryMsg::SupportedDenoms {
+                query_options: Some(QueryOptions {
+                    limit: Some(1),
+                    descending: None,
+                    min: Some(QueryBound::Exclusive(UOSMO.to_string())),
+                    max: None,
+                }),
+            },
+        )
+        .unwrap();
+    assert_eq!(supported_denoms_response, vec![NATIVE_DENOM.to_string()]);
+
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::RemoveMinPrices {
+            denoms: vec![UOSMO.to_string()],
+        },
+    );
+    assert!(response.is_ok());
+
+    let supported_denoms_response = router
+        .wrap()
+        .query_wasm_smart::<Vec<String>>(
+            infinity_global,
+            &QueryMsg::SupportedDenoms {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(supported_denoms_response, vec![NATIVE_DENOM.to_string()]);
+}
+
+#[test]
+fn try_infinity_global_config_history() {
+    let mut router = custom_mock_app();
+    let infinity_global = instantiate_infinity_global(&mut router, vec![]);
+    let instantiate_height = router.block_info().height;
+
+    // Config updates are executed as soon as they are proposed, once the timelock is removed
+    update_config_timelock(&mut router, &infinity_global, 0u64);
+
+    router.update_block(|block| block.height += 10);
+    update_fee_percents(&mut router, &infinity_global, Some(Decimal::percent(2u64)), None);
+
+    // Updates within the same block are merged into one version
+    router.update_block(|block| block.height += 10);
+    for max_swap_fee_percent in [Decimal::percent(8u64), Decimal::percent(6u64)] {
+        update_fee_percents(
+            &mut router,
+            &infinity_global,
+            Some(Decimal::percent(3u64)),
+            Some(max_swap_fee_percent),
+        );
+    }
+
+    let config_history = router
+        .wrap()
+        .query_wasm_smart::<Vec<ConfigVersion>>(
+            infinity_global.clone(),
+            &QueryMsg::ConfigHistory {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(config_history.len(), 3);
+    assert_eq!(
+        config_history
+            .iter()
+            .map(|config_version| (config_version.version, config_version.height))
+            .collect::<Vec<_>>(),
+        vec![
+            (1u64, instantiate_height),
+            (2u64, instantiate_height + 10),
+            (3u64, instantiate_height + 20)
+        ]
+    );
+    assert!(config_history[0].changed_fields.is_empty());
+    assert_eq!(config_history[1].changed_fields, vec!["fair_burn_fee_percent".to_string()]);
+    assert_eq!(
+        config_history[2].changed_fields,
+        vec!["fair_burn_fee_percent".to_string(), "max_swap_fee_percent".to_string()]
+    );
+    assert_eq!(config_history[2].global_config.max_swap_fee_percent, Decimal::percent(6u64));
+
+    // The config at a height is the latest version set at or before that height
+    let config_at_height = |height: u64| {
+        router
+            .wrap()
+            .query_wasm_smart::<Option<ConfigVersion>>(
+                infinity_global.clone(),
+                &QueryMsg::ConfigAtHeight {
+                    height,
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(config_at_height(instantiate_height - 1), None);
+    assert_eq!(config_at_height(instantiate_height + 15), Some(config_history[1].clone()));
+    assert_eq!(
+        config_at_height(instantiate_height + 15).unwrap().global_config.fair_burn_fee_percent,
+        Decimal::percent(2u64)
+    );
+    assert_eq!(config_at_height(instantiate_height + 100), Some(config_history[2].clone()));
+}
+
+#[test]
+fn try_infinity_global_min_prices_config_history() {
+    let mut router = custom_mock_app();
+    let infinity_global =
+        instantiate_infinity_global(&mut router, vec![coin(1_000_000u128, NATIVE_DENOM)]);
+    let instantiate_height = router.block_info().height;
+
+    router.update_block(|block| block.height += 10);
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::AddMinPrices {
+            min_prices: vec![coin(2_000_000u128, UOSMO)],
+        },
+    );
+    assert!(response.is_ok());
+
+    router.update_block(|block| block.height += 10);
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::RemoveMinPrices {
+            denoms: vec![NATIVE_DENOM.to_string()],
+        },
+    );
+    assert!(response.is_ok());
+
+    // Min price changes are recorded in the config history
+    let config_history = router
+        .wrap()
+        .query_wasm_smart::<Vec<ConfigVersion>>(
+            infinity_global.clone(),
+            &QueryMsg::ConfigHistory {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        config_history
+            .iter()
+            .map(|config_version| (config_version.height, config_version.min_prices.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (instantiate_height, vec![coin(1_000_000u128, NATIVE_DENOM)]),
+            (
+                instantiate_height + 10,
+                vec![coin(2_000_000u128, UOSMO), coin(1_000_000u128, NATIVE_DENOM)]
+            ),
+            (instantiate_height + 20, vec![coin(2_000_000u128, UOSMO)]),
+        ]
+    );
+    assert!(config_history[0].changed_fields.is_empty());
+    assert_eq!(config_history[1].changed_fields, vec!["min_prices".to_string()]);
+    assert_eq!(config_history[2].changed_fields, vec!["min_prices".to_string()]);
+
+    let config_at_height = router
+        .wrap()
+        .query_wasm_smart::<Option<ConfigVersion>>(
+            infinity_global,
+            &QueryMsg::ConfigAtHeight {
+                height: instantiate_height + 15,
+            },
+        )
+        .unwrap();
+    assert_eq!(config_at_height, Some(config_history[1].clone()));
+}
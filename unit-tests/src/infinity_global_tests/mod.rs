@@ -1,2 +1,4 @@
 #[cfg(test)]
 mod message_tests;
+#[cfg(test)]
+mod validation_tests;
@@ -0,0 +1,275 @@
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_infinity_contracts::contract_infinity_global;
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+use infinity_global::{
+    helpers::validate_global_config,
+    msg::{InstantiateMsg, SudoMsg},
+    ConfigUpdate, ContractError, GlobalConfig,
+};
+use proptest::prelude::*;
+use sg_multi_test::StargazeApp;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::contract_boxes::custom_mock_app;
+
+fn global_config(
+    fair_burn_fee_percent: Decimal,
+    default_royalty_fee_percent: Decimal,
+    max_royalty_fee_percent: Decimal,
+    max_swap_fee_percent: Decimal,
+    max_referral_fee_percent: Decimal,
+) -> GlobalConfig<Addr> {
+    GlobalConfig {
+        fair_burn: Addr::unchecked("fair_burn"),
+        royalty_registry: Addr::unchecked("royalty_registry"),
+        marketplace: Addr::unchecked("marketplace"),
+        infinity_factory: Addr::unchecked("infinity_factory"),
+        infinity_index: Addr::unchecked("infinity_index"),
+        infinity_router: Addr::unchecked("infinity_router"),
+        infinity_pair_code_id: 1u64,
+        pair_creation_fee: coin(1_000_000u128, NATIVE_DENOM),
+        fair_burn_fee_percent,
+        default_royalty_fee_percent,
+        max_royalty_fee_percent,
+        max_swap_fee_percent,
+        max_referral_fee_percent,
+        paused: false,
+    }
+}
+
+fn instantiate_msg(
+    global_config: GlobalConfig<Addr>,
+    infinity_pair_code_id: u64,
+) -> InstantiateMsg {
+    InstantiateMsg {
+        global_config: GlobalConfig {
+            fair_burn: global_config.fair_burn.to_string(),
+            royalty_registry: global_config.royalty_registry.to_string(),
+            marketplace: global_config.marketplace.to_string(),
+            infinity_factory: global_config.infinity_factory.to_string(),
+            infinity_index: global_config.infinity_index.to_string(),
+            infinity_router: global_config.infinity_router.to_string(),
+            infinity_pair_code_id,
+            pair_creation_fee: global_config.pair_creation_fee,
+            fair_burn_fee_percent: global_config.fair_burn_fee_percent,
+            default_royalty_fee_percent: global_config.default_royalty_fee_percent,
+            max_royalty_fee_percent: global_config.max_royalty_fee_percent,
+            max_swap_fee_percent: global_config.max_swap_fee_percent,
+            max_referral_fee_percent: global_config.max_referral_fee_percent,
+            paused: global_config.paused,
+        },
+        min_prices: vec![coin(10u128, NATIVE_DENOM)],
+    }
+}
+
+fn default_global_config() -> GlobalConfig<Addr> {
+    global_config(
+        Decimal::percent(1u64),
+        Decimal::percent(5u64),
+        Decimal::percent(10u64),
+        Decimal::percent(5u64),
+        Decimal::percent(2u64),
+    )
+}
+
+fn instantiate_infinity_global(
+    router: &mut StargazeApp,
+    global_config: GlobalConfig<Addr>,
+    infinity_pair_code_id: u64,
+) -> Result<Addr, anyhow::Error> {
+    let infinity_global_code_id = router.store_code(contract_infinity_global());
+    router.instantiate_contract(
+        infinity_global_code_id,
+        Addr::unchecked("creator"),
+        &instantiate_msg(global_config, infinity_pair_code_id),
+        &[],
+        "Infinity Global",
+        None,
+    )
+}
+
+proptest! {
+    #[test]
+    fn validate_global_config_accepts_consistent_fees(
+        fair_burn_fee_permille in 0u64..=250u64,
+        max_royalty_fee_permille in 0u64..=250u64,
+        max_swap_fee_permille in 0u64..=250u64,
+        max_referral_fee_permille in 0u64..=250u64,
+        default_royalty_fee_share in 0u64..=100u64,
+    ) {
+        let max_royalty_fee_percent = Decimal::permille(max_royalty_fee_permille);
+        let global_config = global_config(
+            Decimal::permille(fair_burn_fee_permille),
+            max_royalty_fee_percent * Decimal::percent(default_royalty_fee_share),
+            max_royalty_fee_percent,
+            Decimal::permille(max_swap_fee_permille),
+            Decimal::permille(max_referral_fee_permille),
+        );
+        prop_assert!(validate_global_config(&global_config).is_ok());
+    }
+
+    #[test]
+    fn validate_global_config_bounds_fees_by_sale_amount(
+        fair_burn_fee_permille in 0u64..=1_500u64,
+        default_royalty_fee_permille in 0u64..=1_500u64,
+        max_royalty_fee_permille in 0u64..=1_500u64,
+        max_swap_fee_permille in 0u64..=1_500u64,
+        max_referral_fee_permille in 0u64..=1_500u64,
+        sale_amount in 0u128..=u64::MAX as u128,
+    ) {
+        let global_config = global_config(
+            Decimal::permille(fair_burn_fee_permille),
+            Decimal::permille(default_royalty_fee_permille),
+            Decimal::permille(max_royalty_fee_permille),
+            Decimal::permille(max_swap_fee_permille),
+            Decimal::permille(max_referral_fee_permille),
+        );
+
+        let total_fee_permille = fair_burn_fee_permille
+            + max_royalty_fee_permille
+            + max_swap_fee_permille
+            + max_referral_fee_permille;
+        let is_consistent = total_fee_permille <= 1_000u64
+            && default_royalty_fee_permille <= max_royalty_fee_permille;
+        prop_assert_eq!(validate_global_config(&global_config).is_ok(), is_consistent);
+
+        // A valid config never charges more in fees than the sale amount
+        if is_consistent {
+            let sale_amount = Uint128::from(sale_amount);
+            let total_fees = sale_amount * global_config.fair_burn_fee_percent
+                + sale_amount * global_config.max_royalty_fee_percent
+                + sale_amount * global_config.max_swap_fee_percent
+                + sale_amount * global_config.max_referral_fee_percent;
+            prop_assert!(total_fees <= sale_amount);
+        }
+    }
+}
+
+#[test]
+fn try_validate_global_config_errors() {
+    let mut global_config = default_global_config();
+    global_config.max_swap_fee_percent = Decimal::percent(101u64);
+    assert_eq!(
+        validate_global_config(&global_config),
+        Err(ContractError::FeePercentTooHigh {
+            field: "max_swap_fee_percent".to_string(),
+            fee_percent: Decimal::percent(101u64),
+        })
+    );
+
+    let mut global_config = default_global_config();
+    global_config.default_royalty_fee_percent = Decimal::percent(11u64);
+    assert_eq!(
+        validate_global_config(&global_config),
+        Err(ContractError::DefaultRoyaltyExceedsMax(
+            Decimal::percent(11u64),
+            Decimal::percent(10u64)
+        ))
+    );
+
+    let mut global_config = default_global_config();
+    global_config.max_royalty_fee_percent = Decimal::percent(95u64);
+    assert_eq!(
+        validate_global_config(&global_config),
+        Err(ContractError::TotalFeePercentTooHigh {
+            total_fee_percent: Decimal::percent(103u64),
+        })
+    );
+
+    let mut global_config = default_global_config();
+    global_config.infinity_pair_code_id = 0u64;
+    assert_eq!(validate_global_config(&global_config), Err(ContractError::InvalidCodeId(0u64)));
+}
+
+#[test]
+fn try_instantiate_infinity_global_validation() {
+    let mut router = custom_mock_app();
+
+    let mut global_config = default_global_config();
+    global_config.fair_burn_fee_percent = Decimal::percent(90u64);
+    let response = instantiate_infinity_global(&mut router, global_config, 1u64);
+    assert_error(
+        response.map(|_| AppResponse::default()),
+        ContractError::TotalFeePercentTooHigh {
+            total_fee_percent: Decimal::percent(107u64),
+        }
+        .to_string(),
+    );
+
+    // The infinity pair code id must exist
+    let response = instantiate_infinity_global(&mut router, default_global_config(), 100u64);
+    assert_error(
+        response.map(|_| AppResponse::default()),
+        ContractError::InvalidCodeId(100u64).to_string(),
+    );
+
+    let response = instantiate_infinity_global(&mut router, default_global_config(), 1u64);
+    assert!(response.is_ok());
+}
+
+#[test]
+fn try_propose_config_validation() {
+    let mut router = custom_mock_app();
+    let infinity_global =
+        instantiate_infinity_global(&mut router, default_global_config(), 1u64).unwrap();
+
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::ProposeConfig {
+            config_update: ConfigUpdate {
+                max_referral_fee_percent: Some(Decimal::percent(85u64)),
+                ..ConfigUpdate::default()
+            },
+        },
+    );
+    assert_error(
+        response,
+        ContractError::TotalFeePercentTooHigh {
+            total_fee_percent: Decimal::percent(101u64),
+        }
+        .to_string(),
+    );
+
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::ProposeConfig {
+            config_update: ConfigUpdate {
+                infinity_pair_code_id: Some(100u64),
+                ..ConfigUpdate::default()
+            },
+        },
+    );
+    assert_error(response, ContractError::InvalidCodeId(100u64).to_string());
+
+    // Updated addresses must belong to contracts
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::ProposeConfig {
+            config_update: ConfigUpdate {
+                infinity_index: Some("infinity_index_new".to_string()),
+                ..ConfigUpdate::default()
+            },
+        },
+    );
+    assert_error(
+        response,
+        ContractError::NotAContract {
+            field: "infinity_index".to_string(),
+            address: "infinity_index_new".to_string(),
+        }
+        .to_string(),
+    );
+
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::ProposeConfig {
+            config_update: ConfigUpdate {
+                infinity_index: Some(infinity_global.to_string()),
+                max_referral_fee_percent: Some(Decimal::percent(84u64)),
+                ..ConfigUpdate::default()
+            },
+        },
+    );
+    assert!(response.is_ok());
+}
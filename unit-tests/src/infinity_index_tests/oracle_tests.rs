@@ -0,0 +1,297 @@
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use infinity_index::msg::{
+    ExecuteMsg as InfinityIndexExecuteMsg, QueryMsg as InfinityIndexQueryMsg, TwapResponse,
+};
+use infinity_index::state::PriceObservation;
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairType};
+use infinity_shared::InfinityError;
+use sg_multi_test::StargazeApp;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn swap_tokens_for_any_nft(
+    router: &mut StargazeApp,
+    sender: &Addr,
+    pair_address: &Addr,
+) -> Uint128 {
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    let trade_price = pair.internal.buy_from_pair_quote_summary.unwrap().total();
+
+    let response = router.execute_contract(
+        sender.clone(),
+        pair_address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(trade_price.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    trade_price
+}
+
+#[test]
+fn try_oracle_trade_prices() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        10u64,
+        Uint128::zero(),
+    );
+
+    // Only infinity pairs can record trades
+    let response = router.execute_contract(
+        bidder.clone(),
+        infinity_index.clone(),
+        &InfinityIndexExecuteMsg::RecordTrade {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            price: Uint128::from(1u128),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized(
+            "only an infinity pair contract can execute this function".to_string(),
+        )
+        .to_string(),
+    );
+
+    // No trades have been recorded
+    let last_trade_price = router
+        .wrap()
+        .query_wasm_smart::<Option<PriceObservation>>(
+            infinity_index.clone(),
+            &InfinityIndexQueryMsg::LastTradePrice {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(last_trade_price, None);
+
+    let first_trade_timestamp = router.block_info().time.seconds();
+
+    // Execute two trades, 100 seconds apart
+    let mut trade_prices = vec![];
+    for _ in 0..2 {
+        let pair = router
+            .wrap()
+            .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+            .unwrap();
+        let trade_price = pair.internal.buy_from_pair_quote_summary.unwrap().total();
+
+        let response = router.execute_contract(
+            bidder.clone(),
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+                asset_recipient: None,
+            },
+            &[coin(trade_price.u128(), NATIVE_DENOM)],
+        );
+        assert!(response.is_ok());
+        trade_prices.push(trade_price);
+
+        router.update_block(|block| block.time = block.time.plus_seconds(100));
+    }
+    assert_eq!(trade_prices, vec![Uint128::from(10_600_000u128), Uint128::from(11_660_000u128)]);
+
+    let last_trade_price = router
+        .wrap()
+        .query_wasm_smart::<Option<PriceObservation>>(
+            infinity_index.clone(),
+            &InfinityIndexQueryMsg::LastTradePrice {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(last_trade_price.timestamp, first_trade_timestamp + 100);
+    assert_eq!(last_trade_price.price, trade_prices[1]);
+
+    let query_twap = |window_seconds: u64| {
+        router
+            .wrap()
+            .query_wasm_smart::<Option<TwapResponse>>(
+                infinity_index.clone(),
+                &InfinityIndexQueryMsg::Twap {
+                    collection: collection.to_string(),
+                    denom: NATIVE_DENOM.to_string(),
+                    window_seconds,
+                },
+            )
+            .unwrap()
+            .unwrap()
+    };
+
+    // Each trade price was the last trade price for 100 seconds
+    let twap_response = query_twap(200);
+    assert_eq!(twap_response.twap, coin(11_130_000u128, NATIVE_DENOM));
+    assert_eq!(twap_response.start_timestamp, first_trade_timestamp);
+    assert_eq!(twap_response.end_timestamp, first_trade_timestamp + 200);
+    assert_eq!(twap_response.last_trade_timestamp, first_trade_timestamp + 100);
+
+    // Windows longer than the trade history begin at the first trade
+    let twap_response = query_twap(1_000);
+    assert_eq!(twap_response.twap, coin(11_130_000u128, NATIVE_DENOM));
+    assert_eq!(twap_response.start_timestamp, first_trade_timestamp);
+
+    // Windows after the last trade return the last trade price
+    let twap_response = query_twap(50);
+    assert_eq!(twap_response.twap, coin(11_660_000u128, NATIVE_DENOM));
+    assert_eq!(twap_response.start_timestamp, first_trade_timestamp + 150);
+}
+
+#[test]
+fn try_oracle_wash_trade_bounds() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let mut create_pair = |spot_price: Uint128| {
+        create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            pair_config(
+                PairType::Nft,
+                BondingCurve::Linear {
+                    spot_price,
+                    delta: Uint128::zero(),
+                },
+            ),
+            5u64,
+            Uint128::zero(),
+        )
+    };
+
+    let fair_pair = create_pair(Uint128::from(10_000_000u128));
+    let wash_pair = create_pair(Uint128::from(1_000_000_000u128));
+
+    let query_last_trade_price = |router: &StargazeApp| {
+        router
+            .wrap()
+            .query_wasm_smart::<Option<PriceObservation>>(
+                infinity_index.clone(),
+                &InfinityIndexQueryMsg::LastTradePrice {
+                    collection: collection.to_string(),
+                    denom: NATIVE_DENOM.to_string(),
+                },
+            )
+            .unwrap()
+            .unwrap()
+    };
+
+    let first_trade_timestamp = router.block_info().time.seconds();
+    let fair_price = swap_tokens_for_any_nft(&mut router, &bidder, &fair_pair.address);
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    // Trades with the pair owner are not recorded
+    swap_tokens_for_any_nft(&mut router, &owner, &wash_pair.address);
+    let last_trade_price = query_last_trade_price(&router);
+    assert_eq!(last_trade_price.timestamp, first_trade_timestamp);
+    assert_eq!(last_trade_price.price, fair_price);
+
+    // Trades far from the previous price are clamped to the max deviation, and trades within
+    // the same block cannot compound the deviation
+    let max_price = fair_price + fair_price.mul_floor(Decimal::percent(25));
+    for _ in 0..2 {
+        let wash_price = swap_tokens_for_any_nft(&mut router, &bidder, &wash_pair.address);
+        assert!(wash_price > max_price * Uint128::from(50u128));
+    }
+    let last_trade_price = query_last_trade_price(&router);
+    assert_eq!(last_trade_price.timestamp, first_trade_timestamp + 100);
+    assert_eq!(last_trade_price.num_trades, 2);
+    assert_eq!(last_trade_price.price, max_price);
+
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    // The manipulated price only moves the twap for the time that it stands
+    let twap_response = router
+        .wrap()
+        .query_wasm_smart::<Option<TwapResponse>>(
+            infinity_index.clone(),
+            &InfinityIndexQueryMsg::Twap {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                window_seconds: 200,
+            },
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        twap_response.twap,
+        coin(((fair_price + max_price) / Uint128::from(2u128)).u128(), NATIVE_DENOM)
+    );
+}
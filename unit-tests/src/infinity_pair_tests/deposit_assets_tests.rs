@@ -1,5 +1,5 @@
-use crate::helpers::nft_functions::{approve_all, assert_nft_owner, mint_to, transfer};
-use crate::helpers::pair_functions::{create_pair, create_pair_with_deposits};
+use crate::helpers::nft_functions::{approve_all, assert_nft_owner, mint_to, send_nft, transfer};
+use crate::helpers::pair_functions::{create_pair, create_pair_with_deposits, pair_config};
 use crate::helpers::utils::assert_error;
 use crate::setup::setup_accounts::MarketAccounts;
 use crate::setup::setup_infinity_contracts::UOSMO;
@@ -7,11 +7,14 @@ use crate::setup::templates::{
     minter_two_collections, setup_infinity_test, standard_minter_template, InfinityTestSetup,
 };
 
-use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cosmwasm_std::{coin, to_binary, Addr, Binary, Decimal, Uint128};
 use cw_multi_test::Executor;
-use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::msg::{
+    ExecuteMsg as InfinityPairExecuteMsg, NftReconciliationResponse,
+    QueryMsg as InfinityPairQueryMsg, ReceiveNftMsg,
+};
 use infinity_pair::pair::Pair;
-use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_pair::state::{BondingCurve, PairType};
 use infinity_shared::InfinityError;
 use sg_std::NATIVE_DENOM;
 use test_suite::common_setup::msg::MinterTemplateResponse;
@@ -213,19 +216,17 @@ fn try_withdraw_other_collection_nfts() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Trade {
+        pair_config(
+            PairType::Trade {
                 swap_fee_percent: Decimal::zero(),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
             },
-            bonding_curve: BondingCurve::Linear {
+            BondingCurve::Linear {
                 spot_price: Uint128::from(10_000_000u128),
                 delta: Uint128::from(1_000_000u128),
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         10u64,
         Uint128::from(100_000_000u128),
     );
@@ -331,7 +332,7 @@ fn try_deposit_tokens() {
 
     let pair =
         router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
-    assert_eq!(pair.total_tokens.u128(), deposit_amount);
+    assert_eq!(pair.internal.total_tokens.u128(), deposit_amount);
 }
 
 #[test]
@@ -392,6 +393,24 @@ fn try_withdraw_tokens() {
     );
     assert!(response.is_ok());
 
+    // Owner cannot withdraw more tokens than the pair holds
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::WithdrawTokens {
+            funds: vec![coin(deposit_amount, NATIVE_DENOM)],
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InsufficientFunds {
+            expected: coin(deposit_amount, NATIVE_DENOM),
+        }
+        .to_string(),
+    );
+
     // Non owner cannot withdraw all tokens
     let response = router.execute_contract(
         accts.creator.clone(),
@@ -420,7 +439,7 @@ fn try_withdraw_tokens() {
 
     let pair =
         router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
-    assert_eq!(pair.total_tokens.u128(), 0u128);
+    assert_eq!(pair.internal.total_tokens.u128(), 0u128);
 }
 
 #[test]
@@ -455,19 +474,17 @@ fn try_withdraw_other_denom_tokens() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Trade {
+        pair_config(
+            PairType::Trade {
                 swap_fee_percent: Decimal::zero(),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
             },
-            bonding_curve: BondingCurve::Linear {
+            BondingCurve::Linear {
                 spot_price: Uint128::from(10_000_000u128),
                 delta: Uint128::from(1_000_000u128),
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         10u64,
         Uint128::from(100_000_000u128),
     );
@@ -513,3 +530,335 @@ fn try_withdraw_other_denom_tokens() {
         .unwrap();
     assert_eq!(test_pair.pair, pair);
 }
+
+#[test]
+fn try_skim_excess_tokens() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+            },
+            BondingCurve::ConstantProduct,
+        ),
+        10u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // Cannot skim when there are no excess tokens
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SkimExcess {
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("pair does not hold any excess tokens".to_string()).to_string(),
+    );
+
+    // Tokens sent directly to the pair do not move the constant product price
+    let excess_funds = coin(50_000_000u128, NATIVE_DENOM);
+    let response =
+        router.send_tokens(bidder.clone(), test_pair.address.clone(), &[excess_funds.clone()]);
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::RefreshQuotes {},
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair, test_pair.pair);
+
+    // Non owner cannot skim excess tokens
+    let response = router.execute_contract(
+        bidder,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SkimExcess {
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
+    );
+
+    // Owner can skim excess tokens to asset recipient
+    let asset_recipient = Addr::unchecked("asset_recipient");
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SkimExcess {
+            asset_recipient: Some(asset_recipient.to_string()),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let balance = router.wrap().query_balance(&asset_recipient, NATIVE_DENOM).unwrap();
+    assert_eq!(balance, excess_funds);
+
+    let balance = router.wrap().query_balance(&test_pair.address, NATIVE_DENOM).unwrap();
+    assert_eq!(balance.amount, test_pair.pair.internal.total_tokens);
+}
+
+#[test]
+fn try_receive_nft() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: true,
+            },
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // Owner can deposit an NFT by sending it to the pair
+    let token_id = mint_to(&mut router, &creator, &owner, &minter);
+    let response = send_nft(
+        &mut router,
+        &owner,
+        &collection,
+        &test_pair.address,
+        &token_id,
+        Binary::default(),
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_id.clone(), &test_pair.address);
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.total_nfts, 1u64);
+
+    // Non owner cannot deposit an NFT by sending it to the pair
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    let response = send_nft(
+        &mut router,
+        &bidder,
+        &collection,
+        &test_pair.address,
+        &token_id,
+        to_binary(&ReceiveNftMsg::DepositNft {}).unwrap(),
+    );
+    assert_eq!(
+        response.unwrap_err().root_cause().to_string(),
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string()
+    );
+
+    // Non owner can swap an NFT for tokens by sending it to the pair
+    let seller_amount = pair.internal.sell_to_pair_quote_summary.unwrap().seller_amount;
+    let seller_recipient = Addr::unchecked("seller_recipient");
+    let response = send_nft(
+        &mut router,
+        &bidder,
+        &collection,
+        &test_pair.address,
+        &token_id,
+        to_binary(&ReceiveNftMsg::SwapNftForTokens {
+            min_output: coin(seller_amount.u128(), NATIVE_DENOM),
+            asset_recipient: Some(seller_recipient.to_string()),
+        })
+        .unwrap(),
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_id, &test_pair.address);
+
+    let balance = router.wrap().query_balance(&seller_recipient, NATIVE_DENOM).unwrap();
+    assert_eq!(balance.amount, seller_amount);
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.total_nfts, 2u64);
+}
+
+#[test]
+fn try_reconcile_nfts() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        3u64,
+        Uint128::zero(),
+    );
+
+    let reconciliation = router
+        .wrap()
+        .query_wasm_smart::<NftReconciliationResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::NftReconciliation {},
+        )
+        .unwrap();
+    assert!(reconciliation.is_reconciled());
+
+    // NFTs transferred directly to the pair are not recorded as deposits
+    let token_id = mint_to(&mut router, &creator, &owner, &minter);
+    transfer(&mut router, &owner, &test_pair.address, &collection, &token_id);
+
+    let reconciliation = router
+        .wrap()
+        .query_wasm_smart::<NftReconciliationResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::NftReconciliation {},
+        )
+        .unwrap();
+    assert_eq!(
+        reconciliation,
+        NftReconciliationResponse {
+            total_nfts: 3u64,
+            num_deposits: 3u64,
+            num_owned: 4u64,
+            missing_deposits: vec![token_id.clone()],
+            stale_deposits: vec![],
+        }
+    );
+
+    // Non owner reconciliation does not add NFTs to the pair inventory
+    let response = router.execute_contract(
+        bidder,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::Reconcile {},
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let reconciliation = router
+        .wrap()
+        .query_wasm_smart::<NftReconciliationResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::NftReconciliation {},
+        )
+        .unwrap();
+    assert_eq!(reconciliation.missing_deposits, vec![token_id.clone()]);
+
+    // Owner reconciliation adds NFTs owned by the pair to the pair inventory
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::Reconcile {},
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let reconciliation = router
+        .wrap()
+        .query_wasm_smart::<NftReconciliationResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::NftReconciliation {},
+        )
+        .unwrap();
+    assert!(reconciliation.is_reconciled());
+    assert_eq!(reconciliation.total_nfts, 4u64);
+}
@@ -0,0 +1,264 @@
+use crate::helpers::nft_functions::assert_nft_owner;
+use crate::helpers::pair_functions::{create_pair, create_pair_with_deposits, pair_config};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{
+    minter_two_collections, setup_infinity_test, standard_minter_template, InfinityTestSetup,
+};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw_multi_test::Executor;
+use infinity_pair::msg::{
+    ExecuteMsg as InfinityPairExecuteMsg, NftDepositsResponse, QueryMsg as InfinityPairQueryMsg,
+};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_pair::ContractError;
+use infinity_shared::InfinityError;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn trade_pair_config() -> PairConfig<String> {
+    pair_config(
+        PairType::Trade {
+            swap_fee_percent: Decimal::zero(),
+            reinvest_tokens: false,
+            reinvest_nfts: false,
+        },
+        BondingCurve::Linear {
+            spot_price: Uint128::from(10_000_000u128),
+            delta: Uint128::from(1_000_000u128),
+        },
+    )
+}
+
+#[test]
+fn try_migrate_liquidity_to_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let num_tokens = Uint128::from(100_000_000u128);
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        trade_pair_config(),
+        5u64,
+        num_tokens,
+    );
+
+    let (new_pair_addr, _new_pair) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &owner);
+    let (other_owner_pair_addr, _other_owner_pair) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &bidder);
+
+    // Non owner cannot migrate the liquidity of the pair
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::MigrateLiquidityTo {
+            new_pair: new_pair_addr.to_string(),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
+    );
+
+    // Cannot migrate the liquidity of the pair to itself
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::MigrateLiquidityTo {
+            new_pair: test_pair.address.to_string(),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("cannot migrate liquidity to the same pair".to_string())
+            .to_string(),
+    );
+
+    // Cannot migrate the liquidity of the pair to a pair of another owner
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::MigrateLiquidityTo {
+            new_pair: other_owner_pair_addr.to_string(),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidPair("pair is owned by another owner".to_string()).to_string(),
+    );
+
+    // Cannot migrate the liquidity of the pair to a contract that is not a pair
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::MigrateLiquidityTo {
+            new_pair: collection.to_string(),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidPair("address is not an infinity pair".to_string()).to_string(),
+    );
+
+    // Owner can migrate the liquidity of the pair to another of their pairs
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::MigrateLiquidityTo {
+            new_pair: new_pair_addr.to_string(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    for token_id in &test_pair.token_ids {
+        assert_nft_owner(&router, &collection, token_id.clone(), &new_pair_addr);
+    }
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(!pair.config.is_active);
+    assert_eq!(pair.internal.total_nfts, 0u64);
+    assert_eq!(pair.internal.total_tokens, Uint128::zero());
+    let balance = router.wrap().query_balance(&test_pair.address, NATIVE_DENOM).unwrap();
+    assert_eq!(balance.amount, Uint128::zero());
+
+    let new_pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(new_pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(new_pair.internal.total_nfts, 5u64);
+    assert_eq!(new_pair.internal.total_tokens, num_tokens);
+    let balance = router.wrap().query_balance(&new_pair_addr, NATIVE_DENOM).unwrap();
+    assert_eq!(balance.amount, num_tokens);
+
+    let nft_deposits = router
+        .wrap()
+        .query_wasm_smart::<NftDepositsResponse>(
+            new_pair_addr,
+            &InfinityPairQueryMsg::NftDeposits {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    let mut token_ids = test_pair.token_ids.clone();
+    token_ids.sort();
+    assert_eq!(nft_deposits.token_ids, token_ids);
+
+    let nft_deposits = router
+        .wrap()
+        .query_wasm_smart::<NftDepositsResponse>(
+            test_pair.address,
+            &InfinityPairQueryMsg::NftDeposits {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert!(nft_deposits.token_ids.is_empty());
+}
+
+#[test]
+fn try_migrate_liquidity_to_other_collection_pair() {
+    let vt = minter_two_collections(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let minter = collection_response_vec[0].minter.clone().unwrap();
+    let collection = collection_response_vec[0].collection.clone().unwrap();
+    let other_collection = collection_response_vec[1].collection.clone().unwrap();
+
+    let num_tokens = Uint128::from(100_000_000u128);
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        trade_pair_config(),
+        3u64,
+        num_tokens,
+    );
+
+    let (new_pair_addr, _new_pair) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &other_collection, &owner);
+
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::MigrateLiquidityTo {
+            new_pair: new_pair_addr.to_string(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // NFTs of another collection than the new pair's are sent to the asset recipient
+    for token_id in &test_pair.token_ids {
+        assert_nft_owner(&router, &collection, token_id.clone(), &owner);
+    }
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(!pair.config.is_active);
+    assert_eq!(pair.internal.total_nfts, 0u64);
+    assert_eq!(pair.internal.total_tokens, Uint128::zero());
+
+    let new_pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(new_pair_addr, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(new_pair.internal.total_nfts, 0u64);
+    assert_eq!(new_pair.internal.total_tokens, num_tokens);
+}
@@ -1,12 +1,30 @@
 #[cfg(test)]
 mod deposit_assets_tests;
 #[cfg(test)]
+mod migrate_liquidity_tests;
+#[cfg(test)]
 mod nft_pair_swap_tests;
 #[cfg(test)]
+mod nft_price_tests;
+#[cfg(test)]
+mod oracle_anchored_curve_tests;
+#[cfg(test)]
 mod pair_creation_tests;
 #[cfg(test)]
+mod pair_limits_tests;
+#[cfg(test)]
+mod pair_migration_tests;
+#[cfg(test)]
 mod pair_quote_tests;
 #[cfg(test)]
+mod pair_schedule_tests;
+#[cfg(test)]
+mod pair_stats_tests;
+#[cfg(test)]
+mod pair_triggers_tests;
+#[cfg(test)]
+mod royalty_tests;
+#[cfg(test)]
 mod token_pair_swap_tests;
 #[cfg(test)]
 mod trade_pair_swap_tests;
@@ -1,5 +1,5 @@
 use crate::helpers::nft_functions::{approve, assert_nft_owner, mint_to};
-use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
 use crate::helpers::utils::assert_error;
 use crate::setup::setup_accounts::{setup_addtl_account, MarketAccounts, INITIAL_BALANCE};
 use crate::setup::setup_infinity_contracts::UOSMO;
@@ -10,7 +10,9 @@ use cw_multi_test::Executor;
 use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
 use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
 use infinity_pair::pair::Pair;
-use infinity_pair::state::{BondingCurve, PairConfig, PairType, QuoteSummary, TokenPayment};
+use infinity_pair::state::{
+    BondingCurve, NftSelection, PairConfig, PairType, QuoteSummary, TokenPayment,
+};
 use infinity_pair::ContractError;
 use infinity_shared::InfinityError;
 use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
@@ -66,13 +68,14 @@ fn try_nft_pair_invalid_swaps() {
         &creator,
         &owner,
         PairConfig {
-            pair_type: PairType::Nft,
-            bonding_curve: BondingCurve::Linear {
-                spot_price: Uint128::from(10_000_000u128),
-                delta: Uint128::from(1_000_000u128),
-            },
             is_active: false,
-            asset_recipient: None,
+            ..pair_config(
+                PairType::Nft,
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            )
         },
         10u64,
         Uint128::zero(),
@@ -104,6 +107,11 @@ fn try_nft_pair_invalid_swaps() {
             pair_type: None,
             bonding_curve: None,
             asset_recipient: None,
+            nft_selection: None,
+            limits: None,
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
         },
         &[],
     );
@@ -122,12 +130,12 @@ fn try_nft_pair_invalid_swaps() {
                 recipient: global_config.fair_burn,
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(10_000_000u128),
         })
@@ -243,15 +251,13 @@ fn try_nft_pair_linear_user_submits_tokens_swap() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Nft,
-            bonding_curve: BondingCurve::Linear {
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
                 spot_price: Uint128::from(10_000_000u128),
                 delta: Uint128::from(1_000_000u128),
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         10u64,
         Uint128::zero(),
     );
@@ -264,12 +270,12 @@ fn try_nft_pair_linear_user_submits_tokens_swap() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(10_000_000u128),
         })
@@ -304,10 +310,10 @@ fn try_nft_pair_linear_user_submits_tokens_swap() {
                 recipient: global_config.fair_burn,
                 amount: Uint128::from(110_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(collection_info.royalty_info.unwrap().payment_address),
                 amount: Uint128::from(550_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(11_000_000u128),
         })
@@ -362,15 +368,13 @@ fn try_nft_pair_exponential_user_submits_tokens_swap() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Nft,
-            bonding_curve: BondingCurve::Exponential {
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Exponential {
                 spot_price: Uint128::from(10_000_000u128),
                 delta: Decimal::percent(12),
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         10u64,
         Uint128::zero(),
     );
@@ -383,12 +387,12 @@ fn try_nft_pair_exponential_user_submits_tokens_swap() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(10_000_000u128),
         })
@@ -423,12 +427,93 @@ fn try_nft_pair_exponential_user_submits_tokens_swap() {
                 recipient: global_config.fair_burn,
                 amount: Uint128::from(112_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(collection_info.royalty_info.unwrap().payment_address),
                 amount: Uint128::from(560_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(11_200_000u128),
         })
     );
 }
+
+#[test]
+fn try_nft_pair_random_nft_selection() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            nft_selection: NftSelection::Random,
+            ..pair_config(
+                PairType::Nft,
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            )
+        },
+        5u64,
+        Uint128::zero(),
+    );
+    assert_eq!(test_pair.pair.config.nft_selection, NftSelection::Random);
+
+    // Every NFT held by the pair is sold exactly once
+    let mut sold_token_ids: Vec<String> = vec![];
+    for _ in 0..test_pair.token_ids.len() {
+        let pair = router
+            .wrap()
+            .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+            .unwrap();
+        let quote_total = pair.internal.buy_from_pair_quote_summary.unwrap().total();
+
+        let response = router
+            .execute_contract(
+                bidder.clone(),
+                test_pair.address.clone(),
+                &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+                    asset_recipient: None,
+                },
+                &[coin(quote_total.u128(), NATIVE_DENOM)],
+            )
+            .unwrap();
+
+        let swap_event =
+            response.events.iter().find(|e| e.ty == "wasm-swap-tokens-for-nft").unwrap();
+        let token_id =
+            swap_event.attributes.iter().find(|a| a.key == "token_id").unwrap().value.clone();
+        assert_nft_owner(&router, &collection, token_id.clone(), &bidder);
+        sold_token_ids.push(token_id);
+    }
+
+    let mut expected_token_ids = test_pair.token_ids.clone();
+    expected_token_ids.sort();
+    sold_token_ids.sort();
+    assert_eq!(sold_token_ids, expected_token_ids);
+}
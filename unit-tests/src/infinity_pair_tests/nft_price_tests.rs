@@ -0,0 +1,182 @@
+use crate::helpers::nft_functions::assert_nft_owner;
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Uint128};
+use cw_multi_test::Executor;
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, NftPrice, PairType};
+use infinity_shared::InfinityError;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_nft_prices() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        3u64,
+        Uint128::zero(),
+    );
+
+    // Only the pair owner can set NFT prices
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SetNftPrices {
+            token_ids: vec![test_pair.token_ids[0].clone()],
+            nft_price: Some(NftPrice::Fixed(Uint128::from(5_000_000u128))),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
+    );
+
+    // Cannot set the price of an NFT not held by the pair
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SetNftPrices {
+            token_ids: vec!["99999".to_string()],
+            nft_price: Some(NftPrice::Fixed(Uint128::from(5_000_000u128))),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("pair does not own NFT".to_string()).to_string(),
+    );
+
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SetNftPrices {
+            token_ids: vec![test_pair.token_ids[0].clone()],
+            nft_price: Some(NftPrice::Fixed(Uint128::from(5_000_000u128))),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SetNftPrices {
+            token_ids: vec![test_pair.token_ids[1].clone()],
+            nft_price: Some(NftPrice::Premium(Uint128::from(2_000_000u128))),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let nft_price = router
+        .wrap()
+        .query_wasm_smart::<Option<NftPrice>>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::NftPrice {
+                token_id: test_pair.token_ids[1].clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(nft_price, Some(NftPrice::Premium(Uint128::from(2_000_000u128))));
+
+    // The stored buy quote is the quote of the cheapest NFT, here the NFT with a fixed price
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(
+        pair.internal.buy_from_pair_quote_summary.unwrap().total(),
+        Uint128::from(5_300_000u128)
+    );
+
+    // NFTs with a premium are charged the pair price plus the premium, and fees
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForSpecificNft {
+            token_id: test_pair.token_ids[1].clone(),
+            asset_recipient: None,
+        },
+        &[coin(10_600_000u128, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("received funds does not equal quote".to_string()).to_string(),
+    );
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForSpecificNft {
+            token_id: test_pair.token_ids[1].clone(),
+            asset_recipient: None,
+        },
+        &[coin(12_720_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, test_pair.token_ids[1].clone(), &bidder);
+
+    // Swapping for any NFT selects the cheapest NFT, here the NFT with a fixed price
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(5_300_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, test_pair.token_ids[0].clone(), &bidder);
+
+    // The remaining NFT has no listing price and is charged the pair price
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(12_720_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, test_pair.token_ids[2].clone(), &bidder);
+}
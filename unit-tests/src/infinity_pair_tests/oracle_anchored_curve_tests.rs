@@ -0,0 +1,318 @@
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config, refresh_pair};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairType};
+use infinity_pair::ContractError;
+use infinity_shared::InfinityError;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_oracle_anchored_curve_quotes() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    // A linear pair used to generate trades for the oracle
+    let linear_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        1u64,
+        Uint128::zero(),
+    );
+
+    let oracle_anchored_curve = BondingCurve::OracleAnchored {
+        oracle: infinity_index,
+        twap_window_seconds: 1_000u64,
+        max_staleness_seconds: 500u64,
+        sell_to_pair_spread: Decimal::percent(10),
+        buy_from_pair_spread: Decimal::percent(10),
+    };
+
+    let oracle_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+            },
+            oracle_anchored_curve,
+        ),
+        2u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // Spread must be valid
+    let response = router.execute_contract(
+        owner.clone(),
+        oracle_pair.address.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: None,
+            pair_type: None,
+            bonding_curve: Some(BondingCurve::OracleAnchored {
+                oracle: Addr::unchecked("oracle"),
+                twap_window_seconds: 1_000u64,
+                max_staleness_seconds: 500u64,
+                sell_to_pair_spread: Decimal::one(),
+                buy_from_pair_spread: Decimal::percent(10),
+            }),
+            asset_recipient: None,
+            nft_selection: None,
+            limits: None,
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("sell to pair spread must be less than one".to_string())
+            .to_string(),
+    );
+
+    // Without oracle data the pair does not quote
+    assert_eq!(oracle_pair.pair.internal.reference_price, None);
+    assert_eq!(oracle_pair.pair.internal.sell_to_pair_quote_summary, None);
+    assert_eq!(oracle_pair.pair.internal.buy_from_pair_quote_summary, None);
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        linear_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(10_600_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    // Quotes are anchored to the reference price, with a spread on each side
+    let pair = refresh_pair(&mut router, &bidder, &oracle_pair.address);
+    assert_eq!(pair.internal.reference_price, Some(Uint128::from(10_600_000u128)));
+    assert_eq!(
+        pair.internal.sell_to_pair_quote_summary.unwrap().total(),
+        Uint128::from(9_540_000u128)
+    );
+    assert_eq!(
+        pair.internal.buy_from_pair_quote_summary.unwrap().seller_amount,
+        Uint128::from(11_660_000u128)
+    );
+
+    // Trades do not move the spot price of an oracle anchored curve
+    let response = router.execute_contract(
+        bidder.clone(),
+        oracle_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(11_660_000u128 + 116_600u128 + 583_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(oracle_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(
+        pair.internal.buy_from_pair_quote_summary.unwrap().seller_amount,
+        Uint128::from(11_660_000u128)
+    );
+
+    // Once the last oracle trade is older than the max staleness, the pair stops quoting
+    router.update_block(|block| block.time = block.time.plus_seconds(1_000));
+
+    let pair = refresh_pair(&mut router, &bidder, &oracle_pair.address);
+    assert_eq!(pair.internal.reference_price, None);
+    assert_eq!(pair.internal.sell_to_pair_quote_summary, None);
+    assert_eq!(pair.internal.buy_from_pair_quote_summary, None);
+}
+
+#[test]
+fn try_oracle_anchored_curve_reference_price_refresh() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    // A linear pair used to generate trades for the oracle
+    let linear_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        2u64,
+        Uint128::zero(),
+    );
+
+    let oracle_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::OracleAnchored {
+                oracle: infinity_index,
+                twap_window_seconds: 1_000u64,
+                max_staleness_seconds: 500u64,
+                sell_to_pair_spread: Decimal::percent(10),
+                buy_from_pair_spread: Decimal::percent(10),
+            },
+        ),
+        2u64,
+        Uint128::zero(),
+    );
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        linear_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(10_600_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    let pair = refresh_pair(&mut router, &bidder, &oracle_pair.address);
+    assert_eq!(pair.internal.reference_price, Some(Uint128::from(10_600_000u128)));
+    let stale_quote_total = pair.internal.buy_from_pair_quote_summary.unwrap().total();
+    assert_eq!(stale_quote_total, Uint128::from(12_359_600u128));
+
+    // The oracle moves after the oracle pair last updated its quotes
+    let response = router.execute_contract(
+        bidder.clone(),
+        linear_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(11_660_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    // Swaps are executed against the current reference price, not the stored quote
+    let response = router.execute_contract(
+        bidder.clone(),
+        oracle_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(stale_quote_total.u128(), NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("received funds does not equal quote".to_string()).to_string(),
+    );
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        oracle_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(12_243_000u128 + 122_430u128 + 612_150u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(oracle_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.reference_price, Some(Uint128::from(11_130_000u128)));
+
+    // Once the reference price is stale, swaps are rejected without a refresh
+    router.update_block(|block| block.time = block.time.plus_seconds(1_000));
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        oracle_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(12_243_000u128 + 122_430u128 + 612_150u128, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidPair("pair reference price is stale".to_string()).to_string(),
+    );
+}
@@ -1,4 +1,4 @@
-use crate::helpers::pair_functions::create_pair;
+use crate::helpers::pair_functions::{create_pair, pair_config};
 use crate::helpers::utils::assert_error;
 use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
 
@@ -8,7 +8,9 @@ use infinity_factory::msg::ExecuteMsg as InfinityFactoryExecuteMsg;
 use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
 use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
 use infinity_pair::pair::Pair;
-use infinity_pair::state::{BondingCurve, PairConfig, PairImmutable, PairInternal, PairType};
+use infinity_pair::state::{
+    BondingCurve, LimitWindow, PairConfig, PairImmutable, PairInternal, PairType,
+};
 use infinity_shared::InfinityError;
 use sg_multi_test::mock_deps;
 use sg_std::NATIVE_DENOM;
@@ -48,13 +50,14 @@ fn try_create_pair() {
     };
 
     let pair_config = PairConfig {
-        pair_type: PairType::Token,
-        bonding_curve: BondingCurve::Linear {
-            spot_price: Uint128::from(10_000_000u128),
-            delta: Uint128::from(1_000_000u128),
-        },
         is_active: false,
-        asset_recipient: None,
+        ..pair_config(
+            PairType::Token,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        )
     };
 
     // Fails without funds sent
@@ -95,6 +98,12 @@ fn try_create_pair() {
             total_nfts: 0u64,
             sell_to_pair_quote_summary: None,
             buy_from_pair_quote_summary: None,
+            reference_price: None,
+            total_tokens: Uint128::zero(),
+            limit_window: LimitWindow::default(),
+            outside_schedule: false,
+            sell_to_pair_halted: false,
+            buy_from_pair_halted: false,
         }
     );
 }
@@ -130,6 +139,11 @@ fn try_update_pair_config() {
             pair_type: None,
             bonding_curve: None,
             asset_recipient: None,
+            nft_selection: None,
+            limits: None,
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
         },
         &[],
     );
@@ -147,6 +161,11 @@ fn try_update_pair_config() {
             pair_type: None,
             bonding_curve: None,
             asset_recipient: None,
+            nft_selection: None,
+            limits: None,
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
         },
         &[],
     );
@@ -165,6 +184,11 @@ fn try_update_pair_config() {
             pair_type: Some(pair_type.clone()),
             bonding_curve: Some(bonding_curve.clone()),
             asset_recipient: Some(asset_recipient.to_string()),
+            nft_selection: None,
+            limits: None,
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
         },
         &[],
     );
@@ -0,0 +1,301 @@
+use crate::helpers::nft_functions::{approve, mint_to};
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config, query_pair};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use anyhow::Error;
+use cosmwasm_std::{coin, Addr, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+use infinity_factory::msg::ExecuteMsg as InfinityFactoryExecuteMsg;
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_pair::msg::ExecuteMsg as InfinityPairExecuteMsg;
+use infinity_pair::state::{BondingCurve, PairConfig, PairLimits, PairType};
+use infinity_pair::ContractError;
+use infinity_router::msg::QueryMsg as InfinityRouterQueryMsg;
+use infinity_router::nfts_for_tokens_iterators::types::NftForTokensQuote;
+use infinity_shared::InfinityError;
+use sg_multi_test::StargazeApp;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn query_nfts_for_tokens_quotes(
+    router: &StargazeApp,
+    infinity_router: &Addr,
+    collection: &Addr,
+) -> Vec<NftForTokensQuote> {
+    router
+        .wrap()
+        .query_wasm_smart::<Vec<NftForTokensQuote>>(
+            infinity_router,
+            &InfinityRouterQueryMsg::NftsForTokens {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 10,
+                filter_sources: None,
+            },
+        )
+        .unwrap()
+}
+
+fn sell_nft(
+    router: &mut StargazeApp,
+    creator: &Addr,
+    seller: &Addr,
+    minter: &Addr,
+    collection: &Addr,
+    pair_address: &Addr,
+) -> Result<AppResponse, Error> {
+    let token_id = mint_to(router, creator, seller, minter);
+    approve(router, seller, collection, pair_address, token_id.clone());
+    router.execute_contract(
+        seller.clone(),
+        pair_address.clone(),
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id,
+            min_output: coin(1u128, NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    )
+}
+
+#[test]
+fn try_pair_limits() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            limits: PairLimits {
+                window_seconds: 100u64,
+                max_nfts_bought_per_window: Some(2u64),
+                max_tokens_spent_per_window: None,
+                max_inventory: None,
+            },
+            ..pair_config(
+                PairType::Token,
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            )
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // Rate limits require a window
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: None,
+            pair_type: None,
+            bonding_curve: None,
+            asset_recipient: None,
+            nft_selection: None,
+            limits: Some(PairLimits {
+                window_seconds: 0u64,
+                max_nfts_bought_per_window: Some(2u64),
+                max_tokens_spent_per_window: None,
+                max_inventory: None,
+            }),
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("window seconds must be greater than zero".to_string())
+            .to_string(),
+    );
+
+    // The pair buys at most two NFTs within a window
+    for _ in 0..2 {
+        let response =
+            sell_nft(&mut router, &creator, &bidder, &minter, &collection, &test_pair.address);
+        assert!(response.is_ok());
+    }
+
+    let pair = query_pair(&router, &test_pair.address);
+    assert_eq!(pair.internal.limit_window.nfts_bought, 2u64);
+    assert_eq!(pair.internal.limit_window.tokens_spent, Uint128::from(19_000_000u128));
+    assert_eq!(pair.internal.sell_to_pair_quote_summary, None);
+
+    let response =
+        sell_nft(&mut router, &creator, &bidder, &minter, &collection, &test_pair.address);
+    assert_error(
+        response,
+        ContractError::InvalidPair("pair cannot produce quote".to_string()).to_string(),
+    );
+
+    // Once the window elapses, the pair buys NFTs again without being refreshed
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    let response =
+        sell_nft(&mut router, &creator, &bidder, &minter, &collection, &test_pair.address);
+    assert!(response.is_ok());
+
+    let pair = query_pair(&router, &test_pair.address);
+    assert_eq!(pair.internal.limit_window.nfts_bought, 1u64);
+    assert_eq!(pair.internal.limit_window.tokens_spent, Uint128::from(8_000_000u128));
+
+    // The pair spends at most the max tokens within a window
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: None,
+            pair_type: None,
+            bonding_curve: None,
+            asset_recipient: None,
+            nft_selection: None,
+            limits: Some(PairLimits {
+                window_seconds: 100u64,
+                max_nfts_bought_per_window: None,
+                max_tokens_spent_per_window: Some(Uint128::from(10_000_000u128)),
+                max_inventory: None,
+            }),
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response =
+        sell_nft(&mut router, &creator, &bidder, &minter, &collection, &test_pair.address);
+    assert!(response.is_ok());
+
+    let pair = query_pair(&router, &test_pair.address);
+    assert_eq!(pair.internal.limit_window.tokens_spent, Uint128::from(7_000_000u128));
+    assert_eq!(pair.internal.sell_to_pair_quote_summary, None);
+}
+
+#[test]
+fn try_pair_limits_refresh_index_quotes() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            limits: PairLimits {
+                window_seconds: 100u64,
+                max_nfts_bought_per_window: Some(1u64),
+                max_tokens_spent_per_window: None,
+                max_inventory: None,
+            },
+            ..pair_config(
+                PairType::Token,
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            )
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let quotes = query_nfts_for_tokens_quotes(&router, &global_config.infinity_router, &collection);
+    assert_eq!(quotes.len(), 1);
+
+    // Once the pair reaches its limits, it is removed from the index
+    let response =
+        sell_nft(&mut router, &creator, &bidder, &minter, &collection, &test_pair.address);
+    assert!(response.is_ok());
+
+    let quotes = query_nfts_for_tokens_quotes(&router, &global_config.infinity_router, &collection);
+    assert!(quotes.is_empty());
+
+    // The index is not updated when the window elapses
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    let quotes = query_nfts_for_tokens_quotes(&router, &global_config.infinity_router, &collection);
+    assert!(quotes.is_empty());
+
+    // Refreshing the pair through the factory restores its quote in the index
+    let response = router.execute_contract(
+        bidder,
+        infinity_factory,
+        &InfinityFactoryExecuteMsg::RefreshPairQuotes {
+            pair_addresses: vec![test_pair.address.to_string()],
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair = query_pair(&router, &test_pair.address);
+    assert_eq!(pair.internal.limit_window.nfts_bought, 0u64);
+
+    let quotes = query_nfts_for_tokens_quotes(&router, &global_config.infinity_router, &collection);
+    assert_eq!(quotes.len(), 1);
+    assert_eq!(quotes[0].address, test_pair.address);
+    assert_eq!(quotes[0].amount, pair.internal.sell_to_pair_quote_summary.unwrap().seller_amount);
+}
@@ -0,0 +1,276 @@
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::testing::{
+    mock_dependencies_with_balance, mock_env, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{coin, Addr, DepsMut, Env, MessageInfo, OwnedDeps, StdResult, Storage, Uint128};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use infinity_factory::msg::{
+    ExecuteMsg as InfinityFactoryExecuteMsg, SudoMsg as InfinityFactorySudoMsg,
+};
+use infinity_pair::constants::{TopKey, CONTRACT_NAME, CONTRACT_VERSION};
+use infinity_pair::migrate::apply_state_transforms;
+use infinity_pair::msg::{
+    MigrateMsg as PairMigrateMsg, QueryMsg as InfinityPairQueryMsg, StateTransform,
+};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{
+    LimitWindow, NftPrice, NftSelection, PairImmutable, PairLimits, PairSchedule, PairTriggers,
+    SweepPolicy, TokenPayment, INFINITY_GLOBAL, NFT_DEPOSITS, PAIR_CONFIG, PAIR_IMMUTABLE,
+    PAIR_INTERNAL,
+};
+use sg_std::{Response, StargazeMsgWrapper, NATIVE_DENOM};
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+const LEGACY_NFT_DEPOSITS: Map<String, bool> = Map::new(TopKey::NftDeposits.as_str());
+
+const LEGACY_PAIR_CONFIG: &str = r#"{
+    "pair_type": "token",
+    "bonding_curve": {"linear": {"spot_price": "10000000", "delta": "1000000"}},
+    "is_active": true,
+    "asset_recipient": null
+}"#;
+
+const LEGACY_PAIR_INTERNAL: &str = r#"{
+    "total_nfts": 2,
+    "sell_to_pair_quote_summary": {
+        "fair_burn": {"recipient": "fair_burn", "amount": "100000"},
+        "royalty": {"recipient": "creator", "amount": "500000"},
+        "swap": null,
+        "seller_amount": "9400000"
+    },
+    "buy_from_pair_quote_summary": null
+}"#;
+
+fn setup_legacy_deps() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies_with_balance(&[coin(50_000_000u128, NATIVE_DENOM)]);
+    let storage = &mut deps.storage;
+
+    PAIR_IMMUTABLE
+        .save(
+            storage,
+            &PairImmutable {
+                collection: Addr::unchecked("collection"),
+                owner: Addr::unchecked("owner"),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap();
+
+    LEGACY_NFT_DEPOSITS.save(storage, "1".to_string(), &true).unwrap();
+    LEGACY_NFT_DEPOSITS.save(storage, "2".to_string(), &true).unwrap();
+    NFT_DEPOSITS
+        .save(storage, "3".to_string(), &Some(NftPrice::Fixed(Uint128::from(100u128))))
+        .unwrap();
+
+    storage.set(TopKey::PairConfig.as_str().as_bytes(), LEGACY_PAIR_CONFIG.as_bytes());
+    storage.set(TopKey::PairInternal.as_str().as_bytes(), LEGACY_PAIR_INTERNAL.as_bytes());
+
+    deps
+}
+
+#[test]
+fn try_migrate_legacy_pair_state() {
+    let mut deps = setup_legacy_deps();
+
+    // The legacy state cannot be read before it is transformed
+    assert!(NFT_DEPOSITS.load(&deps.storage, "1".to_string()).is_err());
+    assert!(PAIR_INTERNAL.load(&deps.storage).is_err());
+
+    let state_transforms = vec![
+        StateTransform::ConvertNftDeposits,
+        StateTransform::BackfillPairConfig,
+        StateTransform::ConvertPairInternal,
+    ];
+    apply_state_transforms(deps.as_mut(), &mock_env(), &state_transforms).unwrap();
+
+    assert_eq!(NFT_DEPOSITS.load(&deps.storage, "1".to_string()).unwrap(), None);
+    assert_eq!(NFT_DEPOSITS.load(&deps.storage, "2".to_string()).unwrap(), None);
+    assert_eq!(
+        NFT_DEPOSITS.load(&deps.storage, "3".to_string()).unwrap(),
+        Some(NftPrice::Fixed(Uint128::from(100u128)))
+    );
+
+    // The new pair config fields are stored with their defaults
+    let raw_pair_config =
+        String::from_utf8(deps.storage.get(TopKey::PairConfig.as_str().as_bytes()).unwrap())
+            .unwrap();
+    assert!(raw_pair_config.contains("\"triggers\""));
+    let pair_config = PAIR_CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(pair_config.nft_selection, NftSelection::Ordered);
+    assert_eq!(pair_config.limits, PairLimits::default());
+    assert_eq!(pair_config.schedule, PairSchedule::default());
+    assert_eq!(pair_config.sweep_policy, SweepPolicy::Disabled);
+    assert_eq!(pair_config.triggers, PairTriggers::default());
+
+    let pair_internal = PAIR_INTERNAL.load(&deps.storage).unwrap();
+    assert_eq!(pair_internal.total_nfts, 2);
+    assert_eq!(
+        pair_internal.sell_to_pair_quote_summary.unwrap().royalties,
+        vec![TokenPayment {
+            recipient: Addr::unchecked("creator"),
+            amount: Uint128::from(500_000u128),
+        }]
+    );
+    assert_eq!(pair_internal.buy_from_pair_quote_summary, None);
+    // The token reserves of the legacy pair are backfilled from the contract balance
+    assert_eq!(pair_internal.total_tokens, Uint128::from(50_000_000u128));
+    assert_eq!(pair_internal.limit_window, LimitWindow::default());
+    assert!(!pair_internal.outside_schedule);
+    assert!(!pair_internal.sell_to_pair_halted);
+    assert!(!pair_internal.buy_from_pair_halted);
+
+    // The transforms leave state stored in the current format unchanged
+    apply_state_transforms(deps.as_mut(), &mock_env(), &state_transforms).unwrap();
+    assert_eq!(
+        NFT_DEPOSITS.load(&deps.storage, "3".to_string()).unwrap(),
+        Some(NftPrice::Fixed(Uint128::from(100u128)))
+    );
+    assert_eq!(PAIR_INTERNAL.load(&deps.storage).unwrap().total_nfts, 2);
+}
+
+#[test]
+fn try_migrate_without_state_transforms() {
+    let mut deps = setup_legacy_deps();
+
+    apply_state_transforms(deps.as_mut(), &mock_env(), &[]).unwrap();
+
+    assert!(NFT_DEPOSITS.load(&deps.storage, "1".to_string()).is_err());
+    assert!(PAIR_INTERNAL.load(&deps.storage).is_err());
+}
+
+/// Instantiates a mock pair storing the state of the first version of the pair
+#[cw_serde]
+struct LegacyPairInstantiateMsg {
+    infinity_global: String,
+    collection: String,
+    owner: String,
+}
+
+fn legacy_pair_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: LegacyPairInstantiateMsg,
+) -> StdResult<Response> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, "0.1.0")?;
+
+    INFINITY_GLOBAL.save(deps.storage, &deps.api.addr_validate(&msg.infinity_global)?)?;
+    PAIR_IMMUTABLE.save(
+        deps.storage,
+        &PairImmutable {
+            collection: deps.api.addr_validate(&msg.collection)?,
+            owner: deps.api.addr_validate(&msg.owner)?,
+            denom: NATIVE_DENOM.to_string(),
+        },
+    )?;
+    deps.storage.set(TopKey::PairConfig.as_str().as_bytes(), LEGACY_PAIR_CONFIG.as_bytes());
+    deps.storage.set(TopKey::PairInternal.as_str().as_bytes(), LEGACY_PAIR_INTERNAL.as_bytes());
+
+    Ok(Response::new())
+}
+
+fn contract_legacy_infinity_pair() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new(
+        infinity_pair::execute::execute,
+        legacy_pair_instantiate,
+        infinity_pair::query::query,
+    );
+    Box::new(contract)
+}
+
+#[test]
+fn try_migrate_legacy_pair_through_factory() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        infinity_pair_code_id,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection = collection_response_vec[0].collection.clone().unwrap();
+
+    // The legacy pair is instantiated by the factory, which is also its admin,
+    // and holds its token reserves as the contract balance
+    let legacy_pair_code_id = router.store_code(contract_legacy_infinity_pair());
+    let pair_address = router
+        .instantiate_contract(
+            legacy_pair_code_id,
+            infinity_factory.clone(),
+            &LegacyPairInstantiateMsg {
+                infinity_global: infinity_global.to_string(),
+                collection: collection.to_string(),
+                owner: owner.to_string(),
+            },
+            &[],
+            "Infinity Pair",
+            Some(infinity_factory.to_string()),
+        )
+        .unwrap();
+    router
+        .send_tokens(owner.clone(), pair_address.clone(), &[coin(50_000_000u128, NATIVE_DENOM)])
+        .unwrap();
+
+    // The legacy state cannot be loaded before the migration
+    let response = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_address.clone(), &InfinityPairQueryMsg::Pair {});
+    assert!(response.is_err());
+
+    let response = router.wasm_sudo(
+        infinity_factory.clone(),
+        &InfinityFactorySudoMsg::AddUnrestrictedMigration {
+            starting_code_id: legacy_pair_code_id,
+            target_code_id: infinity_pair_code_id,
+            migrate_msg: Some(PairMigrateMsg {
+                state_transforms: vec![
+                    StateTransform::ConvertNftDeposits,
+                    StateTransform::BackfillPairConfig,
+                    StateTransform::ConvertPairInternal,
+                ],
+            }),
+        },
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory,
+        &InfinityFactoryExecuteMsg::UnrestrictedMigratePair {
+            pair_address: pair_address.to_string(),
+            target_code_id: infinity_pair_code_id,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let contract_info = router.wrap().query_wasm_contract_info(pair_address.clone()).unwrap();
+    assert_eq!(contract_info.code_id, infinity_pair_code_id);
+
+    let contract_version = cw2::query_contract_info(&router.wrap(), pair_address.clone()).unwrap();
+    assert_eq!(contract_version.version, CONTRACT_VERSION);
+
+    // The migrated pair loads with its reserves backfilled and its quotes rebuilt
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.immutable.owner, owner);
+    assert_eq!(pair.config.triggers, PairTriggers::default());
+    assert_eq!(pair.internal.total_tokens, Uint128::from(50_000_000u128));
+    assert!(pair.internal.sell_to_pair_quote_summary.is_some());
+}
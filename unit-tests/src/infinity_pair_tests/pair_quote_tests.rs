@@ -1,11 +1,11 @@
-use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
 use crate::setup::setup_accounts::MarketAccounts;
 use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
 
 use cosmwasm_std::{Addr, Decimal, Uint128};
 use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
 use infinity_pair::msg::{QueryMsg as InfinityPairQueryMsg, QuotesResponse};
-use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_pair::state::{BondingCurve, PairType};
 use sg_std::NATIVE_DENOM;
 use test_suite::common_setup::msg::MinterTemplateResponse;
 
@@ -53,15 +53,13 @@ fn try_generate_quotes_token_linear() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Token {},
-            bonding_curve: BondingCurve::Linear {
+        pair_config(
+            PairType::Token {},
+            BondingCurve::Linear {
                 spot_price,
                 delta,
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         0u64,
         remaining_amount,
     );
@@ -134,15 +132,13 @@ fn try_generate_quotes_token_exponential() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Token {},
-            bonding_curve: BondingCurve::Exponential {
+        pair_config(
+            PairType::Token {},
+            BondingCurve::Exponential {
                 spot_price,
                 delta,
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         0u64,
         remaining_amount,
     );
@@ -215,15 +211,13 @@ fn try_generate_quotes_nft_linear() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Nft {},
-            bonding_curve: BondingCurve::Linear {
+        pair_config(
+            PairType::Nft {},
+            BondingCurve::Linear {
                 spot_price,
                 delta,
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         num_nfts,
         Uint128::zero(),
     );
@@ -299,15 +293,13 @@ fn try_generate_quotes_nft_exponential() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Nft {},
-            bonding_curve: BondingCurve::Exponential {
+        pair_config(
+            PairType::Nft {},
+            BondingCurve::Exponential {
                 spot_price,
                 delta,
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         num_nfts,
         Uint128::zero(),
     );
@@ -384,19 +376,17 @@ fn try_generate_quotes_trade_linear() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Trade {
+        pair_config(
+            PairType::Trade {
                 swap_fee_percent,
                 reinvest_tokens: false,
                 reinvest_nfts: false,
             },
-            bonding_curve: BondingCurve::Linear {
+            BondingCurve::Linear {
                 spot_price: original_spot_price,
                 delta,
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         num_nfts,
         remaining_amount,
     );
@@ -501,19 +491,17 @@ fn try_generate_quotes_trade_exponential() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Trade {
+        pair_config(
+            PairType::Trade {
                 swap_fee_percent,
                 reinvest_tokens: false,
                 reinvest_nfts: false,
             },
-            bonding_curve: BondingCurve::Exponential {
+            BondingCurve::Exponential {
                 spot_price: original_spot_price,
                 delta,
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         num_nfts,
         remaining_amount,
     );
@@ -615,16 +603,14 @@ fn try_generate_quotes_trade_cp() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Trade {
+        pair_config(
+            PairType::Trade {
                 swap_fee_percent,
                 reinvest_tokens: false,
                 reinvest_nfts: false,
             },
-            bonding_curve: BondingCurve::ConstantProduct {},
-            is_active: true,
-            asset_recipient: None,
-        },
+            BondingCurve::ConstantProduct {},
+        ),
         original_num_nfts,
         original_remaining_amount,
     );
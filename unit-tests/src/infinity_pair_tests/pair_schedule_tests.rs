@@ -0,0 +1,153 @@
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config, refresh_pair};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Uint128};
+use cw_multi_test::Executor;
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairConfig, PairSchedule, PairType};
+use infinity_pair::ContractError;
+use infinity_shared::InfinityError;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_pair_schedule() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let now = router.block_info().time;
+    let schedule = PairSchedule {
+        active_from: Some(now.plus_seconds(100)),
+        expires_at: Some(now.plus_seconds(200)),
+    };
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            schedule: schedule.clone(),
+            ..pair_config(
+                PairType::Nft,
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            )
+        },
+        3u64,
+        Uint128::zero(),
+    );
+
+    // Schedule must start before it expires
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: None,
+            pair_type: None,
+            bonding_curve: None,
+            asset_recipient: None,
+            nft_selection: None,
+            limits: None,
+            schedule: Some(PairSchedule {
+                active_from: schedule.expires_at,
+                expires_at: schedule.active_from,
+            }),
+            sweep_policy: None,
+            triggers: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("active_from must be before expires_at".to_string())
+            .to_string(),
+    );
+
+    // Before the schedule starts the pair does not quote or trade
+    assert!(test_pair.pair.internal.outside_schedule);
+    assert_eq!(test_pair.pair.internal.buy_from_pair_quote_summary, None);
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(10_600_000u128, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidPair("pair is outside of its schedule".to_string()).to_string(),
+    );
+
+    // Once the schedule starts the pair trades without being refreshed
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(10_600_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(&test_pair.address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(!pair.internal.outside_schedule);
+    assert_eq!(
+        pair.internal.buy_from_pair_quote_summary.unwrap().total(),
+        Uint128::from(11_660_000u128)
+    );
+
+    // Once the schedule expires the pair stops trading and quoting
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(11_660_000u128, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidPair("pair is outside of its schedule".to_string()).to_string(),
+    );
+
+    let pair = refresh_pair(&mut router, &bidder, &test_pair.address);
+    assert!(pair.internal.outside_schedule);
+    assert_eq!(pair.internal.buy_from_pair_quote_summary, None);
+}
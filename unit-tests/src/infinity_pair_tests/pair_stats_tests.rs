@@ -0,0 +1,137 @@
+use crate::helpers::nft_functions::{approve, mint_to};
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
+use crate::setup::setup_accounts::{setup_addtl_account, MarketAccounts, INITIAL_BALANCE};
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::state::{BondingCurve, PairStats, PairType};
+use sg_multi_test::StargazeApp;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn query_pair_stats(router: &StargazeApp, pair_address: &Addr) -> PairStats {
+    router
+        .wrap()
+        .query_wasm_smart::<PairStats>(pair_address, &InfinityPairQueryMsg::PairStats {})
+        .unwrap()
+}
+
+#[test]
+fn try_pair_stats() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Trade {
+                swap_fee_percent: Decimal::percent(1),
+                reinvest_tokens: true,
+                reinvest_nfts: true,
+            },
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        1u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // A new pair has no trading history
+    assert_eq!(query_pair_stats(&router, &test_pair.address), PairStats::default());
+
+    // NFTs sold to the pair are held at their purchase price
+    let seller = setup_addtl_account(&mut router, "seller", INITIAL_BALANCE).unwrap();
+    let token_id = mint_to(&mut router, &creator, &seller, &minter);
+    approve(&mut router, &seller, &collection, &test_pair.address, token_id.clone());
+
+    let response = router.execute_contract(
+        seller,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id,
+            min_output: coin(9_300_000u128, NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair_stats = query_pair_stats(&router, &test_pair.address);
+    assert_eq!(pair_stats.sell_to_pair_count, 1u64);
+    assert_eq!(pair_stats.sell_to_pair_volume, Uint128::from(10_000_000u128));
+    assert_eq!(pair_stats.nft_cost_basis, Uint128::from(10_000_000u128));
+
+    // NFTs bought from the pair realize the sale amount against the average cost basis
+    let response = router.execute_contract(
+        bidder,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(10_700_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let pair_stats = query_pair_stats(&router, &test_pair.address);
+    assert_eq!(
+        pair_stats,
+        PairStats {
+            sell_to_pair_count: 1u64,
+            sell_to_pair_volume: Uint128::from(10_000_000u128),
+            buy_from_pair_count: 1u64,
+            buy_from_pair_volume: Uint128::from(10_700_000u128),
+            swap_fees_earned: Uint128::from(200_000u128),
+            royalties_paid: Uint128::from(1_000_000u128),
+            fair_burn_paid: Uint128::from(200_000u128),
+            nft_cost_basis: Uint128::from(5_000_000u128),
+            realized_gains: Uint128::from(5_000_000u128),
+            realized_losses: Uint128::zero(),
+        }
+    );
+
+    // Withdrawn NFTs take their cost basis with them
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::WithdrawAnyNfts {
+            collection: collection.to_string(),
+            limit: 10u32,
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair_stats = query_pair_stats(&router, &test_pair.address);
+    assert_eq!(pair_stats.nft_cost_basis, Uint128::zero());
+    assert_eq!(pair_stats.realized_gains, Uint128::from(5_000_000u128));
+}
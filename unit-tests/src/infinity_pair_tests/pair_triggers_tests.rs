@@ -0,0 +1,265 @@
+use crate::helpers::nft_functions::{approve, mint_to};
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config, query_pair};
+use crate::setup::setup_accounts::{setup_addtl_account, MarketAccounts, INITIAL_BALANCE};
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::state::{BondingCurve, PairConfig, PairStats, PairTriggers, PairType};
+use sg_multi_test::StargazeApp;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn fired_triggers(response: &AppResponse) -> Vec<String> {
+    response
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm-pair-trigger")
+        .flat_map(|event| event.attributes.iter().filter(|attr| attr.key == "trigger"))
+        .map(|attr| attr.value.clone())
+        .collect()
+}
+
+fn sell_nft_to_pair(
+    router: &mut StargazeApp,
+    creator: &Addr,
+    minter: &Addr,
+    collection: &Addr,
+    pair_address: &Addr,
+) -> AppResponse {
+    let seller = setup_addtl_account(router, "seller", INITIAL_BALANCE).unwrap();
+    let token_id = mint_to(router, creator, &seller, minter);
+    approve(router, &seller, collection, pair_address, token_id.clone());
+
+    router
+        .execute_contract(
+            seller,
+            pair_address.clone(),
+            &InfinityPairExecuteMsg::SwapNftForTokens {
+                token_id,
+                min_output: coin(1u128, NATIVE_DENOM),
+                asset_recipient: None,
+            },
+            &[],
+        )
+        .unwrap()
+}
+
+#[test]
+fn try_pair_min_spot_price_and_min_inventory_triggers() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            triggers: PairTriggers {
+                min_spot_price: Some(Uint128::from(9_500_000u128)),
+                min_inventory: Some(3u64),
+                max_realized_losses: None,
+            },
+            ..pair_config(
+                PairType::Trade {
+                    swap_fee_percent: Decimal::zero(),
+                    reinvest_tokens: true,
+                    reinvest_nfts: true,
+                },
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            )
+        },
+        2u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // Spot price falling below the min spot price stops "sell to" trades
+    let response =
+        sell_nft_to_pair(&mut router, &creator, &minter, &collection, &test_pair.address);
+    assert_eq!(fired_triggers(&response), vec!["min_spot_price".to_string()]);
+
+    let pair = query_pair(&router, &test_pair.address);
+    assert!(pair.config.is_active);
+    assert!(pair.internal.sell_to_pair_halted);
+    assert_eq!(pair.internal.sell_to_pair_quote_summary, None);
+    assert_eq!(
+        pair.internal.buy_from_pair_quote_summary.unwrap().seller_amount,
+        Uint128::from(10_000_000u128)
+    );
+
+    // Inventory falling below the min inventory stops "buy from" trades
+    let response = router
+        .execute_contract(
+            bidder,
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+                asset_recipient: None,
+            },
+            &[coin(10_600_000u128, NATIVE_DENOM)],
+        )
+        .unwrap();
+    assert_eq!(fired_triggers(&response), vec!["min_inventory".to_string()]);
+
+    let pair = query_pair(&router, &test_pair.address);
+    assert!(pair.internal.buy_from_pair_halted);
+    assert_eq!(pair.internal.sell_to_pair_quote_summary, None);
+    assert_eq!(pair.internal.buy_from_pair_quote_summary, None);
+
+    // Reactivating the pair resumes trading on both sides
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: Some(true),
+            pair_type: None,
+            bonding_curve: None,
+            asset_recipient: None,
+            nft_selection: None,
+            limits: None,
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair = query_pair(&router, &test_pair.address);
+    assert!(!pair.internal.sell_to_pair_halted);
+    assert!(!pair.internal.buy_from_pair_halted);
+    assert!(pair.internal.sell_to_pair_quote_summary.is_some());
+    assert!(pair.internal.buy_from_pair_quote_summary.is_some());
+}
+
+#[test]
+fn try_pair_max_realized_losses_trigger() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            triggers: PairTriggers {
+                min_spot_price: None,
+                min_inventory: None,
+                max_realized_losses: Some(Uint128::from(1_000_000u128)),
+            },
+            ..pair_config(
+                PairType::Trade {
+                    swap_fee_percent: Decimal::zero(),
+                    reinvest_tokens: true,
+                    reinvest_nfts: true,
+                },
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            )
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // The pair buys an NFT at 10M
+    let response =
+        sell_nft_to_pair(&mut router, &creator, &minter, &collection, &test_pair.address);
+    assert!(fired_triggers(&response).is_empty());
+
+    // The owner lowers the price, so the NFT is sold at a loss
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: None,
+            pair_type: None,
+            bonding_curve: Some(BondingCurve::Linear {
+                spot_price: Uint128::from(5_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            }),
+            asset_recipient: None,
+            nft_selection: None,
+            limits: None,
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Realized losses above the max realized losses deactivate the pair
+    let response = router
+        .execute_contract(
+            bidder,
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+                asset_recipient: None,
+            },
+            &[coin(6_360_000u128, NATIVE_DENOM)],
+        )
+        .unwrap();
+    assert_eq!(fired_triggers(&response), vec!["max_realized_losses".to_string()]);
+
+    let pair_stats = router
+        .wrap()
+        .query_wasm_smart::<PairStats>(&test_pair.address, &InfinityPairQueryMsg::PairStats {})
+        .unwrap();
+    assert_eq!(pair_stats.realized_losses, Uint128::from(4_000_000u128));
+
+    let pair = query_pair(&router, &test_pair.address);
+    assert!(!pair.config.is_active);
+    assert_eq!(pair.internal.sell_to_pair_quote_summary, None);
+    assert_eq!(pair.internal.buy_from_pair_quote_summary, None);
+}
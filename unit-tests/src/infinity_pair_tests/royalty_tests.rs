@@ -0,0 +1,396 @@
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
+use cw_multi_test::Executor;
+use infinity_global::msg::{
+    ExecuteMsg as InfinityGlobalExecuteMsg, QueryMsg as InfinityGlobalQueryMsg,
+};
+use infinity_global::{RoyaltySettings, RoyaltySplit};
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairType, TokenPayment};
+use infinity_shared::InfinityError;
+use sg721::{ExecuteMsg as Sg721ExecuteMsg, RoyaltyInfoResponse, UpdateCollectionInfoMsg};
+use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_set_royalty_settings() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let royalty_settings = RoyaltySettings {
+        splits: vec![
+            RoyaltySplit {
+                recipient: "recipient-a".to_string(),
+                weight: Decimal::percent(60),
+            },
+            RoyaltySplit {
+                recipient: "recipient-b".to_string(),
+                weight: Decimal::percent(40),
+            },
+        ],
+        min_royalty_fee_percent: None,
+    };
+
+    // Only the collection creator can set royalty settings
+    let response = router.execute_contract(
+        owner,
+        infinity_global.clone(),
+        &InfinityGlobalExecuteMsg::SetRoyaltySettings {
+            collection: collection.to_string(),
+            royalty_settings: Some(royalty_settings.clone()),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the creator of the collection".to_string())
+            .to_string(),
+    );
+
+    // Split weights must sum to one
+    let response = router.execute_contract(
+        creator.clone(),
+        infinity_global.clone(),
+        &InfinityGlobalExecuteMsg::SetRoyaltySettings {
+            collection: collection.to_string(),
+            royalty_settings: Some(RoyaltySettings {
+                splits: vec![RoyaltySplit {
+                    recipient: "recipient-a".to_string(),
+                    weight: Decimal::percent(60),
+                }],
+                min_royalty_fee_percent: None,
+            }),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("royalty split weights must sum to one".to_string())
+            .to_string(),
+    );
+
+    let response = router.execute_contract(
+        creator.clone(),
+        infinity_global.clone(),
+        &InfinityGlobalExecuteMsg::SetRoyaltySettings {
+            collection: collection.to_string(),
+            royalty_settings: Some(royalty_settings.clone()),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let stored_royalty_settings = router
+        .wrap()
+        .query_wasm_smart::<Option<RoyaltySettings<Addr>>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::RoyaltySettings {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(stored_royalty_settings, Some(royalty_settings.str_to_addr(router.api()).unwrap()));
+
+    // Royalty settings can be removed
+    let response = router.execute_contract(
+        creator,
+        infinity_global.clone(),
+        &InfinityGlobalExecuteMsg::SetRoyaltySettings {
+            collection: collection.to_string(),
+            royalty_settings: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let stored_royalty_settings = router
+        .wrap()
+        .query_wasm_smart::<Option<RoyaltySettings<Addr>>>(
+            infinity_global,
+            &InfinityGlobalQueryMsg::RoyaltySettings {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(stored_royalty_settings, None);
+}
+
+#[test]
+fn try_pair_royalty_splits_and_min_royalty_fee() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let collection_info = router
+        .wrap()
+        .query_wasm_smart::<CollectionInfoResponse>(
+            collection.clone(),
+            &Sg721QueryMsg::CollectionInfo {},
+        )
+        .unwrap();
+    let royalty_recipient =
+        Addr::unchecked(collection_info.royalty_info.as_ref().unwrap().payment_address.clone());
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        10u64,
+        Uint128::zero(),
+    );
+
+    // Without royalty settings the full royalty is paid to the royalty recipient
+    assert_eq!(
+        test_pair.pair.internal.buy_from_pair_quote_summary.unwrap().royalties,
+        vec![TokenPayment {
+            recipient: royalty_recipient,
+            amount: Uint128::from(500_000u128),
+        }]
+    );
+
+    let recipient_a = Addr::unchecked("recipient-a");
+    let recipient_b = Addr::unchecked("recipient-b");
+
+    let mut set_royalty_settings_and_refresh =
+        |min_royalty_fee_percent: Option<Decimal>| -> Vec<TokenPayment> {
+            let response = router.execute_contract(
+                creator.clone(),
+                infinity_global.clone(),
+                &InfinityGlobalExecuteMsg::SetRoyaltySettings {
+                    collection: collection.to_string(),
+                    royalty_settings: Some(RoyaltySettings {
+                        splits: vec![
+                            RoyaltySplit {
+                                recipient: recipient_a.to_string(),
+                                weight: Decimal::percent(60),
+                            },
+                            RoyaltySplit {
+                                recipient: recipient_b.to_string(),
+                                weight: Decimal::percent(40),
+                            },
+                        ],
+                        min_royalty_fee_percent,
+                    }),
+                },
+                &[],
+            );
+            assert!(response.is_ok());
+
+            let response = router.execute_contract(
+                owner.clone(),
+                test_pair.address.clone(),
+                &InfinityPairExecuteMsg::UpdatePairConfig {
+                    is_active: Some(true),
+                    pair_type: None,
+                    bonding_curve: None,
+                    asset_recipient: None,
+                    nft_selection: None,
+                    limits: None,
+                    schedule: None,
+                    sweep_policy: None,
+                    triggers: None,
+                },
+                &[],
+            );
+            assert!(response.is_ok());
+
+            router
+                .wrap()
+                .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+                .unwrap()
+                .internal
+                .buy_from_pair_quote_summary
+                .unwrap()
+                .royalties
+        };
+
+    // Royalty is split between recipients by weight
+    assert_eq!(
+        set_royalty_settings_and_refresh(None),
+        vec![
+            TokenPayment {
+                recipient: recipient_a.clone(),
+                amount: Uint128::from(300_000u128),
+            },
+            TokenPayment {
+                recipient: recipient_b.clone(),
+                amount: Uint128::from(200_000u128),
+            },
+        ]
+    );
+
+    // Minimum royalty fee raises the royalty above the registry share
+    assert_eq!(
+        set_royalty_settings_and_refresh(Some(Decimal::percent(8))),
+        vec![
+            TokenPayment {
+                recipient: recipient_a.clone(),
+                amount: Uint128::from(480_000u128),
+            },
+            TokenPayment {
+                recipient: recipient_b.clone(),
+                amount: Uint128::from(320_000u128),
+            },
+        ]
+    );
+
+    // Minimum royalty fee is capped by the max royalty fee in the global config
+    assert_eq!(
+        set_royalty_settings_and_refresh(Some(Decimal::percent(20))),
+        vec![
+            TokenPayment {
+                recipient: recipient_a,
+                amount: Uint128::from(600_000u128),
+            },
+            TokenPayment {
+                recipient: recipient_b,
+                amount: Uint128::from(400_000u128),
+            },
+        ]
+    );
+}
+
+#[test]
+fn try_pair_min_royalty_fee_without_royalty_recipient() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    // Remove the royalty info from the collection, leaving no royalty recipient
+    let response = router.execute_contract(
+        creator.clone(),
+        collection.clone(),
+        &Sg721ExecuteMsg::<Empty, Empty>::UpdateCollectionInfo {
+            collection_info: UpdateCollectionInfoMsg::<RoyaltyInfoResponse> {
+                description: None,
+                image: None,
+                external_link: None,
+                explicit_content: None,
+                royalty_info: Some(None),
+            },
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let collection_info = router
+        .wrap()
+        .query_wasm_smart::<CollectionInfoResponse>(
+            collection.clone(),
+            &Sg721QueryMsg::CollectionInfo {},
+        )
+        .unwrap();
+    assert!(collection_info.royalty_info.is_none());
+
+    // The minimum royalty fee is set without any splits
+    let response = router.execute_contract(
+        creator.clone(),
+        infinity_global.clone(),
+        &InfinityGlobalExecuteMsg::SetRoyaltySettings {
+            collection: collection.to_string(),
+            royalty_settings: Some(RoyaltySettings {
+                splits: vec![],
+                min_royalty_fee_percent: Some(Decimal::percent(5)),
+            }),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        10u64,
+        Uint128::zero(),
+    );
+
+    // The minimum royalty is paid to the collection creator
+    assert_eq!(
+        test_pair.pair.internal.buy_from_pair_quote_summary.unwrap().royalties,
+        vec![TokenPayment {
+            recipient: Addr::unchecked(collection_info.creator),
+            amount: Uint128::from(500_000u128),
+        }]
+    );
+}
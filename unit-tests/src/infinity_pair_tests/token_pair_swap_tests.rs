@@ -1,5 +1,5 @@
 use crate::helpers::nft_functions::{approve, assert_nft_owner, mint_to};
-use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
 use crate::helpers::utils::assert_error;
 use crate::setup::setup_accounts::{setup_addtl_account, MarketAccounts, INITIAL_BALANCE};
 use crate::setup::setup_infinity_contracts::UOSMO;
@@ -65,13 +65,14 @@ fn try_token_pair_invalid_swaps() {
         &creator,
         &owner,
         PairConfig {
-            pair_type: PairType::Token,
-            bonding_curve: BondingCurve::Linear {
-                spot_price: Uint128::from(10_000_000u128),
-                delta: Uint128::from(1_000_000u128),
-            },
             is_active: false,
-            asset_recipient: None,
+            ..pair_config(
+                PairType::Token,
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            )
         },
         0u64,
         Uint128::from(100_000_000u128),
@@ -105,6 +106,11 @@ fn try_token_pair_invalid_swaps() {
             pair_type: None,
             bonding_curve: None,
             asset_recipient: None,
+            nft_selection: None,
+            limits: None,
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
         },
         &[],
     );
@@ -122,12 +128,12 @@ fn try_token_pair_invalid_swaps() {
                 recipient: global_config.fair_burn,
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(9_400_000u128),
         })
@@ -229,15 +235,13 @@ fn try_token_pair_linear_user_submits_nfts_swap() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Token,
-            bonding_curve: BondingCurve::Linear {
+        pair_config(
+            PairType::Token,
+            BondingCurve::Linear {
                 spot_price: Uint128::from(10_000_000u128),
                 delta: Uint128::from(1_000_000u128),
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         0u64,
         Uint128::from(100_000_000u128),
     );
@@ -249,12 +253,12 @@ fn try_token_pair_linear_user_submits_nfts_swap() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(9_400_000u128),
         })
@@ -292,10 +296,10 @@ fn try_token_pair_linear_user_submits_nfts_swap() {
                 recipient: global_config.fair_burn,
                 amount: Uint128::from(90_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(collection_info.royalty_info.unwrap().payment_address),
                 amount: Uint128::from(450_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(8_460_000u128),
         })
@@ -351,15 +355,13 @@ fn try_token_pair_exponential_user_submits_nfts_swap() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Token,
-            bonding_curve: BondingCurve::Exponential {
+        pair_config(
+            PairType::Token,
+            BondingCurve::Exponential {
                 spot_price: Uint128::from(10_000_000u128),
                 delta: Decimal::percent(12),
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         0u64,
         Uint128::from(100_000_000u128),
     );
@@ -371,12 +373,12 @@ fn try_token_pair_exponential_user_submits_nfts_swap() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(9_400_000u128),
         })
@@ -414,10 +416,10 @@ fn try_token_pair_exponential_user_submits_nfts_swap() {
                 recipient: global_config.fair_burn,
                 amount: Uint128::from(89_286u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(collection_info.royalty_info.unwrap().payment_address),
                 amount: Uint128::from(446_429u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(8_392_856u128),
         })
@@ -1,5 +1,5 @@
 use crate::helpers::nft_functions::{approve, approve_all, assert_nft_owner, mint_to};
-use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
 use crate::helpers::utils::assert_error;
 use crate::setup::setup_accounts::{setup_addtl_account, MarketAccounts, INITIAL_BALANCE};
 use crate::setup::setup_infinity_contracts::UOSMO;
@@ -10,7 +10,9 @@ use cw_multi_test::Executor;
 use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
 use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
 use infinity_pair::pair::Pair;
-use infinity_pair::state::{BondingCurve, PairConfig, PairType, QuoteSummary, TokenPayment};
+use infinity_pair::state::{
+    BondingCurve, PairConfig, PairType, QuoteSummary, SweepPolicy, TokenPayment,
+};
 use infinity_pair::ContractError;
 use infinity_shared::InfinityError;
 use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
@@ -66,17 +68,18 @@ fn try_trade_pair_invalid_swaps() {
         &creator,
         &owner,
         PairConfig {
-            pair_type: PairType::Trade {
-                swap_fee_percent: Decimal::percent(0),
-                reinvest_tokens: false,
-                reinvest_nfts: false,
-            },
-            bonding_curve: BondingCurve::Linear {
-                spot_price: Uint128::from(10_000_000u128),
-                delta: Uint128::from(1_000_000u128),
-            },
             is_active: false,
-            asset_recipient: None,
+            ..pair_config(
+                PairType::Trade {
+                    swap_fee_percent: Decimal::percent(0),
+                    reinvest_tokens: false,
+                    reinvest_nfts: false,
+                },
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            )
         },
         0u64,
         Uint128::zero(),
@@ -111,6 +114,11 @@ fn try_trade_pair_invalid_swaps() {
             pair_type: None,
             bonding_curve: None,
             asset_recipient: None,
+            nft_selection: None,
+            limits: None,
+            schedule: None,
+            sweep_policy: None,
+            triggers: None,
         },
         &[],
     );
@@ -184,12 +192,12 @@ fn try_trade_pair_invalid_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(9_400_000u128),
         })
@@ -201,12 +209,12 @@ fn try_trade_pair_invalid_swaps() {
                 recipient: global_config.fair_burn,
                 amount: Uint128::from(110_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(550_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(11_000_000u128),
         })
@@ -303,19 +311,17 @@ fn try_trade_pair_linear_swaps() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Trade {
+        pair_config(
+            PairType::Trade {
                 swap_fee_percent: Decimal::zero(),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
             },
-            bonding_curve: BondingCurve::Linear {
+            BondingCurve::Linear {
                 spot_price: Uint128::from(10_000_000u128),
                 delta: Uint128::from(1_000_000u128),
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         10u64,
         Uint128::from(100_000_000u128),
     );
@@ -327,12 +333,12 @@ fn try_trade_pair_linear_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(9_400_000u128),
         })
@@ -344,12 +350,12 @@ fn try_trade_pair_linear_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(110_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(550_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(11_000_000u128),
         })
@@ -382,12 +388,12 @@ fn try_trade_pair_linear_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(110_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(550_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(10_340_000u128),
         })
@@ -399,12 +405,12 @@ fn try_trade_pair_linear_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(120_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(600_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(12_000_000u128),
         })
@@ -437,12 +443,12 @@ fn try_trade_pair_linear_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(9_400_000u128),
         })
@@ -454,12 +460,12 @@ fn try_trade_pair_linear_swaps() {
                 recipient: global_config.fair_burn,
                 amount: Uint128::from(110_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(550_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(11_000_000u128),
         })
@@ -514,19 +520,17 @@ fn try_trade_pair_exponential_swaps() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Trade {
+        pair_config(
+            PairType::Trade {
                 swap_fee_percent: Decimal::zero(),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
             },
-            bonding_curve: BondingCurve::Exponential {
+            BondingCurve::Exponential {
                 spot_price: Uint128::from(10_000_000u128),
                 delta: Decimal::percent(6),
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         10u64,
         Uint128::from(100_000_000u128),
     );
@@ -538,12 +542,12 @@ fn try_trade_pair_exponential_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(9_400_000u128),
         })
@@ -555,12 +559,12 @@ fn try_trade_pair_exponential_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(106_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(530_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(10_600_000u128),
         })
@@ -593,12 +597,12 @@ fn try_trade_pair_exponential_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(106_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(530_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(9_964_000u128),
         })
@@ -610,12 +614,12 @@ fn try_trade_pair_exponential_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(112_360u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(561_800u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(11_236_000u128),
         })
@@ -648,12 +652,12 @@ fn try_trade_pair_exponential_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(9_400_000u128),
         })
@@ -665,12 +669,12 @@ fn try_trade_pair_exponential_swaps() {
                 recipient: global_config.fair_burn,
                 amount: Uint128::from(106_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(530_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(10_600_000u128),
         })
@@ -725,16 +729,14 @@ fn try_trade_pair_constant_product_swaps() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Trade {
+        pair_config(
+            PairType::Trade {
                 swap_fee_percent: Decimal::zero(),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
             },
-            bonding_curve: BondingCurve::ConstantProduct,
-            is_active: true,
-            asset_recipient: None,
-        },
+            BondingCurve::ConstantProduct,
+        ),
         10u64,
         Uint128::from(100_000_000u128),
     );
@@ -746,12 +748,12 @@ fn try_trade_pair_constant_product_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(90_910u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(454_546u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(8_545_453u128),
         })
@@ -763,12 +765,12 @@ fn try_trade_pair_constant_product_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(111_112u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(555_556u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(11_111_112u128),
         })
@@ -801,12 +803,12 @@ fn try_trade_pair_constant_product_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(100_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(500_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(9_400_000u128),
         })
@@ -818,12 +820,12 @@ fn try_trade_pair_constant_product_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(125_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(625_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(12_500_000u128),
         })
@@ -856,12 +858,12 @@ fn try_trade_pair_constant_product_swaps() {
                 recipient: global_config.fair_burn.clone(),
                 amount: Uint128::from(90_000u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(450_000u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(8_460_000u128),
         })
@@ -873,14 +875,96 @@ fn try_trade_pair_constant_product_swaps() {
                 recipient: global_config.fair_burn,
                 amount: Uint128::from(112_500u128),
             },
-            royalty: Some(TokenPayment {
+            royalties: vec![TokenPayment {
                 recipient: Addr::unchecked(
                     collection_info.royalty_info.as_ref().unwrap().payment_address.clone()
                 ),
                 amount: Uint128::from(562_500u128),
-            }),
+            }],
             swap: None,
             seller_amount: Uint128::from(11_250_000u128),
         })
     );
 }
+
+#[test]
+fn try_trade_pair_sweep_tokens() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            sweep_policy: SweepPolicy::Threshold {
+                max_total_tokens: Uint128::from(25_000_000u128),
+            },
+            ..pair_config(
+                PairType::Trade {
+                    swap_fee_percent: Decimal::zero(),
+                    reinvest_tokens: true,
+                    reinvest_nfts: true,
+                },
+                BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+            )
+        },
+        5u64,
+        Uint128::from(20_000_000u128),
+    );
+
+    let owner_balance_before = router.wrap().query_balance(&owner, NATIVE_DENOM).unwrap();
+
+    // The sale amount is reinvested, and tokens above the threshold are swept to the owner
+    let response = router.execute_contract(
+        bidder,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(11_660_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    assert!(response.unwrap().events.iter().any(|e| e.ty == "wasm-sweep-tokens"));
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.total_tokens, Uint128::from(25_000_000u128));
+
+    let owner_balance_after = router.wrap().query_balance(&owner, NATIVE_DENOM).unwrap();
+    assert_eq!(
+        owner_balance_after.amount - owner_balance_before.amount,
+        Uint128::from(6_000_000u128)
+    );
+
+    let pair_balance = router.wrap().query_balance(&test_pair.address, NATIVE_DENOM).unwrap();
+    assert_eq!(pair_balance.amount, Uint128::from(25_000_000u128));
+}
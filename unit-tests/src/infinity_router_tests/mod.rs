@@ -1,4 +1,8 @@
 #[cfg(test)]
+mod multi_swap_router_tests;
+#[cfg(test)]
 mod nfts_for_tokens_router_tests;
 #[cfg(test)]
+mod router_callback_tests;
+#[cfg(test)]
 mod tokens_for_nfts_router_tests;
@@ -0,0 +1,209 @@
+use crate::helpers::nft_functions::{approve, assert_nft_owner, mint_to};
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{minter_two_collections, setup_infinity_test, InfinityTestSetup};
+
+use cosmwasm_std::{coin, from_binary, Addr, Uint128};
+use cw_multi_test::Executor;
+use cw_utils::parse_execute_response_data;
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_pair::msg::TransactionType;
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_router::msg::{
+    ExecuteMsg as InfinityRouterExecuteMsg, QueryMsg as InfinityRouterQueryMsg, SellOrder, SwapLeg,
+    SwapResponse,
+};
+use infinity_router::nfts_for_tokens_iterators::types::NftForTokensQuote;
+use infinity_router::tokens_for_nfts_iterators::types::TokensForNftQuote;
+use infinity_shared::InfinityError;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn linear_pair_config(pair_type: PairType) -> PairConfig<String> {
+    pair_config(
+        pair_type,
+        BondingCurve::Linear {
+            spot_price: Uint128::from(10_000_000u128),
+            delta: Uint128::from(1_000_000u128),
+        },
+    )
+}
+
+#[test]
+fn try_router_multi_swap() {
+    let vt = minter_two_collections(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let minter_0 = collection_response_vec[0].minter.clone().unwrap();
+    let collection_0 = collection_response_vec[0].collection.clone().unwrap();
+    let minter_1 = collection_response_vec[1].minter.clone().unwrap();
+    let collection_1 = collection_response_vec[1].collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    // An NFT pair in the first collection, and a token pair in the second collection
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter_0,
+        &collection_0,
+        &creator,
+        &owner,
+        linear_pair_config(PairType::Nft),
+        3u64,
+        Uint128::zero(),
+    );
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter_1,
+        &collection_1,
+        &creator,
+        &owner,
+        linear_pair_config(PairType::Token),
+        0u64,
+        Uint128::from(1_000_000_000u128),
+    );
+
+    let buy_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::TokensForNfts {
+                collection: collection_0.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 2,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    let sell_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<NftForTokensQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::NftsForTokens {
+                collection: collection_1.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 1,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter_1);
+    approve(&mut router, &bidder, &collection_1, &global_config.infinity_router, token_id.clone());
+
+    // The max inputs exceed the quotes, the difference is refunded
+    let excess = Uint128::from(1_000_000u128);
+    let max_inputs = buy_quotes.iter().map(|q| q.amount + excess).collect::<Vec<Uint128>>();
+    let total_max_inputs = max_inputs.iter().sum::<Uint128>();
+
+    let legs = vec![
+        SwapLeg::TokensForNfts {
+            collection: collection_0.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs,
+            filter_sources: None,
+        },
+        SwapLeg::NftsForTokens {
+            collection: collection_1.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders: vec![SellOrder {
+                input_token_id: token_id.clone(),
+                min_output: sell_quotes[0].amount,
+            }],
+            filter_sources: None,
+        },
+    ];
+
+    // Legs on the same side of the same collection and denom must be merged into a single leg
+    let mut duplicate_legs = legs.clone();
+    duplicate_legs.push(legs[0].clone());
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::MultiSwap {
+            legs: duplicate_legs,
+            swap_params: None,
+        },
+        &[coin(total_max_inputs.u128() * 2, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!(
+            "duplicate swap leg for collection {} and denom {}",
+            collection_0, NATIVE_DENOM
+        ))
+        .to_string(),
+    );
+
+    // The funds must equal the max inputs of the legs
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::MultiSwap {
+            legs: legs.clone(),
+            swap_params: None,
+        },
+        &[coin(total_max_inputs.u128() - 1, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        InfinityError::InsufficientFunds {
+            expected: coin(total_max_inputs.u128(), NATIVE_DENOM),
+        }
+        .to_string(),
+    );
+
+    let bidder_balance = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::MultiSwap {
+            legs,
+            swap_params: None,
+        },
+        &[coin(total_max_inputs.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let data = parse_execute_response_data(&response.unwrap().data.unwrap()).unwrap().data;
+    let swap_response = from_binary::<SwapResponse>(&data.unwrap()).unwrap();
+    assert_eq!(swap_response.swaps.len(), 3);
+    for swap in &swap_response.swaps[0..2] {
+        assert_eq!(swap.transaction_type, TransactionType::UserSubmitsTokens);
+        assert_nft_owner(&router, &collection_0, swap.token_id.clone(), &bidder);
+    }
+    assert_eq!(swap_response.swaps[2].transaction_type, TransactionType::UserSubmitsNfts);
+    assert_eq!(swap_response.swaps[2].token_id, token_id);
+    assert_nft_owner(&router, &collection_1, token_id, &owner);
+
+    // The bidder pays the buy quotes, receives the sell quote, and is refunded the excess
+    let total_paid = buy_quotes.iter().map(|q| q.amount).sum::<Uint128>();
+    let new_bidder_balance = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(new_bidder_balance, bidder_balance - total_paid + sell_quotes[0].amount);
+}
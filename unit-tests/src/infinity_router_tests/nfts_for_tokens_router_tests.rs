@@ -1,14 +1,20 @@
 use crate::helpers::nft_functions::{approve, assert_nft_owner, mint_to};
-use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
 use crate::setup::setup_accounts::MarketAccounts;
 use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{from_binary, Addr, Decimal, Uint128};
 use cw_multi_test::Executor;
-use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
-use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use cw_utils::parse_execute_response_data;
+use infinity_global::msg::{
+    ExecuteMsg as InfinityGlobalExecuteMsg, QueryMsg as InfinityGlobalQueryMsg,
+};
+use infinity_global::{GlobalConfig, RoyaltySettings, RoyaltySplit};
+use infinity_pair::msg::TransactionType;
+use infinity_pair::state::{BondingCurve, PairType};
 use infinity_router::msg::{
     ExecuteMsg as InfinityRouterExecuteMsg, QueryMsg as InfinityRouterQueryMsg, SellOrder,
+    SwapParams, SwapResponse,
 };
 use infinity_router::nfts_for_tokens_iterators::types::NftForTokensQuote;
 use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
@@ -65,15 +71,13 @@ fn try_router_nfts_for_tokens_swap_simple() {
             &collection,
             &creator,
             &owner,
-            PairConfig {
-                pair_type: PairType::Token,
-                bonding_curve: BondingCurve::Linear {
+            pair_config(
+                PairType::Token,
+                BondingCurve::Linear {
                     spot_price: Uint128::from(100_000_000u128),
                     delta: Uint128::from(1_000_000u128),
                 },
-                is_active: true,
-                asset_recipient: None,
-            },
+            ),
             0u64,
             Uint128::from(10_000_000_000u128),
         ));
@@ -131,4 +135,290 @@ fn try_router_nfts_for_tokens_swap_simple() {
 
     assert_nft_owner(&router, &collection, token_ids[0].clone(), &owner);
     assert_nft_owner(&router, &collection, token_ids[1].clone(), &owner);
+
+    // The NFTs are sent to the pairs, the router does not approve the pairs to transfer them
+    let response = response.unwrap();
+    assert!(!response.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "action" && attr.value == "approve")));
+
+    // The router response data describes each swap
+    let data = parse_execute_response_data(&response.data.unwrap()).unwrap().data;
+    let swap_response = from_binary::<SwapResponse>(&data.unwrap()).unwrap();
+    assert_eq!(swap_response.swaps.len(), num_nfts);
+    for (idx, swap) in swap_response.swaps.iter().enumerate() {
+        assert_eq!(swap.pair, quotes[idx].address);
+        assert_eq!(swap.transaction_type, TransactionType::UserSubmitsNfts);
+        assert_eq!(swap.token_id, token_ids[idx]);
+        assert_eq!(swap.seller_amount, quotes[idx].amount);
+        assert_eq!(swap.price.denom, NATIVE_DENOM);
+        assert_eq!(
+            swap.price.amount,
+            swap.fair_burn_fee + swap.royalty_fee + swap.swap_fee + swap.seller_amount
+        );
+    }
+}
+
+#[test]
+fn try_router_nfts_for_tokens_swap_referral() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Token,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(100_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        0u64,
+        Uint128::from(10_000_000_000u128),
+    );
+
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<NftForTokensQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::NftsForTokens {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 2,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..quotes.len() {
+        let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+        approve(
+            &mut router,
+            &bidder,
+            &collection,
+            &global_config.infinity_router,
+            token_id.clone(),
+        );
+        token_ids.push(token_id);
+    }
+
+    let referrer = Addr::unchecked("referrer");
+    let seller_recipient = Addr::unchecked("seller_recipient");
+    let referral_fee_percent = Decimal::percent(1);
+    let referral_fees =
+        quotes.iter().map(|q| q.amount.mul_ceil(referral_fee_percent)).collect::<Vec<Uint128>>();
+
+    let swap_msg = |min_outputs: Vec<Uint128>| InfinityRouterExecuteMsg::SwapNftsForTokens {
+        collection: collection.to_string(),
+        denom: NATIVE_DENOM.to_string(),
+        sell_orders: token_ids
+            .iter()
+            .zip(min_outputs)
+            .map(|(token_id, min_output)| SellOrder {
+                input_token_id: token_id.clone(),
+                min_output,
+            })
+            .collect(),
+        swap_params: Some(SwapParams {
+            robust: None,
+            asset_recipient: Some(seller_recipient.to_string()),
+            referrer: Some(referrer.to_string()),
+            referral_fee_percent: Some(referral_fee_percent),
+            callback: None,
+        }),
+        filter_sources: None,
+    };
+
+    // The referral fee is deducted from the proceeds, so the quotes no longer meet the min outputs
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &swap_msg(quotes.iter().map(|q| q.amount).collect()),
+        &[],
+    );
+    assert!(response.is_err());
+
+    // Min outputs net of the referral fee are met
+    let min_outputs =
+        quotes.iter().zip(&referral_fees).map(|(q, f)| q.amount - f).collect::<Vec<Uint128>>();
+    let response = router.execute_contract(
+        bidder,
+        global_config.infinity_router.clone(),
+        &swap_msg(min_outputs.clone()),
+        &[],
+    );
+    assert!(response.is_ok());
+
+    for token_id in token_ids {
+        assert_nft_owner(&router, &collection, token_id, &owner);
+    }
+
+    // The router forwards the proceeds net of the referral fee, and pays the referrer
+    let seller_balance = router.wrap().query_balance(&seller_recipient, NATIVE_DENOM).unwrap();
+    assert_eq!(seller_balance.amount, min_outputs.iter().sum::<Uint128>());
+
+    let referrer_balance = router.wrap().query_balance(&referrer, NATIVE_DENOM).unwrap();
+    assert_eq!(referrer_balance.amount, referral_fees.iter().sum::<Uint128>());
+
+    let router_balance =
+        router.wrap().query_balance(&global_config.infinity_router, NATIVE_DENOM).unwrap();
+    assert_eq!(router_balance.amount, Uint128::zero());
+}
+
+#[test]
+fn try_router_nfts_for_tokens_swap_royalty_splits() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    // The royalty is split between two recipients
+    let recipient_a = Addr::unchecked("recipient-a");
+    let recipient_b = Addr::unchecked("recipient-b");
+    let response = router.execute_contract(
+        creator.clone(),
+        infinity_global.clone(),
+        &InfinityGlobalExecuteMsg::SetRoyaltySettings {
+            collection: collection.to_string(),
+            royalty_settings: Some(RoyaltySettings {
+                splits: vec![
+                    RoyaltySplit {
+                        recipient: recipient_a.to_string(),
+                        weight: Decimal::percent(60),
+                    },
+                    RoyaltySplit {
+                        recipient: recipient_b.to_string(),
+                        weight: Decimal::percent(40),
+                    },
+                ],
+                min_royalty_fee_percent: None,
+            }),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Token,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let quote_summary = test_pair.pair.internal.sell_to_pair_quote_summary.unwrap();
+    assert_eq!(quote_summary.royalties.len(), 2);
+
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    approve(&mut router, &bidder, &collection, &global_config.infinity_router, token_id.clone());
+
+    let response = router.execute_contract(
+        bidder,
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders: vec![SellOrder {
+                input_token_id: token_id.clone(),
+                min_output: quote_summary.seller_amount,
+            }],
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // The swap data collected from the pair swap event sums the royalties of every recipient
+    let data = parse_execute_response_data(&response.unwrap().data.unwrap()).unwrap().data;
+    let swap_response = from_binary::<SwapResponse>(&data.unwrap()).unwrap();
+    assert_eq!(swap_response.swaps.len(), 1);
+
+    let swap = &swap_response.swaps[0];
+    assert_eq!(swap.pair, test_pair.address);
+    assert_eq!(swap.token_id, token_id);
+    assert_eq!(swap.royalty_fee, quote_summary.royalty_amount());
+    assert_eq!(swap.seller_amount, quote_summary.seller_amount);
+    assert_eq!(swap.price.amount, quote_summary.total());
+
+    let royalties_received = [recipient_a, recipient_b]
+        .iter()
+        .map(|recipient| router.wrap().query_balance(recipient, NATIVE_DENOM).unwrap().amount)
+        .collect::<Vec<Uint128>>();
+    assert_eq!(
+        royalties_received,
+        quote_summary.royalties.iter().map(|royalty| royalty.amount).collect::<Vec<Uint128>>()
+    );
+    assert_eq!(royalties_received.iter().sum::<Uint128>(), swap.royalty_fee);
 }
@@ -0,0 +1,356 @@
+use crate::helpers::nft_functions::{approve, assert_nft_owner, mint_to};
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::setup_contracts::{setup_mock_callback, MockCallbackExecuteMsg};
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, to_binary, Addr, Uint128};
+use cw721::Cw721ExecuteMsg;
+use cw_multi_test::Executor;
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_pair::msg::{NftDepositsResponse, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_router::msg::{
+    ExecuteMsg as InfinityRouterExecuteMsg, QueryMsg as InfinityRouterQueryMsg, SellOrder,
+    SwapCallback, SwapParams,
+};
+use infinity_router::nfts_for_tokens_iterators::types::NftForTokensQuote;
+use infinity_router::tokens_for_nfts_iterators::types::TokensForNftQuote;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn linear_pair_config(pair_type: PairType) -> PairConfig<String> {
+    pair_config(
+        pair_type,
+        BondingCurve::Linear {
+            spot_price: Uint128::from(10_000_000u128),
+            delta: Uint128::from(1_000_000u128),
+        },
+    )
+}
+
+#[test]
+fn try_router_nfts_for_tokens_swap_callback() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        linear_pair_config(PairType::Token),
+        0u64,
+        Uint128::from(1_000_000_000u128),
+    );
+
+    let mock_callback = setup_mock_callback(&mut router, &creator);
+
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<NftForTokensQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::NftsForTokens {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 2,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+
+    let mut sell_orders: Vec<SellOrder> = vec![];
+    for quote in &quotes {
+        let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+        approve(
+            &mut router,
+            &bidder,
+            &collection,
+            &global_config.infinity_router,
+            token_id.clone(),
+        );
+        sell_orders.push(SellOrder {
+            input_token_id: token_id,
+            min_output: quote.amount,
+        });
+    }
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders,
+            swap_params: Some(SwapParams {
+                robust: None,
+                asset_recipient: Some(mock_callback.to_string()),
+                referrer: None,
+                referral_fee_percent: None,
+                callback: Some(SwapCallback {
+                    contract: mock_callback.to_string(),
+                    msg: to_binary(&MockCallbackExecuteMsg::Succeed {}).unwrap(),
+                }),
+            }),
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // The callback is executed once the proceeds of every swap have been delivered
+    let total_proceeds = quotes.iter().map(|q| q.amount).sum::<Uint128>();
+    let event =
+        response.unwrap().events.into_iter().find(|e| e.ty == "wasm-mock-callback").unwrap();
+    let balance = event.attributes.iter().find(|a| a.key == "balance").unwrap();
+    assert_eq!(balance.value, total_proceeds.to_string());
+
+    // The callback receives the sender of the router swap
+    let sender = event.attributes.iter().find(|a| a.key == "sender").unwrap();
+    assert_eq!(sender.value, bidder.to_string());
+}
+
+#[test]
+fn try_router_tokens_for_nfts_swap_callback() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        linear_pair_config(PairType::Nft),
+        2u64,
+        Uint128::zero(),
+    );
+
+    let mock_callback = setup_mock_callback(&mut router, &creator);
+
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::TokensForNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 2,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    let max_inputs = quotes.iter().map(|q| q.amount).collect::<Vec<Uint128>>();
+    let total_tokens = max_inputs.iter().sum::<Uint128>();
+
+    let swap_msg =
+        |callback_msg: MockCallbackExecuteMsg| InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs: max_inputs.clone(),
+            swap_params: Some(SwapParams {
+                robust: None,
+                asset_recipient: Some(mock_callback.to_string()),
+                referrer: None,
+                referral_fee_percent: None,
+                callback: Some(SwapCallback {
+                    contract: mock_callback.to_string(),
+                    msg: to_binary(&callback_msg).unwrap(),
+                }),
+            }),
+            filter_sources: None,
+        };
+
+    // A failed callback reverts the swap
+    let bidder_balance = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap();
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &swap_msg(MockCallbackExecuteMsg::Fail {}),
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    let err = response.unwrap_err();
+    assert!(err.root_cause().to_string().starts_with("CallbackError: "));
+
+    assert_eq!(router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap(), bidder_balance);
+    let nft_deposits = router
+        .wrap()
+        .query_wasm_smart::<NftDepositsResponse>(
+            &test_pair.address,
+            &InfinityPairQueryMsg::NftDeposits {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(nft_deposits.token_ids.len(), 2);
+
+    // A successful callback is executed once the NFTs have been delivered
+    let response = router.execute_contract(
+        bidder,
+        global_config.infinity_router,
+        &swap_msg(MockCallbackExecuteMsg::Succeed {}),
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    assert!(response.unwrap().events.iter().any(|e| e.ty == "wasm-mock-callback"));
+
+    for token_id in nft_deposits.token_ids {
+        assert_nft_owner(&router, &collection, token_id, &mock_callback);
+    }
+}
+
+#[test]
+fn try_router_swap_callback_requires_receive_swap_callback() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        linear_pair_config(PairType::Nft),
+        1u64,
+        Uint128::zero(),
+    );
+
+    // The bidder approves the router to sell an NFT
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    approve(&mut router, &bidder, &collection, &global_config.infinity_router, token_id.clone());
+
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::TokensForNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 1,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    let max_inputs = quotes.iter().map(|q| q.amount).collect::<Vec<Uint128>>();
+    let total_tokens = max_inputs.iter().sum::<Uint128>();
+
+    // A callback on the collection cannot transfer the NFT approved to the router, since the
+    // callback message is wrapped in a message the collection does not handle
+    let response = router.execute_contract(
+        owner.clone(),
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs,
+            swap_params: Some(SwapParams {
+                robust: None,
+                asset_recipient: None,
+                referrer: None,
+                referral_fee_percent: None,
+                callback: Some(SwapCallback {
+                    contract: collection.to_string(),
+                    msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                        recipient: owner.to_string(),
+                        token_id: token_id.clone(),
+                    })
+                    .unwrap(),
+                }),
+            }),
+            filter_sources: None,
+        },
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    let err = response.unwrap_err();
+    assert!(err.root_cause().to_string().starts_with("CallbackError: "));
+
+    assert_nft_owner(&router, &collection, token_id, &bidder);
+}
@@ -1,13 +1,20 @@
-use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::helpers::nft_functions::assert_nft_owner;
+use crate::helpers::pair_functions::{create_pair_with_deposits, pair_config};
 use crate::setup::setup_accounts::MarketAccounts;
 use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
 
-use cosmwasm_std::{coin, Addr, Uint128};
+use cosmwasm_std::{coin, from_binary, Addr, Decimal, Uint128};
 use cw_multi_test::Executor;
+use cw_utils::parse_execute_response_data;
 use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
-use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_pair::msg::{
+    ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg, QuotesResponse,
+    TransactionType,
+};
+use infinity_pair::state::{BondingCurve, NftPrice, PairType};
 use infinity_router::msg::{
-    ExecuteMsg as InfinityRouterExecuteMsg, QueryMsg as InfinityRouterQueryMsg,
+    ExecuteMsg as InfinityRouterExecuteMsg, QueryMsg as InfinityRouterQueryMsg, SwapParams,
+    SwapResponse,
 };
 use infinity_router::tokens_for_nfts_iterators::types::{TokensForNftQuote, TokensForNftSource};
 use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
@@ -62,15 +69,13 @@ fn try_router_tokens_for_nfts_swap_simple() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Nft,
-            bonding_curve: BondingCurve::Linear {
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
                 spot_price: Uint128::from(10_000_000u128),
                 delta: Uint128::from(1_000_000u128),
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         100u64,
         Uint128::zero(),
     );
@@ -83,15 +88,13 @@ fn try_router_tokens_for_nfts_swap_simple() {
         &collection,
         &creator,
         &owner,
-        PairConfig {
-            pair_type: PairType::Nft,
-            bonding_curve: BondingCurve::Linear {
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
                 spot_price: Uint128::from(10_100_000u128),
                 delta: Uint128::from(1_000_000u128),
             },
-            is_active: true,
-            asset_recipient: None,
-        },
+        ),
         100u64,
         Uint128::zero(),
     );
@@ -127,4 +130,398 @@ fn try_router_tokens_for_nfts_swap_simple() {
         &[coin(total_tokens.u128(), NATIVE_DENOM)],
     );
     assert!(response.is_ok());
+
+    // The router response data describes each swap
+    let data = parse_execute_response_data(&response.unwrap().data.unwrap()).unwrap().data;
+    let swap_response = from_binary::<SwapResponse>(&data.unwrap()).unwrap();
+    assert_eq!(swap_response.swaps.len(), quotes.len());
+    for (swap, quote) in swap_response.swaps.iter().zip(quotes.iter()) {
+        assert_eq!(swap.pair, quote.address);
+        assert_eq!(swap.transaction_type, TransactionType::UserSubmitsTokens);
+        assert_eq!(swap.price, coin(quote.amount.u128(), NATIVE_DENOM));
+        assert_eq!(
+            swap.price.amount,
+            swap.fair_burn_fee + swap.royalty_fee + swap.swap_fee + swap.seller_amount
+        );
+    }
+}
+
+#[test]
+fn try_router_tokens_for_nfts_swap_referral() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let _test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        10u64,
+        Uint128::zero(),
+    );
+
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::TokensForNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 2,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+
+    let referrer = Addr::unchecked("referrer");
+    let referral_fee_percent = Decimal::percent(1);
+    let referral_fees =
+        quotes.iter().map(|q| q.amount.mul_ceil(referral_fee_percent)).collect::<Vec<Uint128>>();
+    let max_inputs =
+        quotes.iter().zip(&referral_fees).map(|(q, f)| q.amount + f).collect::<Vec<Uint128>>();
+    let total_tokens = max_inputs.iter().sum::<Uint128>();
+
+    // Cannot exceed the max referral fee percent
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs: max_inputs.clone(),
+            swap_params: Some(SwapParams {
+                robust: None,
+                asset_recipient: None,
+                referrer: Some(referrer.to_string()),
+                referral_fee_percent: Some(
+                    global_config.max_referral_fee_percent + Decimal::percent(1),
+                ),
+                callback: None,
+            }),
+            filter_sources: None,
+        },
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_err());
+
+    // Cannot pay the quote without the referral fee on top
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs: quotes.iter().map(|q| q.amount).collect(),
+            swap_params: Some(SwapParams {
+                robust: None,
+                asset_recipient: None,
+                referrer: Some(referrer.to_string()),
+                referral_fee_percent: Some(referral_fee_percent),
+                callback: None,
+            }),
+            filter_sources: None,
+        },
+        &[coin(quotes.iter().map(|q| q.amount).sum::<Uint128>().u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_err());
+
+    let response = router.execute_contract(
+        bidder,
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs,
+            swap_params: Some(SwapParams {
+                robust: None,
+                asset_recipient: None,
+                referrer: Some(referrer.to_string()),
+                referral_fee_percent: Some(referral_fee_percent),
+                callback: None,
+            }),
+            filter_sources: None,
+        },
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let referrer_balance = router.wrap().query_balance(&referrer, NATIVE_DENOM).unwrap();
+    assert_eq!(referrer_balance.amount, referral_fees.iter().sum::<Uint128>());
+}
+
+#[test]
+fn try_router_tokens_for_nfts_swap_nft_price() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        3u64,
+        Uint128::zero(),
+    );
+
+    // The last NFT is listed at a fixed price below the pair price
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SetNftPrices {
+            token_ids: vec![test_pair.token_ids[2].clone()],
+            nft_price: Some(NftPrice::Fixed(Uint128::from(5_000_000u128))),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // The indexed quote is the fixed price, followed by the pair price
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::TokensForNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 2,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        quotes.iter().map(|q| q.amount).collect::<Vec<Uint128>>(),
+        vec![Uint128::from(5_300_000u128), Uint128::from(11_660_000u128)]
+    );
+
+    let max_inputs = quotes.iter().map(|q| q.amount).collect::<Vec<Uint128>>();
+    let total_tokens = max_inputs.iter().sum::<Uint128>();
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    // The listed NFT is bought first, at the indexed quote
+    let data = parse_execute_response_data(&response.unwrap().data.unwrap()).unwrap().data;
+    let swap_response = from_binary::<SwapResponse>(&data.unwrap()).unwrap();
+    assert_eq!(swap_response.swaps[0].token_id, test_pair.token_ids[2]);
+    assert_eq!(swap_response.swaps[0].price, coin(5_300_000u128, NATIVE_DENOM));
+    assert_eq!(swap_response.swaps[1].token_id, test_pair.token_ids[0]);
+    assert_eq!(swap_response.swaps[1].price, coin(11_660_000u128, NATIVE_DENOM));
+    assert_nft_owner(&router, &collection, test_pair.token_ids[2].clone(), &bidder);
+    assert_nft_owner(&router, &collection, test_pair.token_ids[0].clone(), &bidder);
+}
+
+#[test]
+fn try_router_tokens_for_nfts_swap_many_nft_prices() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config(
+            PairType::Nft,
+            BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+        ),
+        3u64,
+        Uint128::zero(),
+    );
+
+    // Every NFT is listed, two at fixed prices and one at a premium over the pair price
+    for (token_id, nft_price) in [
+        (&test_pair.token_ids[0], NftPrice::Fixed(Uint128::from(5_000_000u128))),
+        (&test_pair.token_ids[1], NftPrice::Fixed(Uint128::from(7_000_000u128))),
+        (&test_pair.token_ids[2], NftPrice::Premium(Uint128::from(2_000_000u128))),
+    ] {
+        let response = router.execute_contract(
+            owner.clone(),
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SetNftPrices {
+                token_ids: vec![token_id.clone()],
+                nft_price: Some(nft_price),
+            },
+            &[],
+        );
+        assert!(response.is_ok());
+    }
+
+    // Each simulated swap is quoted with the cheapest NFT that remains in the pair
+    let expected_quotes = vec![
+        Uint128::from(5_300_000u128),
+        Uint128::from(7_420_000u128),
+        Uint128::from(14_840_000u128),
+    ];
+
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::TokensForNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 3,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(quotes.iter().map(|q| q.amount).collect::<Vec<Uint128>>(), expected_quotes);
+
+    let sim_quotes = router
+        .wrap()
+        .query_wasm_smart::<QuotesResponse>(
+            &test_pair.address,
+            &InfinityPairQueryMsg::SimBuyFromPairSwaps {
+                limit: 3,
+            },
+        )
+        .unwrap();
+    assert_eq!(sim_quotes.buy_from_pair_quotes, expected_quotes);
+
+    let total_tokens = expected_quotes.iter().sum::<Uint128>();
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs: expected_quotes.clone(),
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    // The NFTs are bought from the cheapest to the most expensive, at the simulated quotes
+    let data = parse_execute_response_data(&response.unwrap().data.unwrap()).unwrap().data;
+    let swap_response = from_binary::<SwapResponse>(&data.unwrap()).unwrap();
+    assert_eq!(
+        swap_response.swaps.iter().map(|s| s.token_id.clone()).collect::<Vec<String>>(),
+        test_pair.token_ids
+    );
+    assert_eq!(
+        swap_response.swaps.iter().map(|s| s.price.amount).collect::<Vec<Uint128>>(),
+        expected_quotes
+    );
+    for token_id in test_pair.token_ids {
+        assert_nft_owner(&router, &collection, token_id, &bidder);
+    }
 }
@@ -1,10 +1,14 @@
 #[cfg(test)]
 mod helpers;
 #[cfg(test)]
+mod infinity_builder_tests;
+#[cfg(test)]
 mod infinity_factory_tests;
 #[cfg(test)]
 mod infinity_global_tests;
 #[cfg(test)]
+mod infinity_index_tests;
+#[cfg(test)]
 mod infinity_pair_tests;
 #[cfg(test)]
 mod infinity_router_tests;
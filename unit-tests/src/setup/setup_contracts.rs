@@ -1,9 +1,14 @@
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo,
+    StdError, StdResult, Uint128,
+};
 use cw_multi_test::{Contract, ContractWrapper, Executor};
 use cw_utils::Duration;
+use infinity_router::msg::ReceiveSwapCallbackMsg;
 use sg_marketplace::ExpiryRange;
 use sg_multi_test::StargazeApp;
-use sg_std::StargazeMsgWrapper;
+use sg_std::{Response, StargazeMsgWrapper, NATIVE_DENOM};
 use stargaze_fair_burn::msg::InstantiateMsg as FairBurnInstantiateMsg;
 use stargaze_royalty_registry::{
     msg::InstantiateMsg as RoyaltyRegistryInstantiateMsg, state::Config as RoyaltyRegistryConfig,
@@ -95,3 +100,70 @@ pub fn setup_marketplace(router: &mut StargazeApp, creator: &Addr) -> Addr {
         .instantiate_contract(marketplace_id, creator.clone(), &msg, &[], "Marketplace", None)
         .unwrap()
 }
+
+/// The callback messages handled by a mock contract used as the target of router swap callbacks
+#[cw_serde]
+pub enum MockCallbackExecuteMsg {
+    /// Emits a `mock-callback` event with the balance of the contract
+    Succeed {},
+    /// Fails the callback
+    Fail {},
+}
+
+#[cw_serde]
+enum MockCallbackContractExecuteMsg {
+    ReceiveSwapCallback(ReceiveSwapCallbackMsg),
+}
+
+fn mock_callback_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn mock_callback_execute(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: MockCallbackContractExecuteMsg,
+) -> StdResult<Response> {
+    let MockCallbackContractExecuteMsg::ReceiveSwapCallback(receive_msg) = msg;
+    match from_binary::<MockCallbackExecuteMsg>(&receive_msg.msg)? {
+        MockCallbackExecuteMsg::Succeed {} => {
+            let balance = deps.querier.query_balance(&env.contract.address, NATIVE_DENOM)?;
+            Ok(Response::new().add_event(
+                Event::new("mock-callback")
+                    .add_attribute("sender", receive_msg.sender)
+                    .add_attribute("balance", balance.amount),
+            ))
+        },
+        MockCallbackExecuteMsg::Fail {} => Err(StdError::generic_err("mock callback failed")),
+    }
+}
+
+fn mock_callback_query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_binary(&Empty {})
+}
+
+pub fn contract_mock_callback() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract =
+        ContractWrapper::new(mock_callback_execute, mock_callback_instantiate, mock_callback_query);
+    Box::new(contract)
+}
+
+pub fn setup_mock_callback(router: &mut StargazeApp, creator: &Addr) -> Addr {
+    let mock_callback_id = router.store_code(contract_mock_callback());
+    router
+        .instantiate_contract(
+            mock_callback_id,
+            creator.clone(),
+            &Empty {},
+            &[],
+            "MockCallback",
+            None,
+        )
+        .unwrap()
+}
@@ -12,7 +12,8 @@ pub fn contract_infinity_global() -> Box<dyn Contract<StargazeMsgWrapper>> {
         infinity_global::instantiate::instantiate,
         infinity_global::query::query,
     )
-    .with_sudo(infinity_global::sudo::sudo);
+    .with_sudo(infinity_global::sudo::sudo)
+    .with_migrate(infinity_global::migrate::migrate);
     Box::new(contract)
 }
 
@@ -43,6 +44,8 @@ pub fn setup_infinity_global(
             default_royalty_fee_percent: Decimal::percent(5),
             max_royalty_fee_percent: Decimal::percent(10),
             max_swap_fee_percent: Decimal::percent(5),
+            max_referral_fee_percent: Decimal::percent(2),
+            paused: false,
         },
         min_prices: vec![coin(10u128, NATIVE_DENOM), coin(10u128, UOSMO)],
     };
@@ -64,7 +67,9 @@ pub fn contract_infinity_factory() -> Box<dyn Contract<StargazeMsgWrapper>> {
         infinity_factory::instantiate::instantiate,
         infinity_factory::query::query,
     )
-    .with_sudo(infinity_factory::sudo::sudo);
+    .with_sudo(infinity_factory::sudo::sudo)
+    .with_reply(infinity_factory::reply::reply)
+    .with_migrate(infinity_factory::migrate::migrate);
     Box::new(contract)
 }
 
@@ -124,7 +129,8 @@ pub fn contract_infinity_router() -> Box<dyn Contract<StargazeMsgWrapper>> {
         infinity_router::execute::execute,
         infinity_router::instantiate::instantiate,
         infinity_router::query::query,
-    );
+    )
+    .with_reply(infinity_router::reply::reply);
     Box::new(contract)
 }
 
@@ -154,6 +160,7 @@ pub fn contract_infinity_pair() -> Box<dyn Contract<StargazeMsgWrapper>> {
         infinity_pair::execute::execute,
         infinity_pair::instantiate::instantiate,
         infinity_pair::query::query,
-    );
+    )
+    .with_migrate(infinity_pair::migrate::migrate);
     Box::new(contract)
 }
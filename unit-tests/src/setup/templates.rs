@@ -99,7 +99,7 @@ pub struct InfinityTestSetup {
     pub infinity_pair_code_id: u64,
 }
 
-fn increment_number_in_string(s: &str, increment: u32) -> String {
+pub fn increment_number_in_string(s: &str, increment: u32) -> String {
     let prefix: String = s.chars().take_while(|c| c.is_alphabetic()).collect();
     let number: String = s.chars().skip_while(|c| c.is_alphabetic()).collect();
     let incremented_number = number.parse::<u32>().unwrap_or(0) + increment;
@@ -115,8 +115,7 @@ pub fn setup_infinity_test(
     let royalty_registry = setup_royalty_registry(&mut vt.router, &vt.accts.creator);
     let marketplace = setup_marketplace(&mut vt.router, &vt.accts.creator.clone());
 
-    let pre_infinity_global =
-        Addr::unchecked(increment_number_in_string(marketplace.as_ref(), 4));
+    let pre_infinity_global = Addr::unchecked(increment_number_in_string(marketplace.as_ref(), 4));
 
     let infinity_factory =
         setup_infinity_factory(&mut vt.router, &vt.accts.creator.clone(), &pre_infinity_global);